@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS recipe_images
+(
+  id                  INTEGER NOT NULL PRIMARY KEY,
+  recipe_id           INTEGER NOT NULL REFERENCES recipes (id) ON DELETE CASCADE,
+  mime_type           TEXT NOT NULL,
+  position            INTEGER NOT NULL DEFAULT 0
+);
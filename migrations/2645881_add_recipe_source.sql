@@ -0,0 +1 @@
+ALTER TABLE recipes ADD COLUMN recipe_source TEXT NOT NULL DEFAULT '{}';
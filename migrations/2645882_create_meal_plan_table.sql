@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS meal_plan
+(
+  id                  INTEGER NOT NULL PRIMARY KEY,
+  plan_date           TEXT NOT NULL,
+  meal_slot           TEXT NOT NULL,
+  recipe_id           INTEGER NOT NULL REFERENCES recipes (id) ON DELETE CASCADE,
+  servings            INTEGER NOT NULL DEFAULT 2,
+  position            INTEGER NOT NULL DEFAULT 0
+);
+CREATE INDEX IF NOT EXISTS meal_plan_date ON meal_plan (plan_date);
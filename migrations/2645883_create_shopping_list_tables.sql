@@ -0,0 +1,19 @@
+CREATE TABLE IF NOT EXISTS shopping_lists
+(
+  id                  INTEGER NOT NULL PRIMARY KEY,
+  title               TEXT NOT NULL,
+  created_at          TEXT NOT NULL,
+  version             INTEGER NOT NULL DEFAULT 0
+);
+CREATE TABLE IF NOT EXISTS shopping_list_items
+(
+  id                  INTEGER NOT NULL PRIMARY KEY,
+  list_id             INTEGER NOT NULL REFERENCES shopping_lists (id) ON DELETE CASCADE,
+  aisle               TEXT NOT NULL,
+  item_name           TEXT NOT NULL,
+  quantity            TEXT,
+  recipe_names        TEXT NOT NULL DEFAULT '[]',
+  is_verbatim         INTEGER NOT NULL DEFAULT 0,
+  checked             INTEGER NOT NULL DEFAULT 0,
+  position            INTEGER NOT NULL DEFAULT 0
+);
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS pantry_items
+(
+  id                  INTEGER NOT NULL PRIMARY KEY,
+  item_name           TEXT NOT NULL,
+  quantity            TEXT,
+  expires_on          TEXT
+);
@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS cook_log
+(
+  id                  INTEGER NOT NULL PRIMARY KEY,
+  recipe_id           INTEGER NOT NULL REFERENCES recipes (id) ON DELETE CASCADE,
+  cooked_on           TEXT NOT NULL,
+  username            TEXT NOT NULL,
+  rating              INTEGER,
+  comment             TEXT NOT NULL DEFAULT ''
+);
+CREATE INDEX IF NOT EXISTS cook_log_recipe ON cook_log (recipe_id);
@@ -0,0 +1,19 @@
+CREATE TABLE IF NOT EXISTS favorites
+(
+  username            TEXT NOT NULL,
+  recipe_id           INTEGER NOT NULL REFERENCES recipes (id) ON DELETE CASCADE,
+  PRIMARY KEY (username, recipe_id)
+);
+CREATE TABLE IF NOT EXISTS collections
+(
+  id                  INTEGER NOT NULL PRIMARY KEY,
+  username            TEXT NOT NULL,
+  collection_name     TEXT NOT NULL,
+  UNIQUE (username, collection_name)
+);
+CREATE TABLE IF NOT EXISTS collection_recipes
+(
+  collection_id       INTEGER NOT NULL REFERENCES collections (id) ON DELETE CASCADE,
+  recipe_id           INTEGER NOT NULL REFERENCES recipes (id) ON DELETE CASCADE,
+  PRIMARY KEY (collection_id, recipe_id)
+);
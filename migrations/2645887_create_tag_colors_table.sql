@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS tag_colors
+(
+  tag_name            TEXT NOT NULL PRIMARY KEY,
+  color               TEXT NOT NULL
+);
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS tag_parents
+(
+  tag_name            TEXT NOT NULL PRIMARY KEY,
+  parent_name         TEXT NOT NULL
+);
@@ -4,9 +4,17 @@ use crate::app::{
             auth_server_functions::{server_login_check, server_try_login},
             auth_utils::LoginAccount,
         },
+        backup::{apply_backup_file, RestoreProblem, RestoreReport},
+        collections::{collection_function, get_user_collections, CollectionActionDescriptor, CollectionFilter, UserCollections},
+        cook_log::{cook_log_function, get_cook_stats, CookLogActionDescriptor, CookStats},
+        cooking_timers::{CookingTimer, CookingTimersPanel},
+        meal_planner::{meal_plan_function, MealPlanActionDescriptor},
         pages::*,
+        pantry::{pantry_function, PantryActionDescriptor},
         recipe::*,
-        recipe_server_functions::{apply_json_save, get_all_recipes_light, recipe_function},
+        recipe_server_functions::{get_all_recipes_light, recipe_function},
+        shopping_list::{shopping_list_function, ShoppingListActionDescriptor},
+        tags::{get_tag_usages, tag_function, TagActionDescriptor, TagUsage},
     },
     elements::popups::*,
 };
@@ -17,7 +25,9 @@ use leptos::prelude::*;
 use leptos_meta::*;
 use leptos_router::components::{Router, Routes, Route};
 use leptos_router::path;
+use leptos_router::SsrMode;
 use rand::{seq::SliceRandom, rng};
+use web_sys::FormData;
 
 pub mod components;
 pub mod elements;
@@ -33,14 +43,32 @@ pub struct IsSettingsMenuOpen(RwSignal<bool>);
 #[derive(Clone)]
 pub struct IsTagsMenuOpen(RwSignal<bool>);
 #[derive(Clone)]
-pub struct ApplySaveFromJson(Action<String, bool>);
+pub struct ApplySaveFromJson(Action<FormData, RestoreReport, LocalStorage>);
 #[derive(Clone)]
 pub struct TryLoginAction(Action<LoginAccount, bool>);
 #[derive(Clone)]
 pub struct RecipeServerAction(Action<RecipeActionDescriptor, Result<(), ServerFnError>>);
 #[derive(Clone)]
+pub struct CollectionServerAction(Action<CollectionActionDescriptor, Result<(), ServerFnError>>);
+#[derive(Clone)]
+pub struct CookLogServerAction(Action<CookLogActionDescriptor, Result<(), ServerFnError>>);
+#[derive(Clone)]
+pub struct MealPlanServerAction(Action<MealPlanActionDescriptor, Result<(), ServerFnError>>);
+#[derive(Clone)]
+pub struct PantryServerAction(Action<PantryActionDescriptor, Result<(), ServerFnError>>);
+#[derive(Clone)]
+pub struct ShoppingListServerAction(Action<ShoppingListActionDescriptor, Result<(), ServerFnError>>);
+#[derive(Clone)]
+pub struct TagServerAction(Action<TagActionDescriptor, Result<(), ServerFnError>>);
+#[derive(Clone)]
 pub struct RecipesLightResource(Resource<std::result::Result<Vec<RecipeLight>, ServerFnError>>);
 #[derive(Clone)]
+pub struct CookStatsResource(Resource<std::result::Result<Vec<CookStats>, ServerFnError>>);
+#[derive(Clone)]
+pub struct TagUsagesResource(Resource<std::result::Result<Vec<TagUsage>, ServerFnError>>);
+#[derive(Clone)]
+pub struct UserCollectionsResource(Resource<std::result::Result<UserCollections, ServerFnError>>);
+#[derive(Clone)]
 pub struct RecipesColorMap(Memo<Vec<ThemeColor>>);
 #[derive(Clone)]
 pub struct ShuffleColors(RwSignal<bool>);
@@ -51,7 +79,11 @@ pub struct AllTagsSignal(RwSignal<Vec<String>>);
 #[derive(Clone)]
 pub struct SelectedTagsRwSignal(RwSignal<Vec<String>>);
 #[derive(Clone)]
+pub struct SelectedCollectionSignal(RwSignal<CollectionFilter>);
+#[derive(Clone)]
 pub struct DeleteInfoSignal(RwSignal<Option<DeletePopupInfo>>);
+#[derive(Clone)]
+pub struct CookingTimersSignal(pub RwSignal<Vec<CookingTimer>>);
 
 #[component]
 pub fn App() -> impl IntoView {
@@ -69,6 +101,36 @@ pub fn App() -> impl IntoView {
         Action::new(|desc: &RecipeActionDescriptor| recipe_function(desc.clone()));
     provide_context(RecipeServerAction(recipe_action));
 
+    // Tag Action
+    let tag_action =
+        Action::new(|desc: &TagActionDescriptor| tag_function(desc.clone()));
+    provide_context(TagServerAction(tag_action));
+
+    // Collection Action
+    let collection_action =
+        Action::new(|desc: &CollectionActionDescriptor| collection_function(desc.clone()));
+    provide_context(CollectionServerAction(collection_action));
+
+    // Cook Log Action
+    let cook_log_action =
+        Action::new(|desc: &CookLogActionDescriptor| cook_log_function(desc.clone()));
+    provide_context(CookLogServerAction(cook_log_action));
+
+    // Meal Plan Action
+    let meal_plan_action =
+        Action::new(|desc: &MealPlanActionDescriptor| meal_plan_function(desc.clone()));
+    provide_context(MealPlanServerAction(meal_plan_action));
+
+    // Shopping List Action
+    let shopping_list_action =
+        Action::new(|desc: &ShoppingListActionDescriptor| shopping_list_function(desc.clone()));
+    provide_context(ShoppingListServerAction(shopping_list_action));
+
+    // Pantry Action
+    let pantry_action =
+        Action::new(|desc: &PantryActionDescriptor| pantry_function(desc.clone()));
+    provide_context(PantryServerAction(pantry_action));
+
     // Settings Menu
     let is_settings_menu_open = RwSignal::new(false);
     provide_context(IsSettingsMenuOpen(is_settings_menu_open));
@@ -122,16 +184,17 @@ pub fn App() -> impl IntoView {
     );
     provide_context(LoginCheckResource(login_check_resource));
 
-    // Apply save from JSON
-    let upload_save_action = Action::new(|save: &String| {
-        let save = save.to_string();
+    // Apply save from the uploaded JSON file
+    // FormData is not Send, so this action must stay local
+    let upload_save_action = Action::new_local(|data: &FormData| {
+        let data = data.clone();
         async move {
-            match apply_json_save(save).await {
+            match apply_backup_file(data.into()).await {
                 Err(e) => {
                     error!("ERROR: {:?}", e.to_string());
-                    false
+                    Err(vec![RestoreProblem { recipe: None, message: e.to_string() }])
                 }
-                _ => true,
+                Ok(report) => report,
             }
         }
     });
@@ -143,6 +206,7 @@ pub fn App() -> impl IntoView {
             (
                 recipe_action.version().get(),
                 upload_save_action.version().get(),
+                tag_action.version().get(),
             )
         },
         move |_| {
@@ -151,6 +215,49 @@ pub fn App() -> impl IntoView {
     );
     provide_context(RecipesLightResource(all_recipe_light));
 
+    // Tag usages and colors
+    let tag_usages: Resource<std::result::Result<Vec<TagUsage>, ServerFnError>> = Resource::new(
+        move || {
+            (
+                recipe_action.version().get(),
+                upload_save_action.version().get(),
+                tag_action.version().get(),
+            )
+        },
+        move |_| {
+            get_tag_usages()
+        },
+    );
+    provide_context(TagUsagesResource(tag_usages));
+
+    // Cook Stats resource, deleted recipes leave the log with them
+    let cook_stats: Resource<std::result::Result<Vec<CookStats>, ServerFnError>> = Resource::new(
+        move || {
+            (
+                cook_log_action.version().get(),
+                recipe_action.version().get(),
+            )
+        },
+        move |_| {
+            get_cook_stats()
+        },
+    );
+    provide_context(CookStatsResource(cook_stats));
+
+    // Favorites and collections of the logged in user
+    let user_collections: Resource<std::result::Result<UserCollections, ServerFnError>> = Resource::new(
+        move || {
+            (
+                collection_action.version().get(),
+                login_check_resource.get(),
+            )
+        },
+        move |_| {
+            get_user_collections()
+        },
+    );
+    provide_context(UserCollectionsResource(user_collections));
+
 
 
     // Colors
@@ -252,12 +359,20 @@ pub fn App() -> impl IntoView {
     let selected_tags = RwSignal::<Vec<String>>::new(vec![]);
     provide_context(SelectedTagsRwSignal(selected_tags));
 
+    // Selected Collection
+    let selected_collection = RwSignal::new(CollectionFilter::default());
+    provide_context(SelectedCollectionSignal(selected_collection));
+
 
 
     // Delete Infos: If this is Some(id), then display the popup that will delete the recipe with this id
     let delete_popup_info = RwSignal::<Option<DeletePopupInfo>>::new(None);
     provide_context(DeleteInfoSignal(delete_popup_info));
 
+    // Cooking Timers: provided here so they keep counting from page to page
+    let cooking_timers = RwSignal::new(Vec::<CookingTimer>::new());
+    provide_context(CookingTimersSignal(cooking_timers));
+
 
 
     view! {
@@ -278,11 +393,19 @@ pub fn App() -> impl IntoView {
                 <Routes fallback=|| "Not found.">
                     <Route path=path!("/")                     view=AllRecipes />
                     <Route path=path!("/new-recipe")           view=NewRecipePage />
-                    <Route path=path!("/recipe/:id/:mode")     view=RecipePage />
+                    // Waits for the recipe, so its JSON-LD is in the served <head>
+                    <Route path=path!("/recipe/:id/:mode")     view=RecipePage ssr=SsrMode::Async />
                     <Route path=path!("/backup")               view=BackupPage />
+                    <Route path=path!("/planner")              view=PlannerPage />
+                    <Route path=path!("/planner/:week")        view=PlannerPage />
+                    <Route path=path!("/shopping-list")        view=ShoppingListPage />
+                    <Route path=path!("/pantry")               view=PantryPage />
+                    <Route path=path!("/tags")                 view=TagsPage />
                     <Route path=path!("/*")                    view=NotFound />
                 </Routes>
 
+                <CookingTimersPanel/>
+
             </main>
 
         </Router>
@@ -134,6 +134,31 @@ pub async fn check_login() -> Result<bool, ServerFnError> {
     Ok(is_logged_in)
 }
 
+/// The username logged in from the request ip, None if not logged in
+#[cfg(feature = "ssr")]
+pub async fn logged_in_username() -> Result<Option<String>, ServerFnError> {
+    // Also refreshes the login date
+    if !check_login().await? {
+        return Ok(None);
+    }
+
+    // Fetch request ip
+    let cur_ip = fetch_request_ip().await?;
+
+    // Fetch state
+    let shared_login_states: Data<SharedLoginStates> =
+        leptos_actix::extract::<Data<SharedLoginStates>>().await?;
+    let shared_login_states = shared_login_states.get_ref().states.clone();
+    let shared_login_states_lock = shared_login_states.lock()?;
+
+    // The latest login of this ip
+    Ok(shared_login_states_lock
+        .iter()
+        .rev()
+        .find(|logged_user| logged_user.current_ip == cur_ip)
+        .map(|logged_user| logged_user.username.clone()))
+}
+
 #[cfg(feature = "ssr")]
 pub async fn log_in_user(submission: &LoginAccount) -> Result<bool, ServerFnError> {
     log!("Attempt to log in user {:?}", submission.username);
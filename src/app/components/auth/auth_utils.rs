@@ -50,6 +50,22 @@ impl SharedLoginStates {
             states: Arc::new(Mutex::new(vec![])),
         }
     }
+
+    /// Check if an ip is logged in, without refreshing its login date.
+    /// Used by the actix routes that are outside of the leptos server functions.
+    pub fn is_ip_logged_in(&self, ip: &str) -> bool {
+        let login_duration = std::time::Duration::from_secs(LOG_PERSISTANCE_DURATION_SECONDS);
+        let current_time = SystemTime::now();
+        match self.states.lock() {
+            Ok(states) => states.iter().any(|logged_user| {
+                logged_user.current_ip == ip && logged_user.log_date + login_duration >= current_time
+            }),
+            Err(e) => {
+                error!("ERROR: Login states are poisoned: {:?}", e.to_string());
+                false
+            }
+        }
+    }
 }
 
 #[cfg(feature = "ssr")]
@@ -0,0 +1,306 @@
+use leptos::logging::*;
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Optional settings file of the automatic backups, along with the .exe
+pub const AUTO_BACKUP_CONFIG_FILE_NAME: &str = "hcb_backups.json";
+
+/// A backup written by the server
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutoBackup {
+    pub file_name: String,
+    // Local date of the backup, "2026-10-19 03:00"
+    pub created_at: String,
+    // In bytes
+    pub size: u64,
+}
+impl AutoBackup {
+    pub fn download_url(&self) -> String {
+        format!("/auto-backups/{}", self.file_name)
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use super::*;
+    use crate::app::components::auth::auth_utils::SharedLoginStates;
+    use crate::app::components::backup::ssr::backup_json;
+    use crate::app::components::recipe_server_functions::ssr::db;
+    use actix_web::{http::header, web, HttpRequest, HttpResponse};
+    use chrono::{Datelike, IsoWeek, Local, NaiveDate, NaiveDateTime, TimeDelta};
+    use std::path::{Path, PathBuf};
+
+    const FILE_PREFIX: &str = "cook_book_backup_";
+    const DATE_FORMAT: &str = "%Y-%m-%d--%H-%M-%S";
+    // How often the task checks if a backup is due
+    const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3600);
+
+    /// Settings of the automatic backups, see `AUTO_BACKUP_CONFIG_FILE_NAME`
+    #[derive(Clone, Debug, Deserialize)]
+    #[serde(default)]
+    pub struct AutoBackupConfig {
+        pub enabled: bool,
+        // Where the backups are written
+        pub directory: String,
+        // Time between two backups
+        pub interval_hours: u64,
+        // How many days, then weeks, keep their latest backup
+        pub keep_daily: usize,
+        pub keep_weekly: usize,
+    }
+    impl Default for AutoBackupConfig {
+        fn default() -> Self {
+            AutoBackupConfig {
+                enabled: true,
+                directory: "backups".to_string(),
+                interval_hours: 24,
+                keep_daily: 7,
+                keep_weekly: 4,
+            }
+        }
+    }
+    impl AutoBackupConfig {
+        /// The settings file, or the default settings without it
+        pub fn load() -> AutoBackupConfig {
+            let Ok(file) = std::fs::File::open(AUTO_BACKUP_CONFIG_FILE_NAME) else {
+                return AutoBackupConfig::default();
+            };
+            match serde_json::from_reader(std::io::BufReader::new(file)) {
+                Ok(config) => config,
+                Err(e) => {
+                    error!("ERROR: Could not read {:?}, using the default settings: {:?}", AUTO_BACKUP_CONFIG_FILE_NAME, e.to_string());
+                    AutoBackupConfig::default()
+                }
+            }
+        }
+    }
+
+    // Only the names written by `write_backup`, so a file name can never be a path
+    fn backup_date(file_name: &str) -> Option<NaiveDateTime> {
+        if file_name.contains(['/', '\\']) {
+            return None;
+        }
+        let date = file_name.strip_prefix(FILE_PREFIX)?.strip_suffix(".json")?;
+        NaiveDateTime::parse_from_str(date, DATE_FORMAT).ok()
+    }
+
+    /// The backups of the directory, newest first
+    pub fn list_backups(directory: &str) -> Vec<(NaiveDateTime, PathBuf)> {
+        let Ok(entries) = std::fs::read_dir(directory) else {
+            return vec![];
+        };
+        let mut backups: Vec<(NaiveDateTime, PathBuf)> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| Some((backup_date(entry.file_name().to_str()?)?, entry.path())))
+            .collect();
+        backups.sort_by(|(a, _), (b, _)| b.cmp(a));
+        backups
+    }
+
+    /// For each backup date, newest first, if it is kept:
+    /// the newest one, and the latest of each of the last days and weeks
+    pub fn kept_backups(dates: &[NaiveDateTime], keep_daily: usize, keep_weekly: usize) -> Vec<bool> {
+        let mut days: Vec<NaiveDate> = vec![];
+        let mut weeks: Vec<IsoWeek> = vec![];
+        dates
+            .iter()
+            .enumerate()
+            .map(|(index, date)| {
+                let day = date.date();
+                let new_day = days.len() < keep_daily && !days.contains(&day);
+                if new_day {
+                    days.push(day);
+                }
+                let week = date.iso_week();
+                let new_week = weeks.len() < keep_weekly && !weeks.contains(&week);
+                if new_week {
+                    weeks.push(week);
+                }
+                index == 0 || new_day || new_week
+            })
+            .collect()
+    }
+
+    /// Write a JSON backup of every recipe now
+    pub async fn write_backup(config: &AutoBackupConfig) -> Result<PathBuf, ServerFnError> {
+        let mut conn = db().await?;
+        let content = backup_json(&mut conn).await?;
+
+        std::fs::create_dir_all(&config.directory)?;
+        let file_name = format!("{}{}.json", FILE_PREFIX, Local::now().format(DATE_FORMAT));
+        let path = Path::new(&config.directory).join(file_name);
+        // Renamed once written, a crash never leaves half a backup in the list
+        let temp_path = path.with_extension("json.tmp");
+        std::fs::write(&temp_path, content)?;
+        std::fs::rename(&temp_path, &path)?;
+        Ok(path)
+    }
+
+    /// Remove the backups that are not kept anymore
+    pub fn prune_backups(config: &AutoBackupConfig) {
+        let backups = list_backups(&config.directory);
+        let dates: Vec<NaiveDateTime> = backups.iter().map(|(date, _)| *date).collect();
+        let kept = kept_backups(&dates, config.keep_daily, config.keep_weekly);
+        for ((_, path), keep) in backups.iter().zip(kept) {
+            if keep {
+                continue;
+            }
+            match std::fs::remove_file(path) {
+                Ok(_) => log!("Old backup {:?} was removed", path),
+                Err(e) => error!("ERROR: Could not remove the old backup {:?}: {:?}", path, e.to_string()),
+            }
+        }
+    }
+
+    /// Background task of the server, writes a backup when the latest one is too old
+    pub async fn run_auto_backups(config: AutoBackupConfig) {
+        if !config.enabled {
+            log!("Automatic backups are disabled");
+            return;
+        }
+        log!("Automatic backups every {:?} hours in {:?}", config.interval_hours, config.directory);
+
+        let interval = TimeDelta::hours(config.interval_hours.max(1) as i64);
+        loop {
+            let latest = list_backups(&config.directory).first().map(|(date, _)| *date);
+            let is_due = latest.is_none_or(|date| Local::now().naive_local() - date >= interval);
+            if is_due {
+                match write_backup(&config).await {
+                    Ok(path) => log!("Automatic backup {:?} was written", path),
+                    Err(e) => error!("ERROR: Could not write the automatic backup: {:?}", e.to_string()),
+                }
+                prune_backups(&config);
+            }
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    }
+
+    /// The content of an automatic backup
+    pub fn read_auto_backup(file_name: &str) -> Result<String, String> {
+        if backup_date(file_name).is_none() {
+            return Err(format!("Unknown backup: {}", file_name));
+        }
+        let config = AutoBackupConfig::load();
+        std::fs::read_to_string(Path::new(&config.directory).join(file_name)).map_err(|e| e.to_string())
+    }
+
+    /// Download of an automatic backup, only for logged in users
+    #[actix_web::get("/auto-backups/{file_name}")]
+    pub async fn serve_auto_backup(
+        req: HttpRequest,
+        path: web::Path<String>,
+        login_states: web::Data<SharedLoginStates>,
+    ) -> HttpResponse {
+        let is_logged_in = req
+            .connection_info()
+            .realip_remote_addr()
+            .is_some_and(|ip| login_states.is_ip_logged_in(ip));
+        if !is_logged_in {
+            return HttpResponse::Unauthorized().body("Not logged in.");
+        }
+
+        let file_name = path.into_inner();
+        let content = match read_auto_backup(&file_name) {
+            Ok(content) => content,
+            Err(e) => {
+                error!("ERROR: Could not read the automatic backup {:?}: {:?}", file_name, e);
+                return HttpResponse::NotFound().body("Backup not found.");
+            }
+        };
+
+        HttpResponse::Ok()
+            .content_type("application/json; charset=utf-8")
+            .insert_header((
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", file_name),
+            ))
+            .body(content)
+    }
+}
+
+/// The automatic backups, newest first
+#[server]
+pub async fn get_auto_backups() -> Result<Vec<AutoBackup>, ServerFnError> {
+    use self::ssr::*;
+    use crate::app::components::auth::auth_server_functions::check_login;
+
+    if !check_login().await? {
+        return Err(ServerFnError::ServerError("Not logged in.".to_string()));
+    }
+
+    let config = AutoBackupConfig::load();
+    Ok(list_backups(&config.directory)
+        .into_iter()
+        .filter_map(|(date, path)| {
+            Some(AutoBackup {
+                file_name: path.file_name()?.to_str()?.to_string(),
+                created_at: date.format("%Y-%m-%d %H:%M").to_string(),
+                size: std::fs::metadata(&path).map(|metadata| metadata.len()).unwrap_or(0),
+            })
+        })
+        .collect())
+}
+
+/// The backups written by the server, to download or restore one
+#[component]
+pub fn AutoBackupList(auto_backup: RwSignal<Option<String>>) -> impl IntoView {
+    let backups = Resource::new(
+        || (),
+        |_| async move {
+            get_auto_backups().await.map_err(|e| {
+                error!("{:?}", e.to_string());
+                e.to_string()
+            })
+        },
+    );
+
+    view! {
+        <div class="auto-backups">
+            <h3 class="auto-backups-title">"Automatic backups"</h3>
+            <Suspense
+                fallback=move || view! { <p>"Loading backups..."</p> }
+            >
+                {move || match backups.get() {
+                    Some(Ok(list)) if list.is_empty() => view! {
+                        <p class="auto-backups-info">"No automatic backup yet."</p>
+                    }.into_any(),
+                    Some(Ok(list)) => view! {
+                        <ul class="auto-backups-list">
+                            {list.into_iter().map(|backup| {
+                                let file_name = backup.file_name.clone();
+                                let is_selected = {
+                                    let file_name = file_name.clone();
+                                    move || auto_backup.read().as_ref() == Some(&file_name)
+                                };
+                                view! {
+                                    <li
+                                        class="auto-backup"
+                                        class:selected=is_selected
+                                    >
+                                        <span class="auto-backup-date">{ backup.created_at.clone() }</span>
+                                        <span class="auto-backup-size">{ format!("{} kB", backup.size.div_ceil(1024)) }</span>
+                                        <a
+                                            class="auto-backup-button"
+                                            href=backup.download_url()
+                                            download=""
+                                        >
+                                            "Download"
+                                        </a>
+                                        <button
+                                            class="auto-backup-button"
+                                            on:click=move |_| auto_backup.set(Some(file_name.clone()))
+                                        >
+                                            "Restore"
+                                        </button>
+                                    </li>
+                                }
+                            }).collect_view()}
+                        </ul>
+                    }.into_any(),
+                    Some(Err(e)) => view! { <p class="auto-backups-info">{e}</p> }.into_any(),
+                    None => ().into_any(),
+                }}
+            </Suspense>
+        </div>
+    }
+}
@@ -0,0 +1,560 @@
+use crate::app::components::recipe::JsonRecipe;
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use server_fn::codec::{MultipartData, MultipartFormData};
+
+/// Maximum size of an uploaded backup (20 MB)
+pub const MAX_BACKUP_SIZE: usize = 20 * 1024 * 1024;
+
+/// The JSON backup of every recipe, as a file
+pub const BACKUP_DOWNLOAD_URL: &str = "/backup-download";
+
+/// Version of the backup files written by this app
+/// 1: a bare list of recipes
+/// 2: the recipes in an envelope, with the export date and app version
+pub const BACKUP_FORMAT_VERSION: u32 = 2;
+
+/// A backup file
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonBackup {
+    pub format_version: u32,
+    // RFC 3339 date, empty for backups older than the envelope
+    pub exported_at: String,
+    // Version of the app that wrote the backup, empty for backups older than the envelope
+    pub app_version: String,
+    pub recipes: Vec<JsonRecipe>,
+}
+impl JsonBackup {
+    /// A backup of the recipes, made now by this app
+    pub fn new(recipes: Vec<JsonRecipe>) -> JsonBackup {
+        JsonBackup {
+            format_version: BACKUP_FORMAT_VERSION,
+            exported_at: chrono::Local::now().to_rfc3339(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            recipes,
+        }
+    }
+}
+
+/// What to do with a backup recipe named like an existing one
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MergeConflict {
+    #[default]
+    KeepExisting,
+    Overwrite,
+    // The backup recipe is added under a new name
+    KeepBoth,
+}
+
+/// How a backup is applied to the current Cook Book.
+/// Recipes are matched by name, so the matched ones keep their ID and images.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RestoreMode {
+    // The Cook Book becomes the backup
+    #[default]
+    ReplaceAll,
+    // Only the recipes with a new name are added
+    AddNewOnly,
+    // New recipes are added, the others follow the conflict strategy
+    Merge(MergeConflict),
+}
+impl RestoreMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RestoreMode::ReplaceAll => "replace-all",
+            RestoreMode::AddNewOnly => "add-new-only",
+            RestoreMode::Merge(MergeConflict::KeepExisting) => "merge-keep-existing",
+            RestoreMode::Merge(MergeConflict::Overwrite) => "merge-overwrite",
+            RestoreMode::Merge(MergeConflict::KeepBoth) => "merge-keep-both",
+        }
+    }
+    pub fn label(&self) -> &'static str {
+        match self {
+            RestoreMode::ReplaceAll => "Replace all recipes",
+            RestoreMode::AddNewOnly => "Add new recipes only",
+            RestoreMode::Merge(MergeConflict::KeepExisting) => "Merge, keep my version",
+            RestoreMode::Merge(MergeConflict::Overwrite) => "Merge, take the backup version",
+            RestoreMode::Merge(MergeConflict::KeepBoth) => "Merge, keep both versions",
+        }
+    }
+    pub const ALL: [RestoreMode; 5] = [
+        RestoreMode::ReplaceAll,
+        RestoreMode::AddNewOnly,
+        RestoreMode::Merge(MergeConflict::KeepExisting),
+        RestoreMode::Merge(MergeConflict::Overwrite),
+        RestoreMode::Merge(MergeConflict::KeepBoth),
+    ];
+}
+impl std::str::FromStr for RestoreMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RestoreMode::ALL
+            .into_iter()
+            .find(|mode| mode.as_str() == s)
+            .ok_or_else(|| format!("Unknown restore mode: {}", s))
+    }
+}
+
+/// Recipe names, by what a restore would do to them
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RestoreDiff {
+    pub added: Vec<String>,
+    pub changed: Vec<String>,
+    pub unchanged: Vec<String>,
+    pub removed: Vec<String>,
+    // Added next to an existing recipe: (backup name, new name)
+    pub renamed: Vec<(String, String)>,
+}
+impl RestoreDiff {
+    pub fn has_changes(&self) -> bool {
+        !(self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty() && self.renamed.is_empty())
+    }
+}
+
+/// Limits of a backup recipe, beyond them the file is most likely not a Cook Book backup
+pub const MAX_NAME_LENGTH: usize = 200;
+pub const MAX_TEXT_LENGTH: usize = 20_000;
+pub const MAX_ENTRIES: usize = 500;
+
+/// Why a backup cannot be restored
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RestoreProblem {
+    // Position (from 1) and name of the recipe in the backup, None for the whole file
+    pub recipe: Option<(usize, String)>,
+    pub message: String,
+}
+impl std::fmt::Display for RestoreProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.recipe {
+            Some((position, name)) if name.trim().is_empty() => write!(f, "Recipe {}: {}", position, self.message),
+            Some((position, name)) => write!(f, "Recipe {} \"{}\": {}", position, name, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// What the restore changes, or why nothing was changed
+pub type RestoreReport = Result<RestoreDiff, Vec<RestoreProblem>>;
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use super::*;
+    use crate::app::components::auth::{auth_server_functions::check_login, auth_utils::SharedLoginStates};
+    use crate::app::components::auto_backup::ssr::read_auto_backup;
+    use crate::app::components::recipe::*;
+    use crate::app::components::recipe_images::ssr::{recipe_image_files, remove_image_files};
+    use crate::app::components::recipe_server_functions::ssr::{all_recipes, db, insert_recipe, update_recipe};
+    use actix_web::{http::header, web, HttpRequest, HttpResponse};
+    use leptos::logging::*;
+    use serde_json::Value;
+    use sqlx::{Connection, SqliteConnection};
+
+    /// A change to the recipes table
+    #[derive(Clone, Debug)]
+    pub enum RestoreStep {
+        Insert(Recipe),
+        Update(u16, Recipe),
+        Delete(u16),
+    }
+
+    // Both sides go through the same conversion, so only real differences count
+    fn normalized(recipe: &Recipe) -> JsonRecipe {
+        JsonRecipe::from_recipe(recipe.clone())
+    }
+
+    // "Name (2)", "Name (3)"... the first one nobody uses
+    fn free_name(name: &str, used_names: &[String]) -> String {
+        (2..)
+            .map(|n| format!("{} ({})", name, n))
+            .find(|candidate| !used_names.iter().any(|used| RecipeLinks::same_name(used, candidate)))
+            .expect("a free name exists")
+    }
+
+    /// The steps applying the backup, with their summary
+    pub fn plan_restore(existing: &[Recipe], backup: Vec<Recipe>, mode: RestoreMode) -> (Vec<RestoreStep>, RestoreDiff) {
+        let mut steps: Vec<RestoreStep> = vec![];
+        let mut diff = RestoreDiff::default();
+        let mut matched_ids: Vec<u16> = vec![];
+        let mut used_names: Vec<String> = existing.iter().map(|r| r.name.clone()).collect();
+
+        for recipe in backup {
+            // Each existing recipe is matched once, the Cook Book can have twice the same name
+            let existing_match = existing.iter().find(|r| {
+                RecipeLinks::same_name(&r.name, &recipe.name) && !r.id.is_some_and(|id| matched_ids.contains(&id))
+            });
+            let Some(existing_recipe) = existing_match else {
+                diff.added.push(recipe.name.clone());
+                used_names.push(recipe.name.clone());
+                steps.push(RestoreStep::Insert(recipe));
+                continue;
+            };
+            let id = existing_recipe.id.expect("recipes from the database have an ID");
+            matched_ids.push(id);
+
+            if normalized(existing_recipe) == normalized(&recipe) {
+                diff.unchanged.push(recipe.name);
+                continue;
+            }
+            match mode {
+                RestoreMode::ReplaceAll | RestoreMode::Merge(MergeConflict::Overwrite) => {
+                    diff.changed.push(recipe.name.clone());
+                    steps.push(RestoreStep::Update(id, recipe));
+                }
+                RestoreMode::AddNewOnly | RestoreMode::Merge(MergeConflict::KeepExisting) => {
+                    diff.unchanged.push(existing_recipe.name.clone());
+                }
+                RestoreMode::Merge(MergeConflict::KeepBoth) => {
+                    let new_name = free_name(&recipe.name, &used_names);
+                    used_names.push(new_name.clone());
+                    diff.renamed.push((recipe.name.clone(), new_name.clone()));
+                    steps.push(RestoreStep::Insert(Recipe { name: new_name, ..recipe }));
+                }
+            }
+        }
+
+        if mode == RestoreMode::ReplaceAll {
+            for recipe in existing {
+                let id = recipe.id.expect("recipes from the database have an ID");
+                if !matched_ids.contains(&id) {
+                    diff.removed.push(recipe.name.clone());
+                    steps.push(RestoreStep::Delete(id));
+                }
+            }
+        }
+
+        for names in [&mut diff.added, &mut diff.changed, &mut diff.unchanged, &mut diff.removed] {
+            names.sort_by_key(|name| name.to_lowercase());
+        }
+
+        (steps, diff)
+    }
+
+    /// Upgrades a backup to the next version, `MIGRATIONS[0]` goes from version 1 to 2
+    type Migration = fn(Value) -> Result<Value, String>;
+    const MIGRATIONS: [Migration; 1] = [migrate_v1_to_v2];
+    // Every version but the current one needs its migration
+    const _: () = assert!(MIGRATIONS.len() == BACKUP_FORMAT_VERSION as usize - 1);
+
+    // The bare list of recipes goes into the envelope, its date and app version are unknown
+    fn migrate_v1_to_v2(backup: Value) -> Result<Value, String> {
+        Ok(serde_json::json!({
+            "format_version": 2,
+            "exported_at": "",
+            "app_version": "",
+            "recipes": backup,
+        }))
+    }
+
+    fn format_version(backup: &Value) -> Result<u32, String> {
+        match backup {
+            Value::Array(_) => Ok(1),
+            Value::Object(fields) => fields
+                .get("format_version")
+                .and_then(Value::as_u64)
+                .and_then(|version| u32::try_from(version).ok())
+                .ok_or_else(|| "The backup has no format version".to_string()),
+            _ => Err("The backup is not a list of recipes".to_string()),
+        }
+    }
+
+    /// Bring a backup of any known version to the current one
+    pub fn migrate_backup(mut backup: Value) -> Result<JsonBackup, String> {
+        let mut version = format_version(&backup)?;
+        if version == 0 || version > BACKUP_FORMAT_VERSION {
+            return Err(format!(
+                "The backup has the format version {}, this app only reads up to version {}. Update the app to restore it.",
+                version, BACKUP_FORMAT_VERSION
+            ));
+        }
+        while version < BACKUP_FORMAT_VERSION {
+            backup = MIGRATIONS[version as usize - 1](backup)?;
+            version += 1;
+        }
+        serde_json::from_value::<JsonBackup>(backup).map_err(|e| format!("The backup could not be read: {}", e))
+    }
+
+    /// The recipes of a JSON backup, of any version
+    pub fn parse_backup(save: &str) -> Result<Vec<Recipe>, Vec<RestoreProblem>> {
+        let problem = |message: String| vec![RestoreProblem { recipe: None, message }];
+
+        let save_json = serde_json::from_str::<Value>(save)
+            .map_err(|e| problem(format!("The backup is not valid JSON: {}", e)))?;
+        let backup = migrate_backup(save_json).map_err(problem)?;
+        log!(
+            "Reading a backup exported at {:?} by version {:?}, with {:?} recipes",
+            backup.exported_at, backup.app_version, backup.recipes.len()
+        );
+
+        Ok(backup.recipes.into_iter().map(JsonRecipe::to_new_recipe).collect())
+    }
+
+    fn text_problem(field: &str, text: &str) -> Option<String> {
+        (text.chars().count() > MAX_TEXT_LENGTH)
+            .then(|| format!("{} is longer than {} characters", field, MAX_TEXT_LENGTH))
+    }
+
+    fn entries_problem<T>(field: &str, entries: &Option<Vec<T>>) -> Option<String> {
+        entries
+            .as_ref()
+            .filter(|entries| entries.len() > MAX_ENTRIES)
+            .map(|entries| format!("{} {}, at most {} are allowed", entries.len(), field, MAX_ENTRIES))
+    }
+
+    /// What is wrong with a single recipe
+    fn recipe_problems(recipe: &Recipe) -> Vec<String> {
+        let mut problems: Vec<String> = vec![];
+
+        if recipe.name.trim().is_empty() {
+            problems.push("The name is empty".to_string());
+        } else if recipe.name.chars().count() > MAX_NAME_LENGTH {
+            problems.push(format!("The name is longer than {} characters", MAX_NAME_LENGTH));
+        }
+
+        let ingredients = RecipeIngredientGroup::flatten(recipe.ingredients.clone());
+        problems.extend([
+            entries_problem("tags", &recipe.tags),
+            entries_problem("ingredient groups", &recipe.ingredients),
+            entries_problem("ingredients", &ingredients),
+            entries_problem("steps", &recipe.instructions),
+            entries_problem("notes", &recipe.notes),
+        ].into_iter().flatten());
+
+        let tags = recipe.tags.iter().flatten().map(|tag| ("A tag", tag.name.as_str()));
+        let groups = recipe.ingredients.iter().flatten().map(|group| ("An ingredient group name", group.name.as_str()));
+        let ingredients = ingredients.iter().flatten()
+            .flat_map(|ingredient| [("An ingredient quantity", ingredient.qty_unit.as_str()), ("An ingredient", ingredient.content.as_str())]);
+        let steps = recipe.instructions.iter().flatten()
+            .flat_map(|step| [("A step title", step.title.as_str()), ("A step", step.content.as_str()), ("A step duration", step.duration.as_str())]);
+        let notes = recipe.notes.iter().flatten().map(|note| ("A note", note.content.as_str()));
+        let source = recipe.source.iter()
+            .flat_map(|source| [("The source URL", source.url.as_str()), ("The source author", source.author.as_str()), ("The source book", source.book.as_str()), ("The source page", source.page.as_str())]);
+
+        for (field, text) in tags.chain(groups).chain(ingredients).chain(steps).chain(notes).chain(source) {
+            if let Some(problem) = text_problem(field, text) {
+                if !problems.contains(&problem) {
+                    problems.push(problem);
+                }
+            }
+        }
+
+        problems
+    }
+
+    /// Everything wrong with the backup, nothing is restored unless it is empty
+    pub fn validate_backup(backup: &[Recipe]) -> Vec<RestoreProblem> {
+        let mut problems: Vec<RestoreProblem> = vec![];
+
+        if backup.is_empty() {
+            problems.push(RestoreProblem {
+                recipe: None,
+                message: "The backup has no recipes".to_string(),
+            });
+        }
+
+        // Recipes with the same name are fine, Duplicate makes them and `plan_restore` matches them one by one
+        for (index, recipe) in backup.iter().enumerate() {
+            let position = index + 1;
+            let messages = recipe_problems(recipe);
+
+            problems.extend(messages.into_iter().map(|message| RestoreProblem {
+                recipe: Some((position, recipe.name.clone())),
+                message,
+            }));
+        }
+
+        problems
+    }
+
+    /// The recipes of a JSON backup, if all of them can be restored
+    pub fn read_backup(save: &str) -> Result<Vec<Recipe>, Vec<RestoreProblem>> {
+        let backup = parse_backup(save)?;
+        let problems = validate_backup(&backup);
+        if problems.is_empty() {
+            Ok(backup)
+        } else {
+            Err(problems)
+        }
+    }
+
+    /// What the backup would change, nothing is written
+    pub async fn preview_restore(save: &str, mode: RestoreMode) -> Result<RestoreReport, ServerFnError> {
+        let backup = match read_backup(save) {
+            Ok(backup) => backup,
+            Err(problems) => return Ok(Err(problems)),
+        };
+        let mut conn = db().await?;
+        let existing = all_recipes(&mut conn).await?;
+
+        let (_, diff) = plan_restore(&existing, backup, mode);
+        Ok(Ok(diff))
+    }
+
+    /// Restore a backup, in a single transaction: either every change is applied or none
+    pub async fn apply_restore(save: &str, mode: RestoreMode) -> Result<RestoreReport, ServerFnError> {
+        let backup = match read_backup(save) {
+            Ok(backup) => backup,
+            Err(problems) => {
+                log!("The save was not applied, {:?} problems were found", problems.len());
+                return Ok(Err(problems));
+            }
+        };
+
+        let mut conn = db().await?;
+        let mut tx = conn.begin().await?;
+        let existing = all_recipes(&mut tx).await?;
+
+        let (steps, diff) = plan_restore(&existing, backup, mode);
+        log!("Applying save with {:?}: {:?}", mode, diff);
+
+        // The image rows are deleted along with the recipes,
+        // the files only for the deleted recipes, once it is committed
+        let mut removed_images = vec![];
+
+        for step in steps {
+            let result = match step {
+                RestoreStep::Insert(recipe) => insert_recipe(&mut tx, recipe).await,
+                RestoreStep::Update(id, recipe) => update_recipe(&mut tx, id, recipe).await,
+                RestoreStep::Delete(id) => {
+                    // Read before the rows go with the recipe
+                    let images = recipe_image_files(&mut tx, id).await?;
+                    let result = sqlx::query("DELETE FROM recipes WHERE id = $1")
+                        .bind(id)
+                        .execute(&mut *tx)
+                        .await;
+                    if result.is_ok() {
+                        removed_images.extend(images);
+                    }
+                    result
+                },
+            };
+            // Dropping the transaction rolls it back
+            if let Err(e) = result {
+                error!("ERROR: The save was not applied: {:?}", e.to_string());
+                return Err(ServerFnError::ServerError(format!("The save was not applied: {}", e)));
+            }
+        }
+
+        tx.commit().await?;
+        removed_images.iter().for_each(|image| remove_image_files(image.id, &image.mime_type));
+        log!("The save was applied succesfully !");
+
+        Ok(Ok(diff))
+    }
+
+    /// Every recipe as a JSON backup, sorted by name
+    pub async fn backup_json(conn: &mut SqliteConnection) -> Result<String, ServerFnError> {
+        let recipes = all_recipes(conn).await?;
+        let backup = JsonBackup::new(recipes.into_iter().map(JsonRecipe::from_recipe).collect());
+        Ok(serde_json::to_string_pretty(&backup)?)
+    }
+
+    /// The backup and the restore mode sent by the upload form, only for logged in users.
+    /// The backup is an uploaded file, or else the name of an automatic backup.
+    pub async fn read_backup_form(data: MultipartData) -> Result<(String, RestoreMode), ServerFnError> {
+        if !check_login().await? {
+            return Err(ServerFnError::ServerError("Not logged in.".to_string()));
+        }
+
+        let mut data = data.into_inner().expect("Expected multipart data on the server.");
+        let mut uploaded: Option<String> = None;
+        let mut auto_backup: Option<String> = None;
+        let mut mode = RestoreMode::default();
+
+        while let Some(mut field) = data.next_field().await? {
+            let name = field.name().unwrap_or_default().to_string();
+            match name.as_str() {
+                "restore_mode" => {
+                    mode = match field.text().await?.parse() {
+                        Ok(mode) => mode,
+                        Err(e) => return Err(ServerFnError::ServerError(e)),
+                    };
+                }
+                "auto_backup" => {
+                    auto_backup = Some(field.text().await?).filter(|file_name| !file_name.is_empty());
+                }
+                "backup_file" => {
+                    let mut bytes: Vec<u8> = vec![];
+                    while let Some(chunk) = field.chunk().await? {
+                        if bytes.len() + chunk.len() > MAX_BACKUP_SIZE {
+                            return Err(ServerFnError::ServerError(format!(
+                                "The backup is too big, the maximum size is {} MB.",
+                                MAX_BACKUP_SIZE / (1024 * 1024)
+                            )));
+                        }
+                        bytes.extend_from_slice(&chunk);
+                    }
+                    match String::from_utf8(bytes) {
+                        Ok(text) => uploaded = Some(text).filter(|text| !text.trim().is_empty()),
+                        Err(_) => return Err(ServerFnError::ServerError("The backup is not a text file.".to_string())),
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(save) = uploaded {
+            return Ok((save, mode));
+        }
+        let Some(file_name) = auto_backup else {
+            return Err(ServerFnError::ServerError("No backup file was sent.".to_string()));
+        };
+        match read_auto_backup(&file_name) {
+            Ok(save) => Ok((save, mode)),
+            Err(e) => Err(ServerFnError::ServerError(e)),
+        }
+    }
+
+    /// Download of the JSON backup as a file, only for logged in users
+    #[actix_web::get("/backup-download")]
+    pub async fn serve_backup_download(req: HttpRequest, login_states: web::Data<SharedLoginStates>) -> HttpResponse {
+        let is_logged_in = req
+            .connection_info()
+            .realip_remote_addr()
+            .is_some_and(|ip| login_states.is_ip_logged_in(ip));
+        if !is_logged_in {
+            return HttpResponse::Unauthorized().body("Not logged in.");
+        }
+
+        let content = match db().await {
+            Ok(mut conn) => backup_json(&mut conn).await,
+            Err(e) => Err(e),
+        };
+        let content = match content {
+            Ok(content) => content,
+            Err(e) => {
+                error!("ERROR: Could not make the backup: {:?}", e.to_string());
+                return HttpResponse::InternalServerError().body(e.to_string());
+            }
+        };
+
+        let date_fmt = chrono::Local::now().format("%Y-%m-%d--%H-%M-%S").to_string();
+        let file_name = format!("cook_book_backup_{}.json", date_fmt);
+
+        HttpResponse::Ok()
+            .content_type("application/json; charset=utf-8")
+            .insert_header((
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", file_name),
+            ))
+            .body(content)
+    }
+}
+
+/// What restoring the uploaded backup would change, nothing is written
+#[server(input = MultipartFormData)]
+pub async fn preview_backup_file(data: MultipartData) -> Result<RestoreReport, ServerFnError> {
+    use self::ssr::*;
+
+    let (save, mode) = read_backup_form(data).await?;
+    preview_restore(&save, mode).await
+}
+
+/// Restore the uploaded backup
+#[server(input = MultipartFormData)]
+pub async fn apply_backup_file(data: MultipartData) -> Result<RestoreReport, ServerFnError> {
+    use self::ssr::*;
+
+    let (save, mode) = read_backup_form(data).await?;
+    apply_restore(&save, mode).await
+}
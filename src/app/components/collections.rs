@@ -0,0 +1,417 @@
+use crate::app::{CollectionServerAction, UserCollectionsResource};
+use leptos::ev::MouseEvent;
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub const MAX_COLLECTION_NAME_LENGTH: usize = 60;
+
+/// A named list of recipes of a user, "Christmas", "Kids' lunches"
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecipeCollection {
+    pub id: u32,
+    pub name: String,
+    pub recipe_ids: Vec<u16>,
+}
+
+/// The favorites and collections of the logged in user
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserCollections {
+    pub favorites: Vec<u16>,
+    pub collections: Vec<RecipeCollection>,
+}
+
+impl UserCollections {
+    pub fn is_favorite(&self, recipe_id: u16) -> bool {
+        self.favorites.contains(&recipe_id)
+    }
+}
+
+/// Recipes shown on the home page
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CollectionFilter {
+    #[default]
+    All,
+    Favorites,
+    Collection(u32),
+}
+impl CollectionFilter {
+    pub fn as_str(&self) -> String {
+        match self {
+            CollectionFilter::All => "all".to_string(),
+            CollectionFilter::Favorites => "favorites".to_string(),
+            CollectionFilter::Collection(id) => format!("collection-{}", id),
+        }
+    }
+
+    /// The recipes to keep, None to keep them all
+    pub fn recipe_ids(&self, user_collections: &UserCollections) -> Option<Vec<u16>> {
+        match self {
+            CollectionFilter::All => None,
+            CollectionFilter::Favorites => Some(user_collections.favorites.clone()),
+            CollectionFilter::Collection(id) => Some(
+                user_collections
+                    .collections
+                    .iter()
+                    .find(|collection| collection.id == *id)
+                    .map(|collection| collection.recipe_ids.clone())
+                    .unwrap_or_default(),
+            ),
+        }
+    }
+}
+impl std::str::FromStr for CollectionFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => Ok(CollectionFilter::All),
+            "favorites" => Ok(CollectionFilter::Favorites),
+            _ => s
+                .strip_prefix("collection-")
+                .and_then(|id| id.parse().ok())
+                .map(CollectionFilter::Collection)
+                .ok_or_else(|| format!("Unknown collection filter: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum CollectionActionDescriptor {
+    SetFavorite {
+        recipe_id: u16,
+        is_favorite: bool,
+    },
+    // With the recipe to add to it, if any
+    Create {
+        name: String,
+        recipe_id: Option<u16>,
+    },
+    SetMembership {
+        collection_id: u32,
+        recipe_id: u16,
+        is_member: bool,
+    },
+    // With collection ID
+    Delete(u32),
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use super::*;
+    use sqlx::SqliteConnection;
+
+    #[derive(sqlx::FromRow)]
+    pub struct DbRowCollection {
+        pub id: u32,
+        pub collection_name: String,
+    }
+
+    /// Collections can only be changed by their owner
+    pub async fn check_collection_owner(
+        conn: &mut SqliteConnection,
+        collection_id: u32,
+        username: &str,
+    ) -> Result<(), ServerFnError> {
+        let owner: Option<String> = sqlx::query_scalar("SELECT username FROM collections WHERE id = $1")
+            .bind(collection_id)
+            .fetch_optional(conn)
+            .await?;
+        if owner.as_deref() == Some(username) {
+            Ok(())
+        } else {
+            Err(ServerFnError::ServerError("Collection not found.".to_string()))
+        }
+    }
+
+    pub fn checked_collection_name(name: &str) -> Result<String, ServerFnError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(ServerFnError::ServerError("The collection needs a name.".to_string()));
+        }
+        Ok(name.chars().take(MAX_COLLECTION_NAME_LENGTH).collect())
+    }
+}
+
+/// The favorites and collections of the logged in user, empty if not logged in
+#[server]
+pub async fn get_user_collections() -> Result<UserCollections, ServerFnError> {
+    use self::ssr::*;
+    use crate::app::components::auth::auth_server_functions::logged_in_username;
+    use crate::app::components::recipe_server_functions::ssr::db;
+
+    let Some(username) = logged_in_username().await? else {
+        return Ok(UserCollections::default());
+    };
+
+    let mut conn = db().await?;
+
+    let favorites: Vec<u16> = sqlx::query_scalar("SELECT recipe_id FROM favorites WHERE username = $1")
+        .bind(&username)
+        .fetch_all(&mut conn)
+        .await?;
+
+    let rows = sqlx::query_as::<_, DbRowCollection>(
+        "SELECT id, collection_name FROM collections WHERE username = $1 ORDER BY collection_name COLLATE NOCASE",
+    )
+    .bind(&username)
+    .fetch_all(&mut conn)
+    .await?;
+
+    let mut collections = vec![];
+    for row in rows {
+        let recipe_ids: Vec<u16> = sqlx::query_scalar("SELECT recipe_id FROM collection_recipes WHERE collection_id = $1")
+            .bind(row.id)
+            .fetch_all(&mut conn)
+            .await?;
+        collections.push(RecipeCollection { id: row.id, name: row.collection_name, recipe_ids });
+    }
+
+    Ok(UserCollections { favorites, collections })
+}
+
+#[server]
+pub async fn collection_function(action: CollectionActionDescriptor) -> Result<(), ServerFnError> {
+    use self::ssr::*;
+    use crate::app::components::auth::auth_server_functions::logged_in_username;
+    use crate::app::components::recipe_server_functions::ssr::db;
+
+    let Some(username) = logged_in_username().await? else {
+        return Err(ServerFnError::ServerError("Not logged in.".to_string()));
+    };
+
+    let mut conn = db().await?;
+
+    match action {
+        CollectionActionDescriptor::SetFavorite { recipe_id, is_favorite } => {
+            let query = if is_favorite {
+                "INSERT OR IGNORE INTO favorites (username, recipe_id) VALUES ($1, $2);"
+            } else {
+                "DELETE FROM favorites WHERE username = $1 AND recipe_id = $2;"
+            };
+            sqlx::query(query)
+                .bind(&username)
+                .bind(recipe_id)
+                .execute(&mut conn)
+                .await?;
+        }
+        CollectionActionDescriptor::Create { name, recipe_id } => {
+            let name = checked_collection_name(&name)?;
+            // An existing collection with the same name is reused
+            sqlx::query("INSERT OR IGNORE INTO collections (username, collection_name) VALUES ($1, $2);")
+                .bind(&username)
+                .bind(&name)
+                .execute(&mut conn)
+                .await?;
+            if let Some(recipe_id) = recipe_id {
+                sqlx::query(
+                    "INSERT OR IGNORE INTO collection_recipes (collection_id, recipe_id)
+                    SELECT id, $3 FROM collections WHERE username = $1 AND collection_name = $2;",
+                )
+                .bind(&username)
+                .bind(&name)
+                .bind(recipe_id)
+                .execute(&mut conn)
+                .await?;
+            }
+        }
+        CollectionActionDescriptor::SetMembership { collection_id, recipe_id, is_member } => {
+            check_collection_owner(&mut conn, collection_id, &username).await?;
+            let query = if is_member {
+                "INSERT OR IGNORE INTO collection_recipes (collection_id, recipe_id) VALUES ($1, $2);"
+            } else {
+                "DELETE FROM collection_recipes WHERE collection_id = $1 AND recipe_id = $2;"
+            };
+            sqlx::query(query)
+                .bind(collection_id)
+                .bind(recipe_id)
+                .execute(&mut conn)
+                .await?;
+        }
+        CollectionActionDescriptor::Delete(collection_id) => {
+            check_collection_owner(&mut conn, collection_id, &username).await?;
+            sqlx::query("DELETE FROM collections WHERE id = $1;")
+                .bind(collection_id)
+                .execute(&mut conn)
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Favorite toggle and collection checkboxes of a recipe, for the logged in user
+#[component]
+pub fn CollectionMembership(recipe_id: u16) -> impl IntoView {
+    let collection_action = use_context::<CollectionServerAction>()
+        .expect("To find CollectionServerAction in context.")
+        .0;
+    let user_collections = use_context::<UserCollectionsResource>()
+        .expect("To find UserCollectionsResource in context.")
+        .0;
+    let user_collections = move || user_collections.get().and_then(|res| res.ok()).unwrap_or_default();
+
+    let new_collection_name = RwSignal::new(String::new());
+
+    let on_create = move |ev: MouseEvent| {
+        ev.stop_propagation();
+        let name = new_collection_name.get_untracked();
+        if name.trim().is_empty() {
+            return;
+        }
+        collection_action.dispatch(CollectionActionDescriptor::Create { name, recipe_id: Some(recipe_id) });
+        new_collection_name.set(String::new());
+    };
+
+    view! {
+        <div
+            class="collection-membership"
+            on:click=|ev| ev.stop_propagation()
+        >
+            <label class="collection-membership-option favorite">
+                <input
+                    type="checkbox"
+                    prop:checked=move || user_collections().is_favorite(recipe_id)
+                    on:change=move |ev| {
+                        collection_action.dispatch(CollectionActionDescriptor::SetFavorite {
+                            recipe_id,
+                            is_favorite: event_target_checked(&ev),
+                        });
+                    }
+                />
+                "★ Favorite"
+            </label>
+            {move || {
+                user_collections()
+                    .collections
+                    .into_iter()
+                    .map(|collection| {
+                        let collection_id = collection.id;
+                        let is_member = collection.recipe_ids.contains(&recipe_id);
+                        view! {
+                            <label class="collection-membership-option">
+                                <input
+                                    type="checkbox"
+                                    prop:checked=is_member
+                                    on:change=move |ev| {
+                                        collection_action.dispatch(CollectionActionDescriptor::SetMembership {
+                                            collection_id,
+                                            recipe_id,
+                                            is_member: event_target_checked(&ev),
+                                        });
+                                    }
+                                />
+                                { collection.name }
+                            </label>
+                        }
+                    })
+                    .collect_view()
+            }}
+            <div class="collection-membership-new">
+                <input
+                    class="collection-membership-new-name"
+                    type="text"
+                    placeholder="New collection"
+                    maxlength=MAX_COLLECTION_NAME_LENGTH.to_string()
+                    prop:value=new_collection_name
+                    on:input=move |ev| new_collection_name.set(event_target_value(&ev))
+                />
+                <button
+                    class="collection-membership-new-button"
+                    on:click=on_create
+                >
+                    "Add"
+                </button>
+            </div>
+        </div>
+    }
+}
+
+/// Choice between all recipes, the favorites or a collection, next to the tags
+#[component]
+pub fn CollectionFilterSelect(selected: RwSignal<CollectionFilter>) -> impl IntoView {
+    let collection_action = use_context::<CollectionServerAction>()
+        .expect("To find CollectionServerAction in context.")
+        .0;
+    let user_collections = use_context::<UserCollectionsResource>()
+        .expect("To find UserCollectionsResource in context.")
+        .0;
+    let user_collections = move || user_collections.get().and_then(|res| res.ok()).unwrap_or_default();
+
+    // A deleted collection no longer filters
+    Effect::new(move |_| {
+        if let CollectionFilter::Collection(id) = selected.get() {
+            let is_missing = user_collections
+                .get()
+                .and_then(|res| res.ok())
+                .is_some_and(|user_collections| user_collections.collections.iter().all(|collection| collection.id != id));
+            if is_missing {
+                selected.set(CollectionFilter::All);
+            }
+        }
+    });
+
+    let on_delete = move |ev: MouseEvent| {
+        ev.stop_propagation();
+        let CollectionFilter::Collection(id) = selected.get_untracked() else {
+            return;
+        };
+        let is_confirmed = window()
+            .confirm_with_message("Delete this collection? Its recipes are kept.")
+            .unwrap_or(false);
+        if is_confirmed {
+            collection_action.dispatch(CollectionActionDescriptor::Delete(id));
+        }
+    };
+
+    view! {
+        <div class="collection-filter">
+            <select
+                class="collection-filter-select"
+                on:change=move |ev| {
+                    if let Ok(filter) = event_target_value(&ev).parse() {
+                        selected.set(filter);
+                    }
+                }
+            >
+                <option
+                    value=CollectionFilter::All.as_str()
+                    prop:selected=move || selected.get() == CollectionFilter::All
+                >
+                    "All recipes"
+                </option>
+                <option
+                    value=CollectionFilter::Favorites.as_str()
+                    prop:selected=move || selected.get() == CollectionFilter::Favorites
+                >
+                    "★ Favorites"
+                </option>
+                {move || {
+                    user_collections()
+                        .collections
+                        .into_iter()
+                        .map(|collection| {
+                            let filter = CollectionFilter::Collection(collection.id);
+                            view! {
+                                <option
+                                    value=filter.as_str()
+                                    prop:selected=move || selected.get() == filter
+                                >
+                                    { collection.name }
+                                </option>
+                            }
+                        })
+                        .collect_view()
+                }}
+            </select>
+            <Show when=move || matches!(selected.get(), CollectionFilter::Collection(_))>
+                <button
+                    class="collection-filter-delete"
+                    title="Delete this collection"
+                    on:click=on_delete
+                >
+                    "×"
+                </button>
+            </Show>
+        </div>
+    }
+}
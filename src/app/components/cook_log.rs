@@ -0,0 +1,389 @@
+use crate::app::{
+    components::meal_planner::{format_plan_date, parse_plan_date, today},
+    CookLogServerAction, LoginCheckResource, PageColor,
+};
+use leptos::ev::MouseEvent;
+use leptos::logging::*;
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub const MAX_RATING: u8 = 5;
+
+/// "★★★☆☆"
+pub fn rating_stars(rating: u8) -> String {
+    let rating = rating.min(MAX_RATING) as usize;
+    "★".repeat(rating) + &"☆".repeat(MAX_RATING as usize - rating)
+}
+
+/// "12 Oct 2026", or the date as stored if it can't be read
+fn display_date(date: &str) -> String {
+    parse_plan_date(date)
+        .map(|date| date.format("%-d %b %Y").to_string())
+        .unwrap_or_else(|| date.to_string())
+}
+
+/// A time a recipe was cooked
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CookLogEntry {
+    pub id: u32,
+    pub recipe_id: u16,
+    pub cooked_on: String,
+    pub username: String,
+    pub rating: Option<u8>,
+    pub comment: String,
+}
+
+/// How often and how recently a recipe was cooked
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CookStats {
+    pub recipe_id: u16,
+    pub times_cooked: u32,
+    pub last_cooked: String,
+}
+
+impl CookStats {
+    pub fn find(stats: &[CookStats], recipe_id: u16) -> Option<&CookStats> {
+        stats.iter().find(|stats| stats.recipe_id == recipe_id)
+    }
+
+    /// "Cooked 3 times, last on 12 Oct 2026"
+    pub fn label(&self) -> String {
+        let times = match self.times_cooked {
+            1 => "once".to_string(),
+            2 => "twice".to_string(),
+            n => format!("{} times", n),
+        };
+        format!("Cooked {}, last on {}", times, display_date(&self.last_cooked))
+    }
+}
+
+/// Order of the recipe list of the home page
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RecipeListOrder {
+    #[default]
+    Default,
+    // Cooked the longest ago first, then never cooked
+    NotCookedInAWhile,
+}
+impl RecipeListOrder {
+    pub const ALL: [RecipeListOrder; 2] = [RecipeListOrder::Default, RecipeListOrder::NotCookedInAWhile];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RecipeListOrder::Default => "default",
+            RecipeListOrder::NotCookedInAWhile => "not-cooked-in-a-while",
+        }
+    }
+    pub fn label(&self) -> &'static str {
+        match self {
+            RecipeListOrder::Default => "Default order",
+            RecipeListOrder::NotCookedInAWhile => "Haven't made in a while",
+        }
+    }
+
+    /// Sort key of a recipe in this order, the list is sorted by it with a stable sort
+    pub fn sort_key(&self, stats: &[CookStats], recipe_id: u16) -> (bool, String) {
+        match self {
+            RecipeListOrder::Default => (false, String::new()),
+            RecipeListOrder::NotCookedInAWhile => match CookStats::find(stats, recipe_id) {
+                Some(stats) => (false, stats.last_cooked.clone()),
+                None => (true, String::new()),
+            },
+        }
+    }
+}
+impl std::str::FromStr for RecipeListOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RecipeListOrder::ALL
+            .into_iter()
+            .find(|order| order.as_str() == s)
+            .ok_or_else(|| format!("Unknown recipe order: {}", s))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum CookLogActionDescriptor {
+    // Logged for the current user
+    Add {
+        recipe_id: u16,
+        cooked_on: String,
+        rating: Option<u8>,
+        comment: String,
+    },
+    // With entry ID
+    Remove(u32),
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use super::*;
+
+    #[derive(sqlx::FromRow)]
+    pub struct DbRowCookLogEntry {
+        pub id: u32,
+        pub recipe_id: u16,
+        pub cooked_on: String,
+        pub username: String,
+        pub rating: Option<u8>,
+        pub comment: String,
+    }
+    impl From<DbRowCookLogEntry> for CookLogEntry {
+        fn from(row: DbRowCookLogEntry) -> Self {
+            CookLogEntry {
+                id: row.id,
+                recipe_id: row.recipe_id,
+                cooked_on: row.cooked_on,
+                username: row.username,
+                rating: row.rating,
+                comment: row.comment,
+            }
+        }
+    }
+
+    #[derive(sqlx::FromRow)]
+    pub struct DbRowCookStats {
+        pub recipe_id: u16,
+        pub times_cooked: u32,
+        pub last_cooked: String,
+    }
+}
+
+/// The times a recipe was cooked, the latest first
+#[server]
+pub async fn get_cook_log(recipe_id: u16) -> Result<Vec<CookLogEntry>, ServerFnError> {
+    use self::ssr::*;
+    use crate::app::components::auth::auth_server_functions::check_login;
+    use crate::app::components::recipe_server_functions::ssr::db;
+
+    // The log shows who cooked
+    if !check_login().await? {
+        return Err(ServerFnError::ServerError("Not logged in.".to_string()));
+    }
+
+    let mut conn = db().await?;
+    let rows = sqlx::query_as::<_, DbRowCookLogEntry>(
+        "SELECT id, recipe_id, cooked_on, username, rating, comment
+        FROM cook_log
+        WHERE recipe_id = $1
+        ORDER BY cooked_on DESC, id DESC",
+    )
+    .bind(recipe_id)
+    .fetch_all(&mut conn)
+    .await?;
+
+    Ok(rows.into_iter().map(CookLogEntry::from).collect())
+}
+
+/// The stats of every recipe cooked at least once
+#[server]
+pub async fn get_cook_stats() -> Result<Vec<CookStats>, ServerFnError> {
+    use self::ssr::*;
+    use crate::app::components::recipe_server_functions::ssr::db;
+
+    let mut conn = db().await?;
+    let rows = sqlx::query_as::<_, DbRowCookStats>(
+        "SELECT recipe_id, COUNT(*) AS times_cooked, MAX(cooked_on) AS last_cooked
+        FROM cook_log
+        GROUP BY recipe_id",
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| CookStats {
+            recipe_id: row.recipe_id,
+            times_cooked: row.times_cooked,
+            last_cooked: row.last_cooked,
+        })
+        .collect())
+}
+
+#[server]
+pub async fn cook_log_function(action: CookLogActionDescriptor) -> Result<(), ServerFnError> {
+    use crate::app::components::auth::auth_server_functions::logged_in_username;
+    use crate::app::components::meal_planner::ssr::checked_date;
+    use crate::app::components::recipe_server_functions::ssr::db;
+
+    let Some(username) = logged_in_username().await? else {
+        return Err(ServerFnError::ServerError("Not logged in.".to_string()));
+    };
+
+    let mut conn = db().await?;
+
+    match action {
+        CookLogActionDescriptor::Add { recipe_id, cooked_on, rating, comment } => {
+            sqlx::query(
+                "INSERT INTO cook_log (recipe_id, cooked_on, username, rating, comment) VALUES ($1, $2, $3, $4, $5);",
+            )
+            .bind(recipe_id)
+            .bind(checked_date(&cooked_on)?)
+            .bind(&username)
+            .bind(rating.map(|rating| rating.clamp(1, MAX_RATING)))
+            .bind(comment.trim())
+            .execute(&mut conn)
+            .await?;
+            log!("{:?} cooked the recipe with ID: {:?} on {:?}", username, recipe_id, cooked_on);
+        }
+        CookLogActionDescriptor::Remove(id) => {
+            sqlx::query("DELETE FROM cook_log WHERE id = $1;")
+                .bind(id)
+                .execute(&mut conn)
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Log that the recipe was cooked, from the recipe menu
+#[component]
+pub fn CookedItForm(recipe_id: u16) -> impl IntoView {
+    let cook_log_action = use_context::<CookLogServerAction>()
+        .expect("To find CookLogServerAction in context.")
+        .0;
+
+    let cooked_on = RwSignal::new(format_plan_date(today()));
+    let rating = RwSignal::new(None::<u8>);
+    let comment = RwSignal::new(String::new());
+    let is_logged = RwSignal::new(false);
+
+    let on_log = move |ev: MouseEvent| {
+        ev.stop_propagation();
+        let Some(date) = parse_plan_date(&cooked_on.get_untracked()) else {
+            return;
+        };
+        cook_log_action.dispatch(CookLogActionDescriptor::Add {
+            recipe_id,
+            cooked_on: format_plan_date(date),
+            rating: rating.get_untracked(),
+            comment: comment.get_untracked(),
+        });
+        rating.set(None);
+        comment.set(String::new());
+        is_logged.set(true);
+    };
+
+    view! {
+        <div
+            class="cooked-it"
+            on:click=|ev| ev.stop_propagation()
+        >
+            <input
+                class="cooked-it-date"
+                type="date"
+                prop:value=cooked_on
+                on:input=move |ev| cooked_on.set(event_target_value(&ev))
+            />
+            <div class="cooked-it-rating">
+                {(1..=MAX_RATING).map(|stars| view! {
+                    <button
+                        class="cooked-it-star"
+                        class:selected=move || rating.get().is_some_and(|rating| rating >= stars)
+                        title=format!("{} / {}", stars, MAX_RATING)
+                        on:click=move |ev: MouseEvent| {
+                            ev.stop_propagation();
+                            // Clicking the rating again clears it
+                            rating.update(|rating| {
+                                *rating = if *rating == Some(stars) { None } else { Some(stars) };
+                            });
+                        }
+                    >
+                        "★"
+                    </button>
+                }).collect_view()}
+            </div>
+            <textarea
+                class="cooked-it-comment"
+                placeholder="How did it go?"
+                prop:value=comment
+                on:input=move |ev| comment.set(event_target_value(&ev))
+            />
+            <button
+                class="cooked-it-button"
+                on:click=on_log
+            >
+                "Cooked it"
+            </button>
+            <Show when=is_logged>
+                <span class="cooked-it-done">"Logged!"</span>
+            </Show>
+        </div>
+    }
+}
+
+/// The times a recipe was cooked, on the recipe sheet
+#[component]
+pub fn CookLogList(recipe_id: u16) -> impl IntoView {
+    // Is logged in
+    let check_login_resource = use_context::<LoginCheckResource>()
+        .expect("Expected to find LoginCheckAction in context")
+        .0;
+
+    // Fetch page color
+    let theme_color = use_context::<PageColor>()
+        .expect("To find PageColor in context.")
+        .0;
+
+    let cook_log_action = use_context::<CookLogServerAction>()
+        .expect("To find CookLogServerAction in context.")
+        .0;
+
+    let entries = Resource::new(
+        move || (cook_log_action.version().get(), check_login_resource.get() == Some(true)),
+        move |(_, is_logged_in)| async move {
+            if !is_logged_in {
+                return Ok(vec![]);
+            }
+            get_cook_log(recipe_id).await.map_err(|e| {
+                error!("ERROR: Could not get the cook log: {:?}", e.to_string());
+                e.to_string()
+            })
+        },
+    );
+
+    view! {
+        <Transition fallback=|| ()>
+            {move || match entries.get() {
+                Some(Ok(entries)) if !entries.is_empty() => {
+                    let entry_list = entries.into_iter().map(|entry| {
+                        let id = entry.id;
+                        view! {
+                            <li class="display-recipe cook-log">
+                                <span class="display-recipe cook-log-date">{ display_date(&entry.cooked_on) }</span>
+                                <span class="display-recipe cook-log-user">{ entry.username }</span>
+                                { entry.rating.map(|rating| view! {
+                                    <span class="display-recipe cook-log-rating">{ rating_stars(rating) }</span>
+                                }) }
+                                <button
+                                    class="display-recipe cook-log-remove"
+                                    title="Remove from the log"
+                                    on:click=move |_| { cook_log_action.dispatch(CookLogActionDescriptor::Remove(id)); }
+                                >"×"</button>
+                                { (!entry.comment.is_empty()).then(|| view! {
+                                    <p class="display-recipe cook-log-comment">{ entry.comment }</p>
+                                }) }
+                            </li>
+                        }
+                    }).collect_view();
+
+                    view! {
+                        <div class="display-recipe cook-log container">
+                            <h3
+                                style=move || { theme_color.get().as_visible_color() }
+                                class="display-recipe cook-log title"
+                            >"Cooked"</h3>
+                            <ul class="display-recipe cook-log">
+                                { entry_list }
+                            </ul>
+                        </div>
+                    }.into_any()
+                },
+                Some(Err(e)) => view! { <pre class="error">"Server Error: " { e }</pre> }.into_any(),
+                _ => ().into_any(),
+            }}
+        </Transition>
+    }
+}
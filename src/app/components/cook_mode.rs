@@ -0,0 +1,279 @@
+use leptos::logging::*;
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use web_sys::wasm_bindgen::{JsCast, JsValue};
+
+use crate::app::{
+    components::recipe::Recipe,
+    elements::icons_svg::BackButtonSVG,
+    PageColor,
+};
+
+/// Where the cook is in a recipe, kept in the browser local storage
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CookProgress {
+    // Indexes in the flattened ingredient list
+    pub checked_ingredients: Vec<usize>,
+    pub step: usize,
+}
+
+fn cook_progress_key(recipe_id: u16) -> String {
+    format!("cook_progress_{}", recipe_id)
+}
+
+fn load_cook_progress(recipe_id: u16) -> Option<CookProgress> {
+    let storage = window().local_storage().ok()??;
+    let json = storage.get_item(&cook_progress_key(recipe_id)).ok()??;
+    serde_json::from_str(&json).ok()
+}
+
+fn save_cook_progress(recipe_id: u16, progress: &CookProgress) {
+    let Ok(Some(storage)) = window().local_storage() else {
+        return;
+    };
+    let result = if progress == &CookProgress::default() {
+        storage.remove_item(&cook_progress_key(recipe_id))
+    } else {
+        match serde_json::to_string(progress) {
+            Ok(json) => storage.set_item(&cook_progress_key(recipe_id), &json),
+            Err(e) => {
+                error!("ERROR: Could not serialize cook progress: {}", e);
+                return;
+            }
+        }
+    };
+    if let Err(e) = result {
+        error!("ERROR: Could not save cook progress: {:?}", e);
+    }
+}
+
+// The Screen Wake Lock API is not in the stable web-sys bindings yet
+async fn request_screen_wake_lock() -> Result<JsValue, JsValue> {
+    use web_sys::js_sys::{Function, Promise, Reflect};
+
+    let wake_lock = Reflect::get(&window().navigator(), &JsValue::from_str("wakeLock"))?;
+    if wake_lock.is_undefined() {
+        return Err(JsValue::from_str("Screen Wake Lock is not supported by this browser"));
+    }
+    let request: Function = Reflect::get(&wake_lock, &JsValue::from_str("request"))?.dyn_into()?;
+    let promise: Promise = request.call1(&wake_lock, &JsValue::from_str("screen"))?.dyn_into()?;
+    wasm_bindgen_futures::JsFuture::from(promise).await
+}
+
+fn release_screen_wake_lock(sentinel: &JsValue) {
+    use web_sys::js_sys::{Function, Reflect};
+
+    let release = Reflect::get(sentinel, &JsValue::from_str("release"))
+        .ok()
+        .and_then(|release| release.dyn_into::<Function>().ok());
+    if let Some(release) = release {
+        let _ = release.call0(sentinel);
+    }
+}
+
+/// Keeps the screen on while the component is mounted, true while the lock is held
+fn use_screen_wake_lock() -> RwSignal<bool> {
+    let is_locked = RwSignal::new(false);
+    let sentinel = StoredValue::new_local(None::<JsValue>);
+
+    let request = move || {
+        leptos::task::spawn_local(async move {
+            match request_screen_wake_lock().await {
+                Ok(lock) => {
+                    sentinel.set_value(Some(lock));
+                    is_locked.set(true);
+                }
+                Err(e) => {
+                    warn!("Could not keep the screen on: {:?}", e);
+                    is_locked.set(false);
+                }
+            }
+        });
+    };
+
+    // Effects only run in the browser
+    Effect::new(move |_| request());
+
+    // The browser releases the lock when the page is hidden
+    let handle = window_event_listener_untyped("visibilitychange", move |_| {
+        if !document().hidden() {
+            request();
+        }
+    });
+
+    on_cleanup(move || {
+        handle.remove();
+        if let Some(lock) = sentinel.try_get_value().flatten() {
+            release_screen_wake_lock(&lock);
+        }
+    });
+
+    is_locked
+}
+
+#[component]
+pub fn CookRecipeSheet(recipe: Recipe) -> impl IntoView {
+
+    // Fetch page color
+    let color = use_context::<PageColor>()
+        .expect("To find PageColor in context.")
+        .0;
+
+    let recipe_id = recipe.id.expect("Expected recipe ID to be Some in cook mode");
+    let recipe_path = format!("/recipe/{}/display", recipe_id);
+
+    let ingredient_groups = recipe.ingredients.unwrap_or_default();
+    let steps = recipe.instructions.unwrap_or_default();
+    let step_count = steps.len();
+
+    // Progress, restored from the local storage once in the browser
+    let progress = RwSignal::new(CookProgress::default());
+    let is_progress_loaded = RwSignal::new(false);
+    Effect::new(move |_| {
+        if let Some(mut saved) = load_cook_progress(recipe_id) {
+            // The recipe may have lost steps since
+            saved.step = saved.step.min(step_count.saturating_sub(1));
+            progress.set(saved);
+        }
+        is_progress_loaded.set(true);
+    });
+    Effect::new(move |_| {
+        let progress = progress.get();
+        if is_progress_loaded.get_untracked() {
+            save_cook_progress(recipe_id, &progress);
+        }
+    });
+
+    let is_screen_locked = use_screen_wake_lock();
+
+    let toggle_ingredient = move |index: usize| {
+        progress.update(|progress| {
+            match progress.checked_ingredients.iter().position(|checked| *checked == index) {
+                Some(position) => { progress.checked_ingredients.remove(position); },
+                None => progress.checked_ingredients.push(index),
+            }
+        });
+    };
+
+    // Ingredients, numbered across groups
+    let mut ingredient_index = 0;
+    let ingredient_list = ingredient_groups
+        .into_iter()
+        .flat_map(|group| {
+            let group_heading = (!group.name.trim().is_empty()).then(|| view! {
+                <li class="cook-recipe ingredients group-name">{ group.name }</li>
+            }.into_any());
+
+            let ingredients = group.ingredients.into_iter().map(|ingredient| {
+                let index = ingredient_index;
+                ingredient_index += 1;
+                let is_checked = move || progress.get().checked_ingredients.contains(&index);
+                view! {
+                    <li
+                        class="cook-recipe ingredients"
+                        class:checked=is_checked
+                        on:click=move |_| toggle_ingredient(index)
+                    >
+                        <input
+                            type="checkbox"
+                            class="cook-recipe ingredients checkbox"
+                            prop:checked=is_checked
+                        />
+                        <span class="cook-recipe ingredients units">{ ingredient.qty_unit }</span>
+                        <span class="cook-recipe ingredients content">
+                            { ingredient.content.replace(['[', ']'], "") }
+                        </span>
+                    </li>
+                }.into_any()
+            }).collect::<Vec<_>>();
+
+            group_heading.into_iter().chain(ingredients)
+        })
+        .collect_view();
+
+    let current_step = move || steps.get(progress.get().step).cloned();
+
+    view! {
+
+        <div class="cook-recipe-container">
+
+            <div
+                class="cook-recipe-header"
+                style=move || color.get().as_bg_main_color()
+            >
+                <button
+                    style=move || color.get().as_alt_color()
+                    class="recipe-menu-button back"
+                    on:click=move |ev| {
+                        ev.stop_propagation();
+                        let navigate = leptos_router::hooks::use_navigate();
+                        navigate(&recipe_path, Default::default());
+                    }
+                >
+                    <BackButtonSVG/>
+                </button>
+                <h2
+                    style=move || color.get().as_alt_color()
+                    class="cook-recipe-name"
+                >
+                    { recipe.name }
+                </h2>
+                <span
+                    class="cook-recipe-wake-lock"
+                    style=move || color.get().as_alt_color()
+                >
+                    { move || if is_screen_locked.get() { "Screen stays on" } else { "Screen may sleep" } }
+                </span>
+            </div>
+
+            <h3 class="cook-recipe-title">"Ingredients"</h3>
+            <ul class="cook-recipe ingredients-list">
+                { ingredient_list }
+            </ul>
+
+            <h3 class="cook-recipe-title">"Instructions"</h3>
+            { move || match current_step() {
+                Some(step) => view! {
+                    <div class="cook-recipe-step">
+                        <p class="cook-recipe-step-count">
+                            { format!("Step {} / {}", progress.get().step + 1, step_count) }
+                        </p>
+                        { (!step.title.trim().is_empty()).then(|| view! {
+                            <h4 class="cook-recipe-step-title">{ step.title.clone() }</h4>
+                        }) }
+                        <p class="cook-recipe-step-content">{ step.content.replace(['[', ']'], "") }</p>
+                        { (!step.duration.trim().is_empty()).then(|| view! {
+                            <p class="cook-recipe-step-duration">{ step.duration.clone() }</p>
+                        }) }
+                        <div class="cook-recipe-step-buttons">
+                            <button
+                                class="cook-recipe-step-button"
+                                disabled=move || progress.get().step == 0
+                                on:click=move |_| progress.update(|progress| progress.step = progress.step.saturating_sub(1))
+                            >
+                                "Previous"
+                            </button>
+                            <button
+                                class="cook-recipe-step-button"
+                                disabled=move || progress.get().step + 1 >= step_count
+                                on:click=move |_| progress.update(|progress| progress.step = (progress.step + 1).min(step_count - 1))
+                            >
+                                "Next"
+                            </button>
+                        </div>
+                    </div>
+                }.into_any(),
+                None => view! { <p class="cook-recipe-empty">"No instructions."</p> }.into_any(),
+            }}
+
+            <button
+                class="cook-recipe-restart"
+                on:click=move |_| progress.set(CookProgress::default())
+            >
+                "Start over"
+            </button>
+
+        </div>
+
+    }
+}
@@ -0,0 +1,232 @@
+use leptos::prelude::*;
+
+use crate::app::CookingTimersSignal;
+
+/// A duration written in an instruction, "simmer for 20 minutes", "cuire 1h30"
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextDuration {
+    // Byte range of the duration in the text
+    pub start: usize,
+    pub end: usize,
+    pub seconds: u32,
+}
+
+/// Every duration in the text, in order:
+/// "1h30", "2 h", "1h30min", or a number followed by an English or French unit,
+/// "20 minutes", "1 hour and 15 minutes", "1 heure 30 minutes", "30 secondes".
+/// A range like "10-12 min" starts a timer for its lower bound.
+pub fn extract_durations(text: &str) -> Vec<TextDuration> {
+    use regex::Regex;
+    let re = Regex::new(concat!(
+        r"(?i)\b(?:",
+        r"(?P<compact_hours>\d+)\s*h(?:\s*(?P<compact_minutes>\d{1,2})(?:\s*(?:min|mn))?)?",
+        r"|",
+        r"(?P<number>\d+(?:[.,]\d+)?)(?:\s*(?:-|–|to|à)\s*\d+(?:[.,]\d+)?)?\s*",
+        r"(?P<unit>hours?|hrs?|heures?|minutes?|mins?|mn|seconds?|secs?|secondes?)",
+        r"(?:\s*(?:and|et)?\s*(?P<minutes>\d+)\s*(?:minutes?|mins?|mn))?",
+        r")\b",
+    ))
+    .unwrap();
+
+    re.captures_iter(text)
+        .filter_map(|caps| {
+            let whole = caps.get(0)?;
+            let number = |name: &str| {
+                caps.name(name)
+                    .and_then(|m| m.as_str().replace(',', ".").parse::<f64>().ok())
+                    .unwrap_or(0.0)
+            };
+
+            let seconds = if caps.name("compact_hours").is_some() {
+                number("compact_hours") * 3600.0 + number("compact_minutes") * 60.0
+            } else {
+                let unit = caps["unit"].to_lowercase();
+                let factor = if unit.starts_with('h') {
+                    3600.0
+                } else if unit.starts_with('s') {
+                    1.0
+                } else {
+                    60.0
+                };
+                number("number") * factor + number("minutes") * 60.0
+            };
+
+            (seconds >= 1.0).then(|| TextDuration {
+                start: whole.start(),
+                end: whole.end(),
+                seconds: seconds.round() as u32,
+            })
+        })
+        .collect()
+}
+
+/// "1:05:00", "19:59", "0:05"
+pub fn format_countdown(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+/// A running countdown, kept in the app context so it survives navigation
+#[derive(Clone, Debug, PartialEq)]
+pub struct CookingTimer {
+    pub id: u32,
+    pub label: String,
+    // Milliseconds since the epoch, as given by Date.now()
+    pub started_at: f64,
+    pub ends_at: f64,
+    pub has_rung: bool,
+}
+
+impl CookingTimer {
+    pub fn remaining_seconds(&self, now: f64) -> i64 {
+        // The clock may not have ticked since the timer started
+        ((self.ends_at - now.max(self.started_at)) / 1000.0).ceil() as i64
+    }
+}
+
+fn now_ms() -> f64 {
+    web_sys::js_sys::Date::now()
+}
+
+pub fn start_cooking_timer(timers: RwSignal<Vec<CookingTimer>>, label: String, seconds: u32) {
+    timers.update(|timers| {
+        let id = timers.iter().map(|timer| timer.id).max().unwrap_or(0) + 1;
+        let started_at = now_ms();
+        timers.push(CookingTimer {
+            id,
+            label,
+            started_at,
+            ends_at: started_at + seconds as f64 * 1000.0,
+            has_rung: false,
+        });
+    });
+}
+
+// Three short beeps
+fn play_timer_alert() {
+    let Ok(context) = web_sys::AudioContext::new() else {
+        return;
+    };
+    let start = context.current_time();
+    for beep in 0..3 {
+        let Ok(oscillator) = context.create_oscillator() else {
+            return;
+        };
+        oscillator.set_type(web_sys::OscillatorType::Square);
+        oscillator.frequency().set_value(880.0);
+        let _ = oscillator.connect_with_audio_node(&context.destination());
+        let beep_start = start + beep as f64 * 0.4;
+        let _ = oscillator.start_with_when(beep_start);
+        let _ = oscillator.stop_with_when(beep_start + 0.25);
+    }
+}
+
+/// A duration in a text, tapping it starts a timer
+#[component]
+pub fn TimerLink(text: String, label: String, seconds: u32, sheet_class: &'static str) -> impl IntoView {
+    let timers = use_context::<CookingTimersSignal>()
+        .expect("To find CookingTimersSignal in context.")
+        .0;
+
+    view! {
+        <button
+            class=format!("{sheet_class} timer-link")
+            title=format!("Start a {} timer", format_countdown(seconds as i64))
+            on:click=move |ev| {
+                ev.stop_propagation();
+                start_cooking_timer(timers, label.clone(), seconds);
+            }
+        >
+            { text }
+        </button>
+    }
+}
+
+/// Floating panel with every running timer
+#[component]
+pub fn CookingTimersPanel() -> impl IntoView {
+    let timers = use_context::<CookingTimersSignal>()
+        .expect("To find CookingTimersSignal in context.")
+        .0;
+
+    // Ticks every second in the browser
+    let now = RwSignal::new(0.0);
+    let _ = leptos_use::use_interval_fn(move || now.set(now_ms()), 1000);
+
+    // Ring once for each timer that just ended
+    Effect::new(move |_| {
+        let now = now.get();
+        let has_new_alert = timers.with(|timers| {
+            timers.iter().any(|timer| !timer.has_rung && timer.remaining_seconds(now) <= 0)
+        });
+        if has_new_alert {
+            play_timer_alert();
+            timers.update(|timers| {
+                timers
+                    .iter_mut()
+                    .filter(|timer| timer.remaining_seconds(now) <= 0)
+                    .for_each(|timer| timer.has_rung = true);
+            });
+        }
+    });
+
+    view! {
+        <Show when=move || timers.with(|timers| !timers.is_empty())>
+            <div class="cooking-timers">
+                <For
+                    each=move || timers.get()
+                    key=|timer| timer.id
+                    let:timer
+                >
+                    {
+                        let id = timer.id;
+                        let remaining = move || {
+                            timers.with(|timers| {
+                                timers
+                                    .iter()
+                                    .find(|timer| timer.id == id)
+                                    .map(|timer| timer.remaining_seconds(now.get()))
+                                    .unwrap_or(0)
+                            })
+                        };
+                        view! {
+                            <div
+                                class="cooking-timer"
+                                class:done=move || remaining() <= 0
+                            >
+                                <span class="cooking-timer-label">{ timer.label.clone() }</span>
+                                <span class="cooking-timer-time">
+                                    { move || if remaining() <= 0 { "Done!".to_string() } else { format_countdown(remaining()) } }
+                                </span>
+                                <button
+                                    class="cooking-timer-button"
+                                    title="One more minute"
+                                    on:click=move |_| timers.update(|timers| {
+                                        if let Some(timer) = timers.iter_mut().find(|timer| timer.id == id) {
+                                            timer.ends_at = timer.ends_at.max(now_ms()) + 60_000.0;
+                                            timer.has_rung = false;
+                                        }
+                                    })
+                                >
+                                    "+1"
+                                </button>
+                                <button
+                                    class="cooking-timer-button"
+                                    title="Stop this timer"
+                                    on:click=move |_| timers.update(|timers| timers.retain(|timer| timer.id != id))
+                                >
+                                    "×"
+                                </button>
+                            </div>
+                        }
+                    }
+                </For>
+            </div>
+        </Show>
+    }
+}
@@ -1,70 +1,57 @@
 use crate::app::{
-    components::recipe_server_functions::*, elements::molecules::LoadingElem, ApplySaveFromJson,
-    PopupColor,
+    components::{
+        backup::{preview_backup_file, RestoreDiff, RestoreMode, RestoreProblem, BACKUP_DOWNLOAD_URL},
+        recipe_export::RecipeExportFormat,
+    },
+    ApplySaveFromJson, PopupColor,
 };
 use leptos::logging::*;
 use leptos::prelude::*;
-use web_sys::SubmitEvent;
-extern crate chrono;
+use wasm_bindgen::JsCast;
+use web_sys::{FormData, HtmlFormElement, SubmitEvent};
 
-/// Download all recipes button
-/// Renders the home page of your application.
+/// Download all recipes button, the server sends the backup as a file
 #[component]
 pub fn DownloadAll(has_been_backed_up: RwSignal<bool>) -> impl IntoView {
-    let all_recipes = Resource::new(
-        || (),
-        |_| async move {
-            match get_all_recipes_as_json_string().await {
-                Ok(content) => Some(content),
-                Err(e) => {
-                    error!("{:?}", e.to_string());
-                    None
-                }
-            }
-        },
-    );
-
     view! {
-        <Suspense
-            fallback=move || view!{ <LoadingElem text="Loading Recipe...".to_owned() /> }
+        <a
+            href=       BACKUP_DOWNLOAD_URL
+            download=   ""
+            on:click=   move |_| { has_been_backed_up.set(true) }
+            class=      "download-backup-button"
         >
-            {move || {
-                let all_recipes_fetched = all_recipes.get();
-                if let Some(Some(data)) = all_recipes_fetched {
-                    let encoded_data = format!("data:text/plain;charset=utf-8,{}", urlencoding::encode(&data));
-                    let date_fmt = chrono::Local::now().format("%Y-%m-%d--%H:%M:%S").to_string();
-                    let save_name =
-                        "all_recipes_json".to_string()
-                        + &date_fmt
-                        + ".json";
-
-                    view! {
-                        <a
-                            href =      {encoded_data}
-                            //download =  &save_name
-                            download=   save_name
-                            on:click =  move |_| { has_been_backed_up.set(true) }
-                            class=      "download-backup-button"
-                        >
-                            "Download Backup"
-                        </a>
-                    }.into_any()
-                } else {
-                    view!{
-                        <p>"Fetched empty data :("</p>
-                    }.into_any()
-                }
-            }}
-        </Suspense>
+            "Download Backup"
+        </a>
+    }
+}
+
+/// Readable copies of the whole book, zipped by the server
+#[component]
+pub fn DownloadBook() -> impl IntoView {
+    view! {
+        <div class="download-book">
+            {[RecipeExportFormat::Markdown, RecipeExportFormat::Text].into_iter().map(|format| view! {
+                <a
+                    href=format.book_url()
+                    class="download-book-button"
+                >
+                    { format!("Download as {} (.zip)", format.label()) }
+                </a>
+            }).collect_view()}
+        </div>
     }
 }
 
-#[allow(unused)] // disable the warning on "ev"
+/// Restore an uploaded backup file, or the selected automatic backup
 #[component]
-pub fn UploadAll(has_been_backed_up: RwSignal<bool>) -> impl IntoView {
+pub fn UploadAll(has_been_backed_up: RwSignal<bool>, auto_backup: RwSignal<Option<String>>) -> impl IntoView {
     // Keep track if the save has be made
     let save_done = RwSignal::new(false);
 
+    // How the backup is applied, and the form waiting for confirmation
+    let restore_mode = RwSignal::new(RestoreMode::default());
+    let pending_save = RwSignal::new_local(None::<FormData>);
+
     // Apply save action
     let upload_save_action = use_context::<ApplySaveFromJson>()
         .expect("Expected to find ApplyJsonSave in context")
@@ -72,29 +59,48 @@ pub fn UploadAll(has_been_backed_up: RwSignal<bool>) -> impl IntoView {
     let upload_pending = upload_save_action.pending();
     let save_action_value = upload_save_action.value();
     Effect::new(move |_| {
-        if let Some(true) = save_action_value.get() {
+        if let Some(Ok(_)) = save_action_value.get() {
             save_done.set(true);
         }
     });
 
-    // Textarea
-    // setup for textarea autosize
-    let textarea = NodeRef::<leptos::html::Textarea>::new();
-
-    #[cfg(feature = "hydrate")]
-    let leptos_use::UseTextareaAutosizeReturn {
-        content: _,
-        set_content,
-        trigger_resize: _,
-    } = leptos_use::use_textarea_autosize(textarea);
+    // Dry run, shows what the save would change before applying it
+    // FormData is not Send, so this action must stay local
+    let preview_action = Action::new_local(|data: &FormData| preview_backup_file(data.clone().into()));
+    let preview_pending = preview_action.pending();
+    let preview_value = preview_action.value();
 
     let on_submit = move |ev: SubmitEvent| {
         ev.prevent_default();
-        let value = textarea
-            .get()
-            .expect("Expected testarea to be mounted.")
-            .value();
-        upload_save_action.dispatch(value);
+        let form = ev
+            .target()
+            .expect("Expected the backup form as event target.")
+            .unchecked_into::<HtmlFormElement>();
+        match FormData::new_with_form(&form) {
+            Ok(form_data) => {
+                pending_save.set(Some(form_data.clone()));
+                save_action_value.set(None);
+                preview_action.dispatch_local(form_data);
+            }
+            Err(e) => error!("ERROR: Could not read the backup form: {:?}", e),
+        }
+    };
+
+    // The preview is only valid for the file and mode it was made with
+    let clear_preview = move || {
+        pending_save.set(None);
+        preview_value.set(None);
+    };
+
+    Effect::new(move |_| {
+        auto_backup.track();
+        clear_preview();
+    });
+
+    let on_confirm = move |_| {
+        if let Some(form_data) = pending_save.get_untracked() {
+            upload_save_action.dispatch_local(form_data);
+        }
     };
 
     let popup_color = RwSignal::new(PopupColor::random());
@@ -131,26 +137,132 @@ pub fn UploadAll(has_been_backed_up: RwSignal<bool>) -> impl IntoView {
                         on:submit =     on_submit
                         class=          "upload-save-form"
                     >
-                        <textarea
-                            class=          "save-input"
-                            node_ref=       textarea
-                            id=             "text-input"
-                            placeholder=    "Paste JSON backup here"
-                            on:input=move |ev| {
-                                // resize box to fit text
-                                #[cfg(feature= "hydrate")]
-                                set_content.set(event_target_value(&ev));
-
-                                // In non-hydrate mode, mute the unused variable warning
-                                #[cfg(not(feature = "hydrate"))]
-                                let _ = ev;
-                            }
-                        > {} </textarea>
-                        <button class="upload-save-button" type="submit"> "Ok" </button>
+                        <fieldset class="restore-modes">
+                            {RestoreMode::ALL.into_iter().map(|mode| view! {
+                                <label class="restore-mode">
+                                    <input
+                                        type="radio"
+                                        name="restore_mode"
+                                        value=mode.as_str()
+                                        prop:checked=move || restore_mode.get() == mode
+                                        on:change=move |_| {
+                                            restore_mode.set(mode);
+                                            clear_preview();
+                                        }
+                                    />
+                                    { mode.label() }
+                                </label>
+                            }).collect_view()}
+                        </fieldset>
+                        {move || match auto_backup.get() {
+                            Some(file_name) => view! {
+                                <input type="hidden" name="auto_backup" value=file_name.clone() />
+                                <p class="restore-auto-backup">
+                                    { format!("Automatic backup: {}", file_name) }
+                                </p>
+                                <button
+                                    class="restore-auto-backup-cancel"
+                                    type="button"
+                                    on:click=move |_| auto_backup.set(None)
+                                >
+                                    "Upload a file instead"
+                                </button>
+                            }.into_any(),
+                            None => view! {
+                                <input
+                                    class=      "backup-file-input"
+                                    type=       "file"
+                                    name=       "backup_file"
+                                    accept=     ".json,application/json"
+                                    required=   true
+                                    on:change=  move |_| clear_preview()
+                                />
+                            }.into_any(),
+                        }}
+                        <button
+                            class="upload-save-button"
+                            type="submit"
+                            disabled=preview_pending
+                        >
+                            { move || if preview_pending.get() { "Checking..." } else { "Preview" } }
+                        </button>
                     </form>
+
+                    {move || match save_action_value.get() {
+                        Some(Err(problems)) => view! { <RestoreProblems problems=problems /> }.into_any(),
+                        _ => ().into_any(),
+                    }}
+
+                    {move || match preview_value.get() {
+                        Some(Err(e)) => view! {
+                            <p class="restore-diff-error">{e.to_string()}</p>
+                        }.into_any(),
+                        Some(Ok(Err(problems))) => view! { <RestoreProblems problems=problems /> }.into_any(),
+                        Some(Ok(Ok(diff))) => {
+                            let has_changes = diff.has_changes();
+                            view! {
+                                <RestoreDiffSummary diff=diff />
+                                <div class="restore-diff-buttons">
+                                    <button class="upload-save-button" on:click=move |_| clear_preview()> "Cancel" </button>
+                                    <Show when=move || has_changes>
+                                        <button class="upload-save-button" on:click=on_confirm> "Apply" </button>
+                                    </Show>
+                                </div>
+                            }.into_any()
+                        },
+                        None => ().into_any(),
+                    }}
                 </Show>
             </Show>
         </Show>
 
     }
 }
+
+/// Why the backup cannot be restored, by recipe
+#[component]
+fn RestoreProblems(problems: Vec<RestoreProblem>) -> impl IntoView {
+    view! {
+        <div class="restore-problems">
+            <p class="restore-diff-error">
+                { format!("The backup cannot be restored, {} problems were found:", problems.len()) }
+            </p>
+            <ul>
+                {problems.into_iter().map(|problem| view! { <li>{problem.to_string()}</li> }).collect_view()}
+            </ul>
+        </div>
+    }
+}
+
+/// What a restore would do, by recipe
+#[component]
+fn RestoreDiffSummary(diff: RestoreDiff) -> impl IntoView {
+    let renamed = diff.renamed
+        .into_iter()
+        .map(|(name, new_name)| format!("{} → {}", name, new_name))
+        .collect::<Vec<String>>();
+
+    let sections = [
+        ("Added", "added", diff.added),
+        ("Added as a copy", "added", renamed),
+        ("Changed", "changed", diff.changed),
+        ("Removed", "removed", diff.removed),
+        ("Unchanged", "unchanged", diff.unchanged),
+    ];
+
+    view! {
+        <div class="restore-diff">
+            {sections.into_iter()
+                .filter(|(_, _, names)| !names.is_empty())
+                .map(|(title, class, names)| view! {
+                    <details class=format!("restore-diff-section {}", class)>
+                        <summary>{ format!("{}: {}", title, names.len()) }</summary>
+                        <ul>
+                            {names.into_iter().map(|name| view! { <li>{name}</li> }).collect_view()}
+                        </ul>
+                    </details>
+                })
+                .collect_view()}
+        </div>
+    }
+}
@@ -0,0 +1,207 @@
+use serde::{Deserialize, Serialize};
+
+/// What a quantity measures, only quantities of the same dimension add up
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Dimension {
+    // In grams
+    Mass,
+    // In milliliters
+    Volume,
+    // Pieces, "3 eggs"
+    Count,
+    // Any other unit, by its singular name: "2 cloves", "1 pinch"
+    Other(String),
+}
+
+/// A unit as written in a recipe
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Unit {
+    // As displayed, "kg", "tbsp"
+    pub name: String,
+    pub dimension: Dimension,
+    // Grams or milliliters in one unit
+    pub factor: f64,
+}
+
+// (names, displayed name, grams in one unit)
+const MASS_UNITS: [(&[&str], &str, f64); 5] = [
+    (&["mg", "milligram", "milligrams"], "mg", 0.001),
+    (&["g", "gr", "gram", "grams", "gramme", "grammes"], "g", 1.0),
+    (&["kg", "kilo", "kilos", "kilogram", "kilograms", "kilogramme", "kilogrammes"], "kg", 1000.0),
+    (&["oz", "ounce", "ounces"], "oz", 28.3495),
+    (&["lb", "lbs", "pound", "pounds"], "lb", 453.592),
+];
+// (names, displayed name, milliliters in one unit)
+const VOLUME_UNITS: [(&[&str], &str, f64); 9] = [
+    (&["ml", "milliliter", "milliliters", "millilitre", "millilitres"], "ml", 1.0),
+    (&["cl", "centiliter", "centiliters", "centilitre", "centilitres"], "cl", 10.0),
+    (&["dl", "deciliter", "deciliters", "decilitre", "decilitres"], "dl", 100.0),
+    (&["l", "liter", "liters", "litre", "litres"], "l", 1000.0),
+    (&["tsp", "teaspoon", "teaspoons", "cc", "cac"], "tsp", 5.0),
+    (&["tbsp", "tablespoon", "tablespoons", "cs", "cas"], "tbsp", 15.0),
+    (&["fl oz", "fl. oz", "fluid ounce", "fluid ounces"], "fl oz", 29.5735),
+    (&["cup", "cups"], "cup", 240.0),
+    (&["pint", "pints"], "pint", 473.176),
+];
+
+impl Unit {
+    /// The unit of a text like "kg", "Tbsp.", "cloves", pieces without a text
+    pub fn parse(text: &str) -> Unit {
+        let text = text.trim().trim_end_matches('.').trim().to_lowercase();
+        if text.is_empty() || ["pc", "pcs", "piece", "pieces", "x"].contains(&text.as_str()) {
+            return Unit { name: "".to_string(), dimension: Dimension::Count, factor: 1.0 };
+        }
+        let known = MASS_UNITS
+            .iter()
+            .map(|unit| (unit, Dimension::Mass))
+            .chain(VOLUME_UNITS.iter().map(|unit| (unit, Dimension::Volume)))
+            .find(|((names, _, _), _)| names.contains(&text.as_str()));
+        match known {
+            Some(((_, name, factor), dimension)) => Unit { name: name.to_string(), dimension, factor: *factor },
+            None => {
+                let name = singular(&text);
+                Unit { name: name.clone(), dimension: Dimension::Other(name), factor: 1.0 }
+            }
+        }
+    }
+
+    /// The unit a total is displayed with, when it was written with different units
+    fn base(dimension: &Dimension, amount: f64) -> Unit {
+        let (name, factor) = match dimension {
+            Dimension::Mass if amount >= 1000.0 => ("kg", 1000.0),
+            Dimension::Mass => ("g", 1.0),
+            Dimension::Volume if amount >= 1000.0 => ("l", 1000.0),
+            Dimension::Volume => ("ml", 1.0),
+            Dimension::Count => ("", 1.0),
+            Dimension::Other(name) => (name.as_str(), 1.0),
+        };
+        Unit { name: name.to_string(), dimension: dimension.clone(), factor }
+    }
+}
+
+/// An amount of an ingredient, "200 g", "1 1/2 cups", "3"
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Quantity {
+    // In grams or milliliters for masses and volumes
+    pub amount: f64,
+    pub unit: Unit,
+}
+
+impl Quantity {
+    /// A number, then a unit: "200 g", "0,3kg", "1 1/2 cups", "½ tsp", "3"
+    pub fn parse(qty_unit: &str) -> Option<Quantity> {
+        use regex::Regex;
+        let qty_unit = replace_unicode_fractions(qty_unit.trim());
+        let re = Regex::new(r"^(?:(\d+)\s+)?(\d+(?:[.,]\d+)?)(?:\s*/\s*(\d+))?\s*(.*)$").unwrap();
+        let caps = re.captures(&qty_unit)?;
+
+        let whole = caps.get(1).map_or(Some(0.0), |m| m.as_str().parse::<f64>().ok())?;
+        let mut number = caps[2].replace(',', ".").parse::<f64>().ok()?;
+        if let Some(denominator) = caps.get(3) {
+            let denominator = denominator.as_str().parse::<f64>().ok()?;
+            if denominator == 0.0 {
+                return None;
+            }
+            number /= denominator;
+        } else if caps.get(1).is_some() {
+            // "1 2" is not a quantity
+            return None;
+        }
+
+        let unit = Unit::parse(&caps[4]);
+        Some(Quantity { amount: (whole + number) * unit.factor, unit })
+    }
+
+    pub fn scaled(self, multiplier: f64) -> Quantity {
+        Quantity { amount: self.amount * multiplier, ..self }
+    }
+
+    /// Sum of quantities of the same dimension, in their unit if they all share it
+    pub fn sum(quantities: &[Quantity]) -> Option<Quantity> {
+        let first = quantities.first()?;
+        if quantities.iter().any(|quantity| quantity.unit.dimension != first.unit.dimension) {
+            return None;
+        }
+        let amount: f64 = quantities.iter().map(|quantity| quantity.amount).sum();
+        let unit = if quantities.iter().all(|quantity| quantity.unit.name == first.unit.name) {
+            first.unit.clone()
+        } else {
+            Unit::base(&first.unit.dimension, amount)
+        };
+        Some(Quantity { amount, unit })
+    }
+
+    /// Enough of this quantity for the needed one, if they can be compared
+    pub fn covers(&self, needed: &Quantity) -> Option<bool> {
+        (self.unit.dimension == needed.unit.dimension).then_some(self.amount >= needed.amount)
+    }
+}
+
+impl std::fmt::Display for Quantity {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let number = format_number(self.amount / self.unit.factor);
+        if self.unit.name.is_empty() {
+            write!(f, "{}", number)
+        } else {
+            write!(f, "{} {}", number, self.unit.name)
+        }
+    }
+}
+
+/// At most 2 decimals, without trailing zeros: "0.5", "12", "1.33"
+pub fn format_number(number: f64) -> String {
+    format!("{:.2}", number)
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+/// Ingredient name to compare ingredients between recipes:
+/// "[[Tomatoes]] (ripe), diced" and "tomato" are both "tomato"
+pub fn normalize_ingredient_name(content: &str) -> String {
+    let content = content.replace(['[', ']'], "");
+    let content = content.split([',', '(']).next().unwrap_or_default();
+    content
+        .split_whitespace()
+        .map(|word| singular(&word.to_lowercase()))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Displayed ingredient name, without links and preparation notes
+pub fn ingredient_display_name(content: &str) -> String {
+    let content = content.replace(['[', ']'], "");
+    content.split([',', '(']).next().unwrap_or_default().trim().to_string()
+}
+
+// Good enough for units and ingredient names: "tomatoes", "cloves", "eggs"
+fn singular(word: &str) -> String {
+    if let Some(stem) = word.strip_suffix("oes") {
+        format!("{}o", stem)
+    } else if let Some(stem) = word.strip_suffix("ies").filter(|stem| stem.len() > 1) {
+        format!("{}y", stem)
+    } else if word.len() > 3 && word.ends_with('s') && !word.ends_with("ss") {
+        word[..word.len() - 1].to_string()
+    } else {
+        word.to_string()
+    }
+}
+
+fn replace_unicode_fractions(text: &str) -> String {
+    const FRACTIONS: [(char, &str); 7] =
+        [('½', "1/2"), ('⅓', "1/3"), ('⅔', "2/3"), ('¼', "1/4"), ('¾', "3/4"), ('⅛', "1/8"), ('⅕', "1/5")];
+    let mut out = String::new();
+    for c in text.chars() {
+        match FRACTIONS.iter().find(|(fraction, _)| *fraction == c) {
+            // "1½" is "1 1/2"
+            Some((_, replacement)) => {
+                if out.ends_with(|c: char| c.is_ascii_digit()) {
+                    out.push(' ');
+                }
+                out.push_str(replacement);
+            }
+            None => out.push(c),
+        }
+    }
+    out
+}
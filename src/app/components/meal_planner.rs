@@ -0,0 +1,493 @@
+use crate::app::{LoginCheckResource, MealPlanServerAction, RecipeServerAction, RecipesLightResource};
+use chrono::{Datelike, Days, NaiveDate};
+use leptos::ev::{DragEvent, MouseEvent};
+use leptos::logging::*;
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Dates are stored and sent as "2026-10-19"
+pub const PLAN_DATE_FORMAT: &str = "%Y-%m-%d";
+pub const DEFAULT_SERVINGS: u16 = 2;
+pub const MAX_SERVINGS: u16 = 99;
+
+pub fn parse_plan_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date, PLAN_DATE_FORMAT).ok()
+}
+
+pub fn format_plan_date(date: NaiveDate) -> String {
+    date.format(PLAN_DATE_FORMAT).to_string()
+}
+
+/// Monday of the week of the date
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Days::new(date.weekday().num_days_from_monday() as u64)
+}
+
+pub fn today() -> NaiveDate {
+    chrono::Local::now().date_naive()
+}
+
+/// The planner page of the week of the date
+pub fn planner_url(date: NaiveDate) -> String {
+    format!("/planner/{}", format_plan_date(week_start(date)))
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MealSlot {
+    Breakfast,
+    Lunch,
+    #[default]
+    Dinner,
+}
+impl MealSlot {
+    pub const ALL: [MealSlot; 3] = [MealSlot::Breakfast, MealSlot::Lunch, MealSlot::Dinner];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MealSlot::Breakfast => "breakfast",
+            MealSlot::Lunch => "lunch",
+            MealSlot::Dinner => "dinner",
+        }
+    }
+    pub fn label(&self) -> &'static str {
+        match self {
+            MealSlot::Breakfast => "Breakfast",
+            MealSlot::Lunch => "Lunch",
+            MealSlot::Dinner => "Dinner",
+        }
+    }
+}
+impl std::str::FromStr for MealSlot {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        MealSlot::ALL
+            .into_iter()
+            .find(|slot| slot.as_str() == s)
+            .ok_or_else(|| format!("Unknown meal slot: {}", s))
+    }
+}
+
+/// A recipe planned for a meal
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MealPlanEntry {
+    pub id: u32,
+    pub date: String,
+    pub slot: MealSlot,
+    pub recipe_id: u16,
+    pub recipe_name: String,
+    pub servings: u16,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum MealPlanActionDescriptor {
+    Add {
+        date: String,
+        slot: MealSlot,
+        recipe_id: u16,
+        servings: u16,
+    },
+    // With entry ID, to the end of another meal
+    Move {
+        id: u32,
+        date: String,
+        slot: MealSlot,
+    },
+    SetServings {
+        id: u32,
+        servings: u16,
+    },
+    // With entry ID
+    Remove(u32),
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use super::*;
+    use sqlx::SqliteConnection;
+
+    #[derive(sqlx::FromRow)]
+    pub struct DbRowMealPlanEntry {
+        pub id: u32,
+        pub plan_date: String,
+        pub meal_slot: String,
+        pub recipe_id: u16,
+        pub recipe_name: String,
+        pub servings: u16,
+    }
+    impl DbRowMealPlanEntry {
+        pub fn to_entry(self) -> Result<MealPlanEntry, ServerFnError> {
+            let slot = match self.meal_slot.parse() {
+                Ok(slot) => slot,
+                Err(e) => return Err(ServerFnError::ServerError(e)),
+            };
+            Ok(MealPlanEntry {
+                id: self.id,
+                date: self.plan_date,
+                slot,
+                recipe_id: self.recipe_id,
+                recipe_name: self.recipe_name,
+                servings: self.servings,
+            })
+        }
+    }
+
+    /// The entries from `from` included to `to` excluded, by date then in their meal order
+    pub async fn meal_plan_entries(
+        conn: &mut SqliteConnection,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<MealPlanEntry>, ServerFnError> {
+        sqlx::query_as::<_, DbRowMealPlanEntry>(
+            "SELECT m.id, m.plan_date, m.meal_slot, m.recipe_id, COALESCE(r.recipe_name, '') AS recipe_name, m.servings
+            FROM meal_plan m
+            JOIN recipes r ON r.id = m.recipe_id
+            WHERE m.plan_date >= $1 AND m.plan_date < $2
+            ORDER BY m.plan_date, m.position, m.id",
+        )
+        .bind(format_plan_date(from))
+        .bind(format_plan_date(to))
+        .fetch_all(conn)
+        .await?
+        .into_iter()
+        .map(DbRowMealPlanEntry::to_entry)
+        .collect()
+    }
+
+    // Only real dates are stored, so they sort and compare as text
+    pub fn checked_date(date: &str) -> Result<String, ServerFnError> {
+        parse_plan_date(date)
+            .map(format_plan_date)
+            .ok_or_else(|| ServerFnError::ServerError(format!("Invalid date: {}", date)))
+    }
+}
+
+/// The meals of the week starting at `week_start`
+#[server]
+pub async fn get_meal_plan_week(week_start: String) -> Result<Vec<MealPlanEntry>, ServerFnError> {
+    use self::ssr::*;
+    use crate::app::components::recipe_server_functions::ssr::db;
+
+    let Some(from) = parse_plan_date(&week_start) else {
+        return Err(ServerFnError::ServerError(format!("Invalid date: {}", week_start)));
+    };
+    let mut conn = db().await?;
+    meal_plan_entries(&mut conn, from, from + Days::new(7)).await
+}
+
+#[server]
+pub async fn meal_plan_function(action: MealPlanActionDescriptor) -> Result<(), ServerFnError> {
+    use self::ssr::*;
+    use crate::app::components::auth::auth_server_functions::check_login;
+    use crate::app::components::recipe_server_functions::ssr::db;
+
+    if !check_login().await? {
+        return Err(ServerFnError::ServerError("Not logged in.".to_string()));
+    }
+
+    let mut conn = db().await?;
+
+    // New entries go at the end of their meal
+    const NEXT_POSITION: &str =
+        "(SELECT COALESCE(MAX(position), -1) + 1 FROM meal_plan WHERE plan_date = $1 AND meal_slot = $2)";
+
+    match action {
+        MealPlanActionDescriptor::Add { date, slot, recipe_id, servings } => {
+            sqlx::query(&format!(
+                "INSERT INTO meal_plan (plan_date, meal_slot, recipe_id, servings, position) VALUES ($1, $2, $3, $4, {});",
+                NEXT_POSITION
+            ))
+            .bind(checked_date(&date)?)
+            .bind(slot.as_str())
+            .bind(recipe_id)
+            .bind(servings.clamp(1, MAX_SERVINGS))
+            .execute(&mut conn)
+            .await?;
+            log!("Recipe with ID: {:?} was planned for the {:?} of {:?}", recipe_id, slot, date);
+        }
+        MealPlanActionDescriptor::Move { id, date, slot } => {
+            sqlx::query(&format!(
+                "UPDATE meal_plan SET plan_date = $1, meal_slot = $2, position = {} WHERE id = $3;",
+                NEXT_POSITION
+            ))
+            .bind(checked_date(&date)?)
+            .bind(slot.as_str())
+            .bind(id)
+            .execute(&mut conn)
+            .await?;
+        }
+        MealPlanActionDescriptor::SetServings { id, servings } => {
+            sqlx::query("UPDATE meal_plan SET servings = $1 WHERE id = $2;")
+                .bind(servings.clamp(1, MAX_SERVINGS))
+                .bind(id)
+                .execute(&mut conn)
+                .await?;
+        }
+        MealPlanActionDescriptor::Remove(id) => {
+            sqlx::query("DELETE FROM meal_plan WHERE id = $1;")
+                .bind(id)
+                .execute(&mut conn)
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Plan a recipe for a day, from the recipe card or the recipe menu
+#[component]
+pub fn AddToMealPlan(recipe_id: u16) -> impl IntoView {
+    let meal_plan_action = use_context::<MealPlanServerAction>()
+        .expect("To find MealPlanServerAction in context.")
+        .0;
+
+    let date = RwSignal::new(format_plan_date(today()));
+    let slot = RwSignal::new(MealSlot::default());
+    let servings = RwSignal::new(DEFAULT_SERVINGS);
+    // The day the recipe was added to, to link its week
+    let added_to = RwSignal::new(None::<NaiveDate>);
+
+    let on_add = move |ev: MouseEvent| {
+        ev.stop_propagation();
+        let Some(plan_date) = parse_plan_date(&date.get_untracked()) else {
+            return;
+        };
+        meal_plan_action.dispatch(MealPlanActionDescriptor::Add {
+            date: format_plan_date(plan_date),
+            slot: slot.get_untracked(),
+            recipe_id,
+            servings: servings.get_untracked(),
+        });
+        added_to.set(Some(plan_date));
+    };
+
+    view! {
+        <div
+            class="add-to-meal-plan"
+            on:click=|ev| ev.stop_propagation()
+        >
+            <input
+                class="add-to-meal-plan-date"
+                type="date"
+                prop:value=date
+                on:input=move |ev| date.set(event_target_value(&ev))
+            />
+            <select
+                class="add-to-meal-plan-slot"
+                on:change=move |ev| {
+                    if let Ok(new_slot) = event_target_value(&ev).parse() {
+                        slot.set(new_slot);
+                    }
+                }
+            >
+                {MealSlot::ALL.into_iter().map(|meal_slot| view! {
+                    <option
+                        value=meal_slot.as_str()
+                        prop:selected=move || slot.get() == meal_slot
+                    >
+                        { meal_slot.label() }
+                    </option>
+                }).collect_view()}
+            </select>
+            <label class="add-to-meal-plan-servings">
+                <input
+                    type="number"
+                    min="1"
+                    max=MAX_SERVINGS.to_string()
+                    prop:value=move || servings.get().to_string()
+                    on:input=move |ev| {
+                        if let Ok(value) = event_target_value(&ev).parse::<u16>() {
+                            servings.set(value.clamp(1, MAX_SERVINGS));
+                        }
+                    }
+                />
+                "servings"
+            </label>
+            <button
+                class="add-to-meal-plan-button"
+                on:click=on_add
+            >
+                "Add to planner"
+            </button>
+            {move || added_to.get().map(|plan_date| view! {
+                <a class="add-to-meal-plan-link" href=planner_url(plan_date)>
+                    { format!("Planned for {}, see the week", plan_date.format("%A %-d %B")) }
+                </a>
+            })}
+        </div>
+    }
+}
+
+/// The meals of a week, by day and meal slot
+#[component]
+pub fn MealPlanWeek(#[prop(into)] week_start: Signal<NaiveDate>) -> impl IntoView {
+    // Is logged in
+    let check_login_resource = use_context::<LoginCheckResource>()
+        .expect("Expected to find LoginCheckAction in context")
+        .0;
+    let is_logged_in = move || check_login_resource.get() == Some(true);
+
+    let meal_plan_action = use_context::<MealPlanServerAction>()
+        .expect("To find MealPlanServerAction in context.")
+        .0;
+    let recipe_action = use_context::<RecipeServerAction>()
+        .expect("To find RecipeServerAction in context.")
+        .0;
+    let all_recipes_light = use_context::<RecipesLightResource>()
+        .expect("To find RecipesLightResource in context.")
+        .0;
+
+    // Deleted recipes leave the plan with them
+    let entries = Resource::new(
+        move || (week_start.get(), meal_plan_action.version().get(), recipe_action.version().get()),
+        |(week_start, _, _)| async move {
+            get_meal_plan_week(format_plan_date(week_start)).await.map_err(|e| {
+                error!("ERROR: Could not get the meal plan: {:?}", e.to_string());
+                e.to_string()
+            })
+        },
+    );
+
+    // The entry being dragged to another meal
+    let dragged = RwSignal::new(None::<u32>);
+
+    let move_entry = move |id: u32, date: NaiveDate, slot: MealSlot| {
+        meal_plan_action.dispatch(MealPlanActionDescriptor::Move { id, date: format_plan_date(date), slot });
+    };
+
+    let meal_view = move |date: NaiveDate, slot: MealSlot, meal_entries: Vec<MealPlanEntry>| {
+        let entries_view = meal_entries.into_iter().map(|entry| {
+            let MealPlanEntry { id, recipe_id, recipe_name, servings, .. } = entry;
+            view! {
+                <li
+                    class="planner-entry"
+                    draggable=move || is_logged_in().to_string()
+                    on:dragstart=move |ev: DragEvent| {
+                        // Firefox only starts a drag with some data, the entry itself is kept in a signal
+                        if let Some(data) = ev.data_transfer() {
+                            let _ = data.set_data("text/plain", &format!("meal-plan-entry-{}", id));
+                        }
+                        dragged.set(Some(id));
+                    }
+                    on:dragend=move |_| dragged.set(None)
+                >
+                    <a class="planner-entry-name" href=format!("/recipe/{}/display", recipe_id)>
+                        { recipe_name }
+                    </a>
+                    <Show when=is_logged_in>
+                        <div class="planner-entry-buttons">
+                            <button
+                                class="planner-entry-button"
+                                on:click=move |_| move_entry(id, date - Days::new(1), slot)
+                            >"←"</button>
+                            <button
+                                class="planner-entry-button"
+                                on:click=move |_| meal_plan_action.dispatch(
+                                    MealPlanActionDescriptor::SetServings { id, servings: servings.saturating_sub(1).max(1) }
+                                )
+                            >"-"</button>
+                            <span class="planner-entry-servings">{ servings }</span>
+                            <button
+                                class="planner-entry-button"
+                                on:click=move |_| meal_plan_action.dispatch(
+                                    MealPlanActionDescriptor::SetServings { id, servings: servings + 1 }
+                                )
+                            >"+"</button>
+                            <button
+                                class="planner-entry-button"
+                                on:click=move |_| move_entry(id, date + Days::new(1), slot)
+                            >"→"</button>
+                            <button
+                                class="planner-entry-button remove"
+                                on:click=move |_| meal_plan_action.dispatch(MealPlanActionDescriptor::Remove(id))
+                            >"×"</button>
+                        </div>
+                    </Show>
+                </li>
+            }
+        }).collect_view();
+
+        view! {
+            <div
+                class="planner-meal"
+                on:dragover=move |ev: DragEvent| {
+                    if dragged.get_untracked().is_some() {
+                        // Allows the drop
+                        ev.prevent_default();
+                    }
+                }
+                on:drop=move |ev: DragEvent| {
+                    ev.prevent_default();
+                    if let Some(id) = dragged.get_untracked() {
+                        dragged.set(None);
+                        move_entry(id, date, slot);
+                    }
+                }
+            >
+                <span class="planner-meal-name">{ slot.label() }</span>
+                <ul class="planner-entries">{ entries_view }</ul>
+                <Show when=is_logged_in>
+                    <select
+                        class="planner-add-recipe"
+                        on:change=move |ev| {
+                            if let Ok(recipe_id) = event_target_value(&ev).parse::<u16>() {
+                                meal_plan_action.dispatch(MealPlanActionDescriptor::Add {
+                                    date: format_plan_date(date),
+                                    slot,
+                                    recipe_id,
+                                    servings: DEFAULT_SERVINGS,
+                                });
+                            }
+                        }
+                    >
+                        <option value="" selected=true>"+ Add a recipe"</option>
+                        {move || all_recipes_light.get()
+                            .and_then(Result::ok)
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(|recipe| view! { <option value=recipe.id.to_string()>{ recipe.name }</option> })
+                            .collect_view()}
+                    </select>
+                </Show>
+            </div>
+        }
+    };
+
+    view! {
+        <Transition
+            fallback=move || view! { <p class="planner-info">"Loading the week..."</p> }
+        >
+            {move || {
+                let week_start = week_start.get();
+                match entries.get() {
+                    Some(Ok(entries)) => view! {
+                        <div class="planner-grid">
+                            {(0..7).map(|day| {
+                                let date = week_start + Days::new(day);
+                                let meals = MealSlot::ALL.into_iter().map(|slot| {
+                                    let meal_entries: Vec<MealPlanEntry> = entries
+                                        .iter()
+                                        .filter(|entry| entry.slot == slot && parse_plan_date(&entry.date) == Some(date))
+                                        .cloned()
+                                        .collect();
+                                    meal_view(date, slot, meal_entries)
+                                }).collect_view();
+                                view! {
+                                    <div
+                                        class="planner-day"
+                                        class:today=date == today()
+                                    >
+                                        <h3 class="planner-day-name">{ date.format("%A %-d").to_string() }</h3>
+                                        { meals }
+                                    </div>
+                                }
+                            }).collect_view()}
+                        </div>
+                    }.into_any(),
+                    Some(Err(e)) => view! { <p class="planner-info">{e}</p> }.into_any(),
+                    None => ().into_any(),
+                }
+            }}
+        </Transition>
+    }
+}
@@ -1,7 +1,21 @@
 pub mod auth;
+pub mod auto_backup;
+pub mod backup;
+pub mod cook_log;
+pub mod cook_mode;
+pub mod cooking_timers;
 pub mod download_upload;
+pub mod ingredient_units;
+pub mod meal_planner;
 pub mod pages;
+pub mod pantry;
 pub mod recipe;
+pub mod recipe_export;
+pub mod recipe_images;
+pub mod recipe_importers;
+pub mod recipe_pdf;
 pub mod recipe_server_functions;
 pub mod recipe_sheets;
+pub mod schema_org;
+pub mod shopping_list;
 pub mod tags;
@@ -14,9 +14,19 @@ use elements::{
 
 use crate::app::{
     components::{
-        download_upload::{DownloadAll, UploadAll},
+        auto_backup::AutoBackupList,
+        collections::CollectionFilterSelect,
+        cook_log::RecipeListOrder,
+        cook_mode::CookRecipeSheet,
+        download_upload::{DownloadAll, DownloadBook, UploadAll},
+        meal_planner::{format_plan_date, parse_plan_date, planner_url, today, week_start, MealPlanWeek},
+        pantry::{CookableRecipes, PantryList},
+        recipe_importers::ImportRecipes,
+        recipe_pdf::PdfBookForm,
         recipe_server_functions::*,
         recipe_sheets::{EditableRecipeSheet, RecipeCard, RecipeSheet},
+        schema_org::{ImportedRecipe, RecipeImportForm},
+        shopping_list::{ShoppingListActionDescriptor, ShoppingListForm, ShoppingListSource, ShoppingListView},
         tags::*,
     },
     elements::molecules::*,
@@ -116,6 +126,14 @@ pub fn NewRecipePage() -> impl IntoView {
     let is_page_dirty = RwSignal::new(false);
     provide_context(IsPageDirtySignal(is_page_dirty));
 
+    // Recipe read from a web page, to prefill the sheet
+    let imported_recipe: RwSignal<Option<ImportedRecipe>> = RwSignal::new(None);
+    Effect::new(move |_| {
+        if imported_recipe.read().is_some() {
+            is_page_dirty.set(true);
+        }
+    });
+
     // Setup action
     let recipe_action = use_context::<RecipeServerAction>()
         .expect("To find RecipeServerAction in context.")
@@ -181,9 +199,24 @@ pub fn NewRecipePage() -> impl IntoView {
         <div
             class="main-content"
         >
-            <EditableRecipeSheet
-                is_new_recipe=  true
+            <RecipeImportForm
+                imported_recipe=imported_recipe
             />
+
+            // Rebuilt with the imported recipe, for review before saving
+            {move || match imported_recipe.get() {
+                Some(imported) => view! {
+                    <EditableRecipeSheet
+                        recipe=         imported.recipe
+                        is_new_recipe=  true
+                    />
+                }.into_any(),
+                None => view! {
+                    <EditableRecipeSheet
+                        is_new_recipe=  true
+                    />
+                }.into_any(),
+            }}
         </div>
 
         <Show
@@ -220,6 +253,7 @@ pub enum RecipePageMode {
     Display,
     Editable,
     Print,
+    Cook,
 }
 impl std::str::FromStr for RecipePageMode {
     type Err = ParamsError;
@@ -229,6 +263,7 @@ impl std::str::FromStr for RecipePageMode {
             "display" => Ok(RecipePageMode::Display),
             "editable" => Ok(RecipePageMode::Editable),
             "print" => Ok(RecipePageMode::Print),
+            "cook" => Ok(RecipePageMode::Cook),
             _ => Err(ParamsError::Params(Arc::new(ParseRecipePageModeError))),
         }
     }
@@ -276,6 +311,7 @@ pub fn RecipePage() -> impl IntoView {
             RecipePageMode::Display => "Display Recipe",
             RecipePageMode::Editable => "Edit Recipe",
             RecipePageMode::Print => "Print Recipe",
+            RecipePageMode::Cook => "Cook Recipe",
         });
     });
 
@@ -359,6 +395,14 @@ pub fn RecipePage() -> impl IntoView {
                                     recipe= recipe
                                 />
                             }.into_any()
+                        },
+                        RecipePageMode::Cook => {
+                            // Cook Recipe, one step at a time
+                            view! {
+                                <CookRecipeSheet
+                                    recipe= recipe
+                                />
+                            }.into_any()
                         }
                     }
                 } else {
@@ -375,7 +419,7 @@ pub fn RecipePage() -> impl IntoView {
 }
 
 // Colors
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
 pub enum ThemeColor {
     Color1,
     Color2,
@@ -385,6 +429,28 @@ pub enum ThemeColor {
 }
 use ThemeColor::*;
 impl ThemeColor {
+    /// The colors that can be chosen, Undefined is only a fallback
+    pub const ALL: [ThemeColor; 4] = [Color1, Color2, Color3, Color4];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Color1  => "color-1",
+            Color2  => "color-2",
+            Color3  => "color-3",
+            Color4  => "color-4",
+            Undefined    => "undefined",
+        }
+    }
+    pub fn label(&self) -> &'static str {
+        match self {
+            Color1  => "Color 1",
+            Color2  => "Color 2",
+            Color3  => "Color 3",
+            Color4  => "Color 4",
+            Undefined    => "No color",
+        }
+    }
+
     pub fn main_color(&self) -> String {
         match self {
             Color1  => "var(--theme-color-1)",
@@ -435,6 +501,10 @@ impl ThemeColor {
 
         "color: ".to_string() + &col + ";"
     }
+    /// Text and border in the color, for outlined buttons
+    pub fn as_outline_color(&self) -> String {
+        self.as_visible_color() + " border-color: currentColor;"
+    }
 
     pub fn random() -> Self {
         use rand::Rng;
@@ -449,6 +519,18 @@ impl ThemeColor {
     }
 }
 
+impl std::str::FromStr for ThemeColor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ThemeColor::ALL
+            .into_iter()
+            .chain([Undefined])
+            .find(|color| color.as_str() == s)
+            .ok_or_else(|| format!("Unknown color: {}", s))
+    }
+}
+
 // Popup Colors
 #[derive(Clone, Copy)]
 pub enum PopupColor {
@@ -518,6 +600,23 @@ pub fn AllRecipes() -> impl IntoView {
 
     let search_input = RwSignal::<Vec<String>>::new(vec![]);
 
+    let selected_collection = use_context::<SelectedCollectionSignal>()
+        .expect("To find SelectedCollectionSignal in context.")
+        .0;
+    let user_collections = use_context::<UserCollectionsResource>()
+        .expect("To find UserCollectionsResource in context.")
+        .0;
+
+    // Selecting a category also selects the tags under it
+    let tag_usages = use_context::<TagUsagesResource>()
+        .expect("To find TagUsagesResource in context.")
+        .0;
+
+    let list_order = RwSignal::new(RecipeListOrder::default());
+    let cook_stats = use_context::<CookStatsResource>()
+        .expect("To find CookStatsResource in context.")
+        .0;
+
     let request_search_clear = RwSignal::new(false);
 
     let all_recipes_light = use_context::<RecipesLightResource>()
@@ -585,10 +684,30 @@ pub fn AllRecipes() -> impl IntoView {
                         all_tags=all_tags_signal
                         selected_tags_signal=selected_tags_signal
                     />
+                    <Show when=move || check_login_resource.get() == Some(true)>
+                        <CollectionFilterSelect selected=selected_collection />
+                    </Show>
                     <RecipeSearchBar
                         search_input=search_input
                         request_search_clear=request_search_clear
                     />
+                    <select
+                        class="recipe-list-order"
+                        on:change=move |ev| {
+                            if let Ok(order) = event_target_value(&ev).parse() {
+                                list_order.set(order);
+                            }
+                        }
+                    >
+                        {RecipeListOrder::ALL.into_iter().map(|order| view! {
+                            <option
+                                value=order.as_str()
+                                prop:selected=move || list_order.get() == order
+                            >
+                                { order.label() }
+                            </option>
+                        }).collect_view()}
+                    </select>
                 </div>
 
                 // Selected tags
@@ -656,12 +775,31 @@ pub fn AllRecipes() -> impl IntoView {
 
                                         // filter tags
                                         if !sel_tags.is_empty() {
-                                            recipes.retain(|recipe| recipe.1.has_tags(&sel_tags));
+                                            let usages = tag_usages.get().and_then(|usages| usages.ok()).unwrap_or_default();
+                                            let tag_groups: Vec<Vec<String>> = sel_tags
+                                                .iter()
+                                                .map(|tag| tag_with_descendants(&usages, tag))
+                                                .collect();
+                                            recipes.retain(|recipe| recipe.1.has_tag_groups(&tag_groups));
+                                        }
+                                        // filter collection
+                                        let collection_ids = (check_login_resource.get() == Some(true))
+                                            .then(|| user_collections.get().and_then(|res| res.ok()))
+                                            .flatten()
+                                            .and_then(|user_collections| selected_collection.get().recipe_ids(&user_collections));
+                                        if let Some(collection_ids) = collection_ids {
+                                            recipes.retain(|recipe| collection_ids.contains(&recipe.1.id));
                                         }
                                         // filter search
                                         if !search_input_value.is_empty() {
                                             recipes.retain(|recipe| recipe.1.is_in_search(&search_input_value));
                                         }
+                                        // sort, the color of a recipe stays with it
+                                        let order = list_order.get();
+                                        if order != RecipeListOrder::Default {
+                                            let stats = cook_stats.get().and_then(|stats| stats.ok()).unwrap_or_default();
+                                            recipes.sort_by_cached_key(|recipe| order.sort_key(&stats, recipe.1.id));
+                                        }
 
                                         // Fetch the current Color Map
                                         let color_map = use_context::<RecipesColorMap>()
@@ -730,6 +868,10 @@ pub fn BackupPage() -> impl IntoView {
     set_page_name("Backup");
 
     let has_been_backed_up: RwSignal<bool> = RwSignal::new(false);
+    // Automatic backup selected for restore
+    let auto_backup: RwSignal<Option<String>> = RwSignal::new(None);
+    // Backup of this Cook Book, or import from other apps
+    let import_mode: RwSignal<bool> = RwSignal::new(false);
 
     view! {
 
@@ -748,14 +890,235 @@ pub fn BackupPage() -> impl IntoView {
             <BackButtonSVG backup_page=true />
         </button>
 
+        <div class="save-page-modes">
+            <button
+                class="save-page-mode"
+                class:selected=move || !import_mode.get()
+                on:click=move |_| import_mode.set(false)
+            >
+                "Backup"
+            </button>
+            <button
+                class="save-page-mode"
+                class:selected=import_mode
+                on:click=move |_| import_mode.set(true)
+            >
+                "Import"
+            </button>
+        </div>
+
         //<h2>"Download current Cook Book save or Upload save to current Cook Book."</h2>
-        <div class="save-page-container" >
-            <DownloadAll
-                has_been_backed_up = has_been_backed_up
-            />
-            <UploadAll
-                has_been_backed_up = has_been_backed_up
+        <Show
+            when=move || !import_mode.get()
+            fallback=|| view! {
+                <div class="save-page-container import" >
+                    <ImportRecipes/>
+                </div>
+            }
+        >
+            <div class="save-page-container" >
+                <DownloadAll
+                    has_been_backed_up = has_been_backed_up
+                />
+                <DownloadBook/>
+                <PdfBookForm/>
+                <AutoBackupList
+                    auto_backup = auto_backup
+                />
+                <UploadAll
+                    has_been_backed_up = has_been_backed_up
+                    auto_backup = auto_backup
+                />
+            </div>
+        </Show>
+    }
+}
+
+#[derive(Params, PartialEq, Clone, Default)]
+struct PlannerWeekParam {
+    week: Option<String>,
+}
+
+/// The meal planner, by week, the current one without a week in the path
+#[component]
+pub fn PlannerPage() -> impl IntoView {
+    set_page_name("Planner");
+
+    // Any day of the week works, the week starts on Monday
+    let planner_week = Memo::new(move |_| {
+        let date = use_params::<PlannerWeekParam>()
+            .get()
+            .unwrap_or_default()
+            .week
+            .and_then(|week| parse_plan_date(&week))
+            .unwrap_or_else(today);
+        week_start(date)
+    });
+
+    // Is logged in
+    let check_login_resource = use_context::<LoginCheckResource>()
+        .expect("Expected to find LoginCheckAction in context")
+        .0;
+
+    let shopping_list_action = use_context::<ShoppingListServerAction>()
+        .expect("To find ShoppingListServerAction in context.")
+        .0;
+    let on_shopping_list_click = move |_| {
+        let week = format_plan_date(planner_week.get_untracked());
+        shopping_list_action.dispatch(ShoppingListActionDescriptor::Generate(ShoppingListSource::Week(week)));
+        let navigate = leptos_router::hooks::use_navigate();
+        navigate("/shopping-list", Default::default());
+    };
+
+    let week_title = move || {
+        let week = planner_week.get();
+        let week_end = week + chrono::Days::new(6);
+        format!("{} - {}", week.format("%-d %B"), week_end.format("%-d %B %Y"))
+    };
+
+    view! {
+
+        <SettingsMenu/>
+
+        <button
+            class="recipe-menu-button back backup-page"
+            on:click=move |ev| {
+                ev.stop_propagation();
+                let navigate = leptos_router::hooks::use_navigate();
+                navigate("/", Default::default());
+            }
+        >
+            <BackButtonSVG backup_page=true />
+        </button>
+
+        <div class="planner-page">
+            <div class="planner-navigation">
+                <a
+                    class="planner-navigation-button"
+                    href=move || planner_url(planner_week.get() - chrono::Days::new(7))
+                >
+                    "←"
+                </a>
+                <h2 class="planner-week-title">{ week_title }</h2>
+                <a
+                    class="planner-navigation-button"
+                    href=move || planner_url(planner_week.get() + chrono::Days::new(7))
+                >
+                    "→"
+                </a>
+                <Show when=move || planner_week.get() != week_start(today())>
+                    <a
+                        class="planner-navigation-button this-week"
+                        href=planner_url(today())
+                    >
+                        "This week"
+                    </a>
+                </Show>
+            </div>
+            <MealPlanWeek
+                week_start = planner_week
             />
+            <Show when=move || check_login_resource.get() == Some(true)>
+                <button
+                    class="planner-shopping-list-button"
+                    on:click=on_shopping_list_click
+                >
+                    "Shopping list for this week"
+                </button>
+            </Show>
+        </div>
+    }
+}
+
+/// The shared shopping list, and a new one from picked recipes
+#[component]
+pub fn ShoppingListPage() -> impl IntoView {
+    set_page_name("Shopping List");
+
+    // Is logged in
+    let check_login_resource = use_context::<LoginCheckResource>()
+        .expect("Expected to find LoginCheckAction in context")
+        .0;
+
+    view! {
+
+        <SettingsMenu/>
+
+        <button
+            class="recipe-menu-button back backup-page"
+            on:click=move |ev| {
+                ev.stop_propagation();
+                let navigate = leptos_router::hooks::use_navigate();
+                navigate("/", Default::default());
+            }
+        >
+            <BackButtonSVG backup_page=true />
+        </button>
+
+        <div class="shopping-list-page">
+            <Show when=move || check_login_resource.get() == Some(true)>
+                <ShoppingListForm/>
+            </Show>
+            <ShoppingListView/>
+        </div>
+    }
+}
+
+/// The items on hand, and the recipes they make
+#[component]
+pub fn PantryPage() -> impl IntoView {
+    set_page_name("Pantry");
+
+    view! {
+
+        <SettingsMenu/>
+
+        <button
+            class="recipe-menu-button back backup-page"
+            on:click=move |ev| {
+                ev.stop_propagation();
+                let navigate = leptos_router::hooks::use_navigate();
+                navigate("/", Default::default());
+            }
+        >
+            <BackButtonSVG backup_page=true />
+        </button>
+
+        <div class="pantry-page">
+            <h2 class="pantry-title">"Pantry"</h2>
+            <PantryList/>
+            <CookableRecipes/>
+        </div>
+    }
+}
+
+#[component]
+pub fn TagsPage() -> impl IntoView {
+    set_page_name("Tags");
+
+    view! {
+
+        <CheckLogin/>
+
+        <SettingsMenu/>
+
+        <button
+            class="recipe-menu-button back backup-page"
+            on:click=move |ev| {
+                ev.stop_propagation();
+                let navigate = leptos_router::hooks::use_navigate();
+                navigate("/", Default::default());
+            }
+        >
+            <BackButtonSVG backup_page=true />
+        </button>
+
+        <div class="tag-admin-page">
+            <h2 class="tag-admin-title">"Tags"</h2>
+            <p class="tag-admin-info">
+                "Renaming a tag to an existing one merges them. Changes apply to every recipe."
+            </p>
+            <TagAdminList/>
         </div>
     }
 }
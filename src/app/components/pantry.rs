@@ -0,0 +1,443 @@
+use crate::app::components::meal_planner::{parse_plan_date, today};
+use crate::app::{LoginCheckResource, PantryServerAction, RecipeServerAction};
+use chrono::Days;
+use leptos::logging::*;
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Items expiring within these days are used first
+pub const EXPIRING_SOON_DAYS: u64 = 3;
+
+/// An item on hand
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PantryItem {
+    pub id: u32,
+    pub name: String,
+    // As written, "500 g", "2"
+    pub quantity: Option<String>,
+    // "2026-10-19"
+    pub expires_on: Option<String>,
+}
+impl PantryItem {
+    /// Expired, or expiring within `EXPIRING_SOON_DAYS`
+    pub fn is_expiring(&self) -> bool {
+        self.expires_on
+            .as_deref()
+            .and_then(parse_plan_date)
+            .is_some_and(|date| date <= today() + Days::new(EXPIRING_SOON_DAYS))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum PantryActionDescriptor {
+    Add(PantryItem),
+    Update(PantryItem),
+    // With item ID
+    Remove(u32),
+}
+
+/// A recipe and what the pantry has of its ingredients
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CookableRecipe {
+    pub recipe_id: u16,
+    pub recipe_name: String,
+    pub covered: usize,
+    pub total: usize,
+    // Ingredients missing from the pantry, as written
+    pub missing: Vec<String>,
+    // Pantry items used by the recipe that expire soon
+    pub expiring: Vec<String>,
+    // The earliest expiry date of `expiring`
+    pub expiring_on: Option<String>,
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use super::*;
+    use crate::app::components::ingredient_units::{normalize_ingredient_name, Quantity};
+    use crate::app::components::meal_planner::format_plan_date;
+    use crate::app::components::recipe::{Recipe, RecipeIngredient, RecipeIngredientGroup};
+    use sqlx::SqliteConnection;
+
+    #[derive(sqlx::FromRow)]
+    pub struct DbRowPantryItem {
+        pub id: u32,
+        pub item_name: String,
+        pub quantity: Option<String>,
+        pub expires_on: Option<String>,
+    }
+
+    /// Items expiring first, then by name
+    pub async fn pantry_items(conn: &mut SqliteConnection) -> Result<Vec<PantryItem>, ServerFnError> {
+        Ok(sqlx::query_as::<_, DbRowPantryItem>(
+            "SELECT id, item_name, quantity, expires_on FROM pantry_items
+            ORDER BY expires_on IS NULL, expires_on, item_name COLLATE NOCASE",
+        )
+        .fetch_all(conn)
+        .await?
+        .into_iter()
+        .map(|row| PantryItem {
+            id: row.id,
+            name: row.item_name,
+            quantity: row.quantity,
+            expires_on: row.expires_on,
+        })
+        .collect())
+    }
+
+    // Empty fields are stored as NULL, dates only when they are dates
+    pub fn checked_item(item: PantryItem) -> Result<PantryItem, ServerFnError> {
+        let name = item.name.trim().to_string();
+        if name.is_empty() {
+            return Err(ServerFnError::ServerError("The item has no name.".to_string()));
+        }
+        let quantity = item.quantity.map(|quantity| quantity.trim().to_string()).filter(|quantity| !quantity.is_empty());
+        let expires_on = match item.expires_on.filter(|date| !date.trim().is_empty()) {
+            Some(date) => match parse_plan_date(date.trim()) {
+                Some(date) => Some(format_plan_date(date)),
+                None => return Err(ServerFnError::ServerError(format!("Invalid date: {}", date))),
+            },
+            None => None,
+        };
+        Ok(PantryItem { id: item.id, name, quantity, expires_on })
+    }
+
+    /// The pantry item an ingredient is made with, if there is enough of it.
+    /// Matched on whole normalized words, all the words of the item must be found:
+    /// "egg" is in "2 eggs, beaten" but not in "eggplant"
+    fn covering_item<'a>(pantry: &'a [PantryItem], ingredient: &RecipeIngredient) -> Option<&'a PantryItem> {
+        let ingredient_name = normalize_ingredient_name(&ingredient.content);
+        let ingredient_words: Vec<&str> = ingredient_name.split_whitespace().collect();
+        let needed = Quantity::parse(&ingredient.qty_unit);
+        pantry.iter().find(|item| {
+            let item_name = normalize_ingredient_name(&item.name);
+            let is_same = !item_name.is_empty()
+                && item_name
+                    .split_whitespace()
+                    .all(|item_word| ingredient_words.contains(&item_word));
+            // Quantities that cannot be compared are enough
+            let is_enough = match (item.quantity.as_deref().and_then(Quantity::parse), &needed) {
+                (Some(on_hand), Some(needed)) => on_hand.covers(needed).unwrap_or(true),
+                _ => true,
+            };
+            is_same && is_enough
+        })
+    }
+
+    /// Recipes using at least one pantry item, those using soon expiring items first,
+    /// then the most covered ones
+    pub fn rank_recipes(recipes: Vec<Recipe>, pantry: &[PantryItem]) -> Vec<CookableRecipe> {
+        let mut cookable: Vec<CookableRecipe> = recipes
+            .into_iter()
+            .filter_map(|recipe| {
+                let ingredients: Vec<RecipeIngredient> = RecipeIngredientGroup::flatten(recipe.ingredients)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|ingredient| !ingredient.content.trim().is_empty())
+                    .collect();
+
+                let mut missing: Vec<String> = vec![];
+                let mut used: Vec<&PantryItem> = vec![];
+                for ingredient in &ingredients {
+                    match covering_item(pantry, ingredient) {
+                        Some(item) => {
+                            if !used.iter().any(|used_item| used_item.id == item.id) {
+                                used.push(item);
+                            }
+                        }
+                        None => missing.push(format!("{} {}", ingredient.qty_unit.trim(), ingredient.content.trim()).trim().to_string()),
+                    }
+                }
+                if used.is_empty() {
+                    return None;
+                }
+
+                let expiring_items: Vec<&PantryItem> = used.into_iter().filter(|item| item.is_expiring()).collect();
+                Some(CookableRecipe {
+                    recipe_id: recipe.id?,
+                    recipe_name: recipe.name,
+                    covered: ingredients.len() - missing.len(),
+                    total: ingredients.len(),
+                    missing,
+                    expiring: expiring_items.iter().map(|item| item.name.clone()).collect(),
+                    expiring_on: expiring_items.iter().filter_map(|item| item.expires_on.clone()).min(),
+                })
+            })
+            .collect();
+
+        cookable.sort_by(|a, b| {
+            // Dates sort as text, recipes without expiring items last
+            let by_expiry = match (&a.expiring_on, &b.expiring_on) {
+                (Some(a), Some(b)) => a.cmp(b),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            };
+            // covered / total, compared without dividing
+            let by_coverage = (b.covered * a.total).cmp(&(a.covered * b.total));
+            by_expiry
+                .then(by_coverage)
+                .then(a.missing.len().cmp(&b.missing.len()))
+                .then(a.recipe_name.to_lowercase().cmp(&b.recipe_name.to_lowercase()))
+        });
+        cookable
+    }
+}
+
+#[server]
+pub async fn get_pantry_items() -> Result<Vec<PantryItem>, ServerFnError> {
+    use self::ssr::*;
+    use crate::app::components::recipe_server_functions::ssr::db;
+
+    let mut conn = db().await?;
+    pantry_items(&mut conn).await
+}
+
+#[server]
+pub async fn pantry_function(action: PantryActionDescriptor) -> Result<(), ServerFnError> {
+    use self::ssr::*;
+    use crate::app::components::auth::auth_server_functions::check_login;
+    use crate::app::components::recipe_server_functions::ssr::db;
+
+    if !check_login().await? {
+        return Err(ServerFnError::ServerError("Not logged in.".to_string()));
+    }
+
+    let mut conn = db().await?;
+
+    match action {
+        PantryActionDescriptor::Add(item) => {
+            let item = checked_item(item)?;
+            sqlx::query("INSERT INTO pantry_items (item_name, quantity, expires_on) VALUES ($1, $2, $3);")
+                .bind(&item.name)
+                .bind(&item.quantity)
+                .bind(&item.expires_on)
+                .execute(&mut conn)
+                .await?;
+            log!("{:?} was added to the pantry", item.name);
+        }
+        PantryActionDescriptor::Update(item) => {
+            let item = checked_item(item)?;
+            sqlx::query("UPDATE pantry_items SET item_name = $1, quantity = $2, expires_on = $3 WHERE id = $4;")
+                .bind(&item.name)
+                .bind(&item.quantity)
+                .bind(&item.expires_on)
+                .bind(item.id)
+                .execute(&mut conn)
+                .await?;
+        }
+        PantryActionDescriptor::Remove(id) => {
+            sqlx::query("DELETE FROM pantry_items WHERE id = $1;")
+                .bind(id)
+                .execute(&mut conn)
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Recipes ranked by what the pantry has of their ingredients
+#[server]
+pub async fn get_cookable_recipes() -> Result<Vec<CookableRecipe>, ServerFnError> {
+    use self::ssr::*;
+    use crate::app::components::recipe_server_functions::ssr::{all_recipes, db};
+
+    let mut conn = db().await?;
+    let pantry = pantry_items(&mut conn).await?;
+    if pantry.is_empty() {
+        return Ok(vec![]);
+    }
+    let recipes = all_recipes(&mut conn).await?;
+    Ok(rank_recipes(recipes, &pantry))
+}
+
+/// The items on hand, editable in place when logged in
+#[component]
+pub fn PantryList() -> impl IntoView {
+    // Is logged in
+    let check_login_resource = use_context::<LoginCheckResource>()
+        .expect("Expected to find LoginCheckAction in context")
+        .0;
+    let is_logged_in = move || check_login_resource.get() == Some(true);
+
+    let pantry_action = use_context::<PantryServerAction>()
+        .expect("To find PantryServerAction in context.")
+        .0;
+
+    let items = Resource::new(
+        move || pantry_action.version().get(),
+        |_| async move {
+            get_pantry_items().await.map_err(|e| {
+                error!("ERROR: Could not get the pantry: {:?}", e.to_string());
+                e.to_string()
+            })
+        },
+    );
+
+    // The new item form
+    let new_item = RwSignal::new(PantryItem::default());
+    let on_add = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        pantry_action.dispatch(PantryActionDescriptor::Add(new_item.get_untracked()));
+        new_item.set(PantryItem::default());
+    };
+
+    view! {
+        <div class="pantry">
+            <Show when=is_logged_in>
+                <form class="pantry-add" on:submit=on_add>
+                    <input
+                        class="pantry-input name"
+                        type="text"
+                        placeholder="Item"
+                        required=true
+                        prop:value=move || new_item.read().name.clone()
+                        on:input=move |ev| new_item.update(|item| item.name = event_target_value(&ev))
+                    />
+                    <input
+                        class="pantry-input quantity"
+                        type="text"
+                        placeholder="Quantity"
+                        prop:value=move || new_item.read().quantity.clone().unwrap_or_default()
+                        on:input=move |ev| new_item.update(|item| item.quantity = Some(event_target_value(&ev)))
+                    />
+                    <input
+                        class="pantry-input expiry"
+                        type="date"
+                        title="Expiry date"
+                        prop:value=move || new_item.read().expires_on.clone().unwrap_or_default()
+                        on:input=move |ev| new_item.update(|item| item.expires_on = Some(event_target_value(&ev)))
+                    />
+                    <button class="pantry-button" type="submit">"Add"</button>
+                </form>
+            </Show>
+
+            <Transition
+                fallback=move || view! { <p class="pantry-info">"Loading the pantry..."</p> }
+            >
+                {move || match items.get() {
+                    Some(Ok(list)) if list.is_empty() => view! {
+                        <p class="pantry-info">"The pantry is empty."</p>
+                    }.into_any(),
+                    Some(Ok(list)) => view! {
+                        <ul class="pantry-items">
+                            {list.into_iter().map(|item| {
+                                let is_expiring = item.is_expiring();
+                                // Each field is saved when it is left
+                                let item = StoredValue::new(item);
+                                let update = move |change: &dyn Fn(&mut PantryItem)| {
+                                    let mut changed = item.get_value();
+                                    change(&mut changed);
+                                    if changed != item.get_value() {
+                                        pantry_action.dispatch(PantryActionDescriptor::Update(changed));
+                                    }
+                                };
+                                view! {
+                                    <li
+                                        class="pantry-item"
+                                        class:expiring=is_expiring
+                                    >
+                                        <input
+                                            class="pantry-input name"
+                                            type="text"
+                                            disabled=move || !is_logged_in()
+                                            value=item.with_value(|item| item.name.clone())
+                                            on:change=move |ev| update(&|item| item.name = event_target_value(&ev))
+                                        />
+                                        <input
+                                            class="pantry-input quantity"
+                                            type="text"
+                                            placeholder="Quantity"
+                                            disabled=move || !is_logged_in()
+                                            value=item.with_value(|item| item.quantity.clone().unwrap_or_default())
+                                            on:change=move |ev| update(&|item| item.quantity = Some(event_target_value(&ev)))
+                                        />
+                                        <input
+                                            class="pantry-input expiry"
+                                            type="date"
+                                            title="Expiry date"
+                                            disabled=move || !is_logged_in()
+                                            value=item.with_value(|item| item.expires_on.clone().unwrap_or_default())
+                                            on:change=move |ev| update(&|item| item.expires_on = Some(event_target_value(&ev)))
+                                        />
+                                        <Show when=is_logged_in>
+                                            <button
+                                                class="pantry-button remove"
+                                                on:click=move |_| pantry_action.dispatch(
+                                                    PantryActionDescriptor::Remove(item.with_value(|item| item.id))
+                                                )
+                                            >"×"</button>
+                                        </Show>
+                                    </li>
+                                }
+                            }).collect_view()}
+                        </ul>
+                    }.into_any(),
+                    Some(Err(e)) => view! { <p class="pantry-info">{e}</p> }.into_any(),
+                    None => ().into_any(),
+                }}
+            </Transition>
+        </div>
+    }
+}
+
+/// What can be cooked with the pantry, soon expiring items first
+#[component]
+pub fn CookableRecipes() -> impl IntoView {
+    let pantry_action = use_context::<PantryServerAction>()
+        .expect("To find PantryServerAction in context.")
+        .0;
+    let recipe_action = use_context::<RecipeServerAction>()
+        .expect("To find RecipeServerAction in context.")
+        .0;
+
+    let cookable = Resource::new(
+        move || (pantry_action.version().get(), recipe_action.version().get()),
+        |_| async move {
+            get_cookable_recipes().await.map_err(|e| {
+                error!("ERROR: Could not rank the recipes: {:?}", e.to_string());
+                e.to_string()
+            })
+        },
+    );
+
+    view! {
+        <div class="cookable-recipes">
+            <h2 class="cookable-recipes-title">"What can I cook now?"</h2>
+            <Transition
+                fallback=move || view! { <p class="pantry-info">"Looking through the recipes..."</p> }
+            >
+                {move || match cookable.get() {
+                    Some(Ok(list)) if list.is_empty() => view! {
+                        <p class="pantry-info">"No recipe uses the pantry items."</p>
+                    }.into_any(),
+                    Some(Ok(list)) => view! {
+                        <ul class="cookable-recipes-list">
+                            {list.into_iter().map(|recipe| {
+                                let expiring = (!recipe.expiring.is_empty())
+                                    .then(|| format!("Uses soon: {}", recipe.expiring.join(", ")));
+                                let missing = (!recipe.missing.is_empty())
+                                    .then(|| format!("Missing: {}", recipe.missing.join(", ")));
+                                view! {
+                                    <li class="cookable-recipe">
+                                        <a class="cookable-recipe-name" href=format!("/recipe/{}/display", recipe.recipe_id)>
+                                            { recipe.recipe_name }
+                                        </a>
+                                        <span class="cookable-recipe-coverage">
+                                            { format!("{}/{} ingredients", recipe.covered, recipe.total) }
+                                        </span>
+                                        {expiring.map(|text| view! { <p class="cookable-recipe-expiring">{ text }</p> })}
+                                        {missing.map(|text| view! { <p class="cookable-recipe-missing">{ text }</p> })}
+                                    </li>
+                                }
+                            }).collect_view()}
+                        </ul>
+                    }.into_any(),
+                    Some(Err(e)) => view! { <p class="pantry-info">{e}</p> }.into_any(),
+                    None => ().into_any(),
+                }}
+            </Transition>
+        </div>
+    }
+}
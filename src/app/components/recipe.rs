@@ -14,9 +14,10 @@ pub struct Recipe {
     pub id: Option<u16>,
     pub name: String,
     pub tags: Option<Vec<RecipeTag>>,
-    pub ingredients: Option<Vec<RecipeIngredient>>,
-    pub instructions: RecipeInstruction,
+    pub ingredients: Option<Vec<RecipeIngredientGroup>>,
+    pub instructions: Option<Vec<RecipeInstruction>>,
     pub notes: Option<Vec<RecipeNote>>,
+    pub source: Option<RecipeSource>,
 }
 
 /// Lightweight recipe format
@@ -26,6 +27,9 @@ pub struct RecipeLight {
     pub name: String,
     pub tags: Option<Vec<RecipeTag>>,
     pub ingredients: Option<Vec<RecipeIngredient>>,
+    // The main image of the recipe, if any
+    pub image_id: Option<u32>,
+    pub source: Option<RecipeSource>,
 }
 
 impl RecipeLight {
@@ -43,10 +47,18 @@ impl RecipeLight {
         }
     }
 
-    pub fn is_in_search(&self, search_words: &[String]) -> bool {
-        use regex::Regex;
-        let re = Regex::new(r"\b\w+\b").unwrap();
+    pub fn has_tag_groups(&self, tag_groups: &[Vec<String>]) -> bool {
+        // The recipe MUST contain one tag of each group, a category and the tags under it
+        if let Some(tags) = &self.tags {
+            tag_groups.iter().all(|group| {
+                group.iter().any(|tag| tags.contains(&RecipeTag{name: tag.to_string()}))
+            })
+        } else {
+            tag_groups.is_empty()
+        }
+    }
 
+    pub fn is_in_search(&self, search_words: &[String]) -> bool {
         // gather all recipe text
         let mut recipe_text: String = "".to_string();
         // add name
@@ -65,33 +77,130 @@ impl RecipeLight {
                 recipe_text += i.content.as_str();
             }
         }
-
-        // lowercase all recipe text
-        recipe_text = recipe_text.to_lowercase();
+        // add source
+        if let Some(source) = &self.source {
+            recipe_text += " ";
+            recipe_text += source.search_text().as_str();
+        }
 
         // separate text into words
-        let recipe_words: Vec<&str> = re.find_iter(&recipe_text).map(|mat| mat.as_str()).collect();
+        let recipe_words = search_words_of(&recipe_text);
 
         // Find matching words
         search_words.iter().any(|item| {
-            recipe_words.iter().any(|word| {
-                // Check if recipe_words contains any of search_words
-                // (find exactly matching words)
-                
-                word.contains(item)
-                // Check if search_words are sub parts of recipe_words
-                // But only if the word is at least 3 characters long
-                // (find matchings word parts. ex: find "rec" in "recipe" but not "re" in "recipe")
-                || { word.len() >= 3 && item.contains(word) }
-            })
+            recipe_words.iter().any(|word| search_word_matches(word, item))
         })
     }
 }
 
-/// The Recipe format, without the ID, that will be serialize into JSON
+/// Lowercased words of a text, as the search compares them
+pub fn search_words_of(text: &str) -> Vec<String> {
+    use regex::Regex;
+    let re = Regex::new(r"\b\w+\b").unwrap();
+
+    // lowercase all text
+    let text = text.to_lowercase();
+    re.find_iter(&text).map(|mat| mat.as_str().to_string()).collect()
+}
+
+/// If a searched word matches a word of the recipe text
+pub fn search_word_matches(word: &str, item: &str) -> bool {
+    // Check if recipe_words contains any of search_words
+    // (find exactly matching words)
+    word.contains(item)
+    // Check if search_words are sub parts of recipe_words
+    // But only if the word is at least 3 characters long
+    // (find matchings word parts. ex: find "rec" in "recipe" but not "re" in "recipe")
+    || { word.len() >= 3 && item.contains(word) }
+}
+
+impl Recipe {
+    /// Names of all the recipes referenced with `[[Recipe Name]]`
+    /// in the ingredients, instructions and notes
+    pub fn linked_recipe_names(&self) -> Vec<String> {
+        let ingredients = self.ingredients.iter().flatten()
+            .flat_map(|group| group.ingredients.iter())
+            .map(|ingredient| ingredient.content.as_str());
+        let instructions = self.instructions.iter().flatten()
+            .flat_map(|step| [step.title.as_str(), step.content.as_str()]);
+        let notes = self.notes.iter().flatten()
+            .map(|note| note.content.as_str());
+
+        let mut names: Vec<String> = vec![];
+        for text in ingredients.chain(instructions).chain(notes) {
+            for name in RecipeLinks::find_names(text) {
+                if !names.iter().any(|n| RecipeLinks::same_name(n, &name)) {
+                    names.push(name);
+                }
+            }
+        }
+        names
+    }
+}
+
+/// Links between a recipe and the recipes it references with `[[Recipe Name]]`
 #[derive(Default, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
-pub struct JsonRecipeCollection(pub Vec<JsonRecipe>);
+pub struct RecipeLinks {
+    // Referenced recipes that were found: (name as written, id)
+    pub linked: Vec<(String, u16)>,
+    // Recipes referencing this one: (id, name)
+    pub used_in: Vec<(u16, String)>,
+}
+
+impl RecipeLinks {
+    /// Find every `[[Recipe Name]]` in a text
+    pub fn find_names(text: &str) -> Vec<String> {
+        use regex::Regex;
+        let re = Regex::new(r"\[\[([^\[\]]+)\]\]").unwrap();
+        re.captures_iter(text)
+            .map(|caps| caps[1].trim().to_string())
+            .filter(|name| !name.is_empty())
+            .collect()
+    }
+
+    /// Split a text into plain parts and `[[Recipe Name]]` parts.
+    /// Returns (text, is_link)
+    pub fn split_text(text: &str) -> Vec<(String, bool)> {
+        use regex::Regex;
+        let re = Regex::new(r"\[\[([^\[\]]+)\]\]").unwrap();
+
+        let mut parts: Vec<(String, bool)> = vec![];
+        let mut last_end = 0;
+        for caps in re.captures_iter(text) {
+            let whole = caps.get(0).unwrap();
+            if whole.start() > last_end {
+                parts.push((text[last_end..whole.start()].to_string(), false));
+            }
+            parts.push((caps[1].trim().to_string(), true));
+            last_end = whole.end();
+        }
+        if last_end < text.len() {
+            parts.push((text[last_end..].to_string(), false));
+        }
+        parts
+    }
+
+    /// The text with `[[Recipe Name]]` turned into "Recipe Name"
+    pub fn plain_text(text: &str) -> String {
+        Self::split_text(text)
+            .into_iter()
+            .map(|(part, _)| part)
+            .collect()
+    }
+
+    /// Recipe names are matched case insensitively
+    pub fn same_name(a: &str, b: &str) -> bool {
+        a.trim().to_lowercase() == b.trim().to_lowercase()
+    }
+
+    /// The ID of a referenced recipe, if it exists
+    pub fn get_id(&self, name: &str) -> Option<u16> {
+        self.linked
+            .iter()
+            .find(|(linked_name, _)| Self::same_name(linked_name, name))
+            .map(|(_, id)| *id)
+    }
+}
 
 /// The Recipe format, without the ID, that will be serialize into JSON
 #[derive(Default, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -102,6 +211,9 @@ pub struct JsonRecipe {
     pub ingredients: JsonRecipeIngredients,
     pub instructions: JsonRecipeInstructions,
     pub notes: JsonRecipeNotes,
+    // Older saves have no source
+    #[serde(default, skip_serializing_if = "JsonRecipeSource::is_empty")]
+    pub source: JsonRecipeSource,
 }
 impl JsonRecipe {
     pub fn to_recipe(self, id: u16) -> Recipe {
@@ -112,6 +224,14 @@ impl JsonRecipe {
             ingredients: self.ingredients.to_recipe_ingredients(),
             instructions: self.instructions.to_recipe_instructions(),
             notes: self.notes.to_recipe_notes(),
+            source: self.source.to_recipe_source(),
+        }
+    }
+    // Recipe not yet in the database
+    pub fn to_new_recipe(self) -> Recipe {
+        Recipe {
+            id: None,
+            ..self.to_recipe(0)
         }
     }
     pub fn from_recipe(recipe: Recipe) -> JsonRecipe {
@@ -121,6 +241,7 @@ impl JsonRecipe {
             ingredients: JsonRecipeIngredients::from_recipe_ingredients(recipe.ingredients),
             instructions: JsonRecipeInstructions::from_recipe_instructions(recipe.instructions),
             notes: JsonRecipeNotes::from_recipe_notes(recipe.notes),
+            source: JsonRecipeSource::from_recipe_source(recipe.source),
         }
     }
 }
@@ -137,33 +258,122 @@ impl JsonRecipeTags {
     }
 }
 
+/// Ingredients are stored as a list of named groups.
+/// Older saves stored a flat list of (quantity, ingredient) tuples,
+/// which is still readable and becomes a single unnamed group.
 #[derive(Default, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
-pub struct JsonRecipeIngredients(Option<Vec<(String, String)>>);
+#[serde(from = "JsonRecipeIngredientsFormat")]
+pub struct JsonRecipeIngredients(Option<Vec<JsonRecipeIngredientGroup>>);
 impl JsonRecipeIngredients {
-    pub fn to_recipe_ingredients(self) -> Option<Vec<RecipeIngredient>> {
-        self.0.map(|ingrs| ingrs
+    pub fn to_recipe_ingredients(self) -> Option<Vec<RecipeIngredientGroup>> {
+        self.0.map(|groups| groups
                     .into_iter()
-                    .map(|(qty_unit, content)| RecipeIngredient { qty_unit, content })
+                    .map(|group| RecipeIngredientGroup {
+                        name: group.name,
+                        ingredients: group.ingredients
+                            .into_iter()
+                            .map(|(qty_unit, content)| RecipeIngredient { qty_unit, content })
+                            .collect(),
+                    })
                     .collect())
     }
-    pub fn from_recipe_ingredients(recipe_ingrs: Option<Vec<RecipeIngredient>>) -> Self {
+    pub fn from_recipe_ingredients(recipe_ingrs: Option<Vec<RecipeIngredientGroup>>) -> Self {
         JsonRecipeIngredients(recipe_ingrs.map(|recipe_ingrs| recipe_ingrs
                     .into_iter()
-                    .map(|i| (i.qty_unit, i.content))
+                    .map(|group| JsonRecipeIngredientGroup {
+                        name: group.name,
+                        ingredients: group.ingredients
+                            .into_iter()
+                            .map(|i| (i.qty_unit, i.content))
+                            .collect(),
+                    })
                     .collect()))
     }
 }
 
+#[derive(Default, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonRecipeIngredientGroup {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    pub ingredients: Vec<(String, String)>,
+}
+
+// Every format the ingredients were ever saved with
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonRecipeIngredientsFormat {
+    List(Option<Vec<(String, String)>>),
+    Groups(Vec<JsonRecipeIngredientGroup>),
+}
+impl From<JsonRecipeIngredientsFormat> for JsonRecipeIngredients {
+    fn from(format: JsonRecipeIngredientsFormat) -> Self {
+        match format {
+            JsonRecipeIngredientsFormat::List(list) => JsonRecipeIngredients(list.map(|ingredients| {
+                vec![JsonRecipeIngredientGroup { name: "".to_string(), ingredients }]
+            })),
+            JsonRecipeIngredientsFormat::Groups(groups) => JsonRecipeIngredients(Some(groups)),
+        }
+    }
+}
+
+/// Instructions are stored as an ordered list of steps.
+/// Older saves stored them as a single text blob, which is still readable
+/// and gets split into steps when loaded.
 #[derive(Default, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
-pub struct JsonRecipeInstructions(String);
+#[serde(from = "JsonRecipeInstructionsFormat")]
+pub struct JsonRecipeInstructions(Option<Vec<JsonRecipeStep>>);
 impl JsonRecipeInstructions {
-    pub fn to_recipe_instructions(self) -> RecipeInstruction {
-        RecipeInstruction { content: self.0 }
+    pub fn to_recipe_instructions(self) -> Option<Vec<RecipeInstruction>> {
+        self.0.map(|steps| steps
+                    .into_iter()
+                    .map(|step| RecipeInstruction {
+                        title: step.title,
+                        content: step.content,
+                        duration: step.duration,
+                    })
+                    .collect())
+    }
+    pub fn from_recipe_instructions(recipe_instrs: Option<Vec<RecipeInstruction>>) -> Self {
+        JsonRecipeInstructions(recipe_instrs.map(|recipe_instrs| recipe_instrs
+                    .into_iter()
+                    .map(|i| JsonRecipeStep {
+                        title: i.title,
+                        content: i.content,
+                        duration: i.duration,
+                    })
+                    .collect()))
     }
-    pub fn from_recipe_instructions(recipe_instrs: RecipeInstruction) -> Self {
-        JsonRecipeInstructions(recipe_instrs.content)
+}
+
+#[derive(Default, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonRecipeStep {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub title: String,
+    pub content: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub duration: String,
+}
+
+// Every format the instructions were ever saved with
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonRecipeInstructionsFormat {
+    Steps(Option<Vec<JsonRecipeStep>>),
+    Blob(String),
+}
+impl From<JsonRecipeInstructionsFormat> for JsonRecipeInstructions {
+    fn from(format: JsonRecipeInstructionsFormat) -> Self {
+        match format {
+            JsonRecipeInstructionsFormat::Steps(steps) => JsonRecipeInstructions(steps),
+            JsonRecipeInstructionsFormat::Blob(blob) => {
+                let steps = RecipeInstruction::from_text_blob(&blob);
+                JsonRecipeInstructions::from_recipe_instructions(
+                    if steps.is_empty() { None } else { Some(steps) }
+                )
+            }
+        }
     }
 }
 
@@ -182,6 +392,41 @@ impl JsonRecipeNotes {
     }
 }
 
+#[derive(Default, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct JsonRecipeSource {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub url: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub author: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub book: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub page: String,
+}
+impl JsonRecipeSource {
+    pub fn is_empty(&self) -> bool {
+        self.url.is_empty() && self.author.is_empty() && self.book.is_empty() && self.page.is_empty()
+    }
+    pub fn to_recipe_source(self) -> Option<RecipeSource> {
+        let source = RecipeSource {
+            url: self.url,
+            author: self.author,
+            book: self.book,
+            page: self.page,
+        };
+        if source.is_empty() { None } else { Some(source) }
+    }
+    pub fn from_recipe_source(recipe_source: Option<RecipeSource>) -> Self {
+        recipe_source.map(|s| JsonRecipeSource {
+            url: s.url,
+            author: s.author,
+            book: s.book,
+            page: s.page,
+        }).unwrap_or_default()
+    }
+}
+
 // Recipe format when it is stored in the DB
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
@@ -192,6 +437,19 @@ pub struct DbRowRecipe {
     pub recipe_ingredients: String,
     pub recipe_instructions: String,
     pub recipe_notes: String,
+    pub recipe_source: String,
+}
+impl DbRowRecipe {
+    pub fn to_json_recipe(self) -> Result<JsonRecipe, serde_json::Error> {
+        Ok(JsonRecipe {
+            name: self.recipe_name,
+            tags: serde_json::from_str::<JsonRecipeTags>(&self.recipe_tags)?,
+            ingredients: serde_json::from_str::<JsonRecipeIngredients>(&self.recipe_ingredients)?,
+            instructions: serde_json::from_str::<JsonRecipeInstructions>(&self.recipe_instructions)?,
+            notes: serde_json::from_str::<JsonRecipeNotes>(&self.recipe_notes)?,
+            source: serde_json::from_str::<JsonRecipeSource>(&self.recipe_source)?,
+        })
+    }
 }
 
 // All infos needed for AllRecipe page
@@ -202,6 +460,8 @@ pub struct DbRowRecipeLight {
     pub recipe_name: String,
     pub recipe_tags: String,
     pub recipe_ingredients: String,
+    pub recipe_source: String,
+    pub image_id: Option<u32>,
 }
 
 // Only ID
@@ -254,6 +514,56 @@ pub trait RecipeEntry: std::fmt::Debug + Clone + Default + std::marker::Sync + s
     fn is_empty(&self) -> bool;
 }
 
+/// SOURCE -----
+/// Where a recipe was found or adapted from
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RecipeSource {
+    pub url: String,
+    pub author: String,
+    pub book: String,
+    pub page: String,
+}
+
+impl RecipeSource {
+    pub fn is_empty(&self) -> bool {
+        self.url.trim().is_empty()
+            && self.author.trim().is_empty()
+            && self.book.trim().is_empty()
+            && self.page.trim().is_empty()
+    }
+
+    /// Trimmed copy, or None if every field is empty
+    pub fn cleaned(self) -> Option<Self> {
+        let source = RecipeSource {
+            url: self.url.trim().to_string(),
+            author: self.author.trim().to_string(),
+            book: self.book.trim().to_string(),
+            page: self.page.trim().to_string(),
+        };
+        if source.is_empty() { None } else { Some(source) }
+    }
+
+    /// The URL if it can be a link, "javascript:" and other schemes are only shown as text
+    pub fn safe_url(&self) -> Option<&str> {
+        let url = self.url.trim();
+        let scheme = url.split_once("://").map(|(scheme, _)| scheme.to_lowercase());
+        matches!(scheme.as_deref(), Some("http") | Some("https")).then_some(url)
+    }
+
+    /// The website name, without the protocol and the "www."
+    pub fn url_host(&self) -> String {
+        let url = self.url.trim();
+        let url = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+        let host = url.split(['/', '?', '#']).next().unwrap_or(url);
+        host.trim_start_matches("www.").to_string()
+    }
+
+    /// Text used by the search
+    pub fn search_text(&self) -> String {
+        [self.url_host(), self.author.clone(), self.book.clone()].join(" ")
+    }
+}
+
 /// INGREDIENTS and implementions -----
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct RecipeIngredient {
@@ -356,16 +666,81 @@ impl RecipeEntry for RecipeIngredient {
     }
 }
 
+/// INGREDIENT GROUPS -----
+/// A named section of the ingredient list ("For the dough", "For the filling"...).
+/// The name can be empty for recipes that don't need sections.
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RecipeIngredientGroup {
+    pub name: String,
+    pub ingredients: Vec<RecipeIngredient>,
+}
+
+impl RecipeIngredientGroup {
+    /// Flatten the groups into a single ingredient list
+    pub fn flatten(groups: Option<Vec<RecipeIngredientGroup>>) -> Option<Vec<RecipeIngredient>> {
+        groups.map(|groups| groups
+                    .into_iter()
+                    .flat_map(|group| group.ingredients)
+                    .collect())
+    }
+}
+
 /// INSTRUCTIONS and implementions -----
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct RecipeInstruction {
+    pub title: String,
     pub content: String,
+    pub duration: String,
+}
+
+impl RecipeInstruction {
+    /// Split a single instructions text into steps.
+    /// Paragraphs (separated by blank lines) become steps, and if there is only one paragraph,
+    /// lines starting with a step number ("1.", "2)", "Step 3:"...) start a new step.
+    pub fn from_text_blob(blob: &str) -> Vec<RecipeInstruction> {
+        use regex::Regex;
+        let paragraph_re = Regex::new(r"\n[ \t]*\n").unwrap();
+        let number_re = Regex::new(r"(?i)^\s*(?:step\s*)?\d+\s*[.):\-](?:\s+|$)").unwrap();
+
+        let blob = blob.replace("\r\n", "\n");
+
+        let mut steps: Vec<String> = paragraph_re
+            .split(&blob)
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .collect();
+
+        // A single paragraph can still be a numbered list
+        if steps.len() == 1 {
+            let mut numbered_steps: Vec<String> = vec![];
+            for line in steps[0].lines() {
+                match numbered_steps.last_mut() {
+                    Some(step) if !number_re.is_match(line) => {
+                        step.push('\n');
+                        step.push_str(line);
+                    }
+                    _ => numbered_steps.push(line.to_string()),
+                }
+            }
+            steps = numbered_steps;
+        }
+
+        steps
+            .into_iter()
+            .map(|step| RecipeInstruction {
+                content: number_re.replace(&step, "").trim().to_string(),
+                ..Default::default()
+            })
+            .filter(|step| !step.content.is_empty())
+            .collect()
+    }
 }
 
 impl IntoRender for RecipeInstruction {
     type Output = AnyView;
     fn into_render(self) -> AnyView {
         view! {
+            <p>{self.title}</p>
             <p>{self.content}</p>
         }
         .into_any()
@@ -373,7 +748,7 @@ impl IntoRender for RecipeInstruction {
 }
 
 impl RecipeEntry for RecipeInstruction {
-    type S = RwSignal<Self>;
+    type S = ArcRwSignal<Self>;
 
     fn get_entry_type() -> RecipeEntryType {
         RecipeEntryType::Instructions
@@ -384,29 +759,82 @@ impl RecipeEntry for RecipeInstruction {
     }
 
     fn is_empty(&self) -> bool {
-        self.content.trim().is_empty()
+        self.title.trim().is_empty() && self.content.trim().is_empty() && self.duration.trim().is_empty()
     }
 
     fn into_editable_view(
         rw_entry: Self::S,
-        _menu_info: Option<RecipeEntryMenuInfo<Self>>,
+        menu_info: Option<RecipeEntryMenuInfo<Self>>,
     ) -> AnyView {
         view! {
-            <RecipeEntryInput
-                class=              "instructions".to_owned()
-                placeholder=        "".to_owned()
-                rw_entry=           rw_entry
+            <RecipeEntryMenu
+                entry_menu_info=     menu_info.expect("Expected to find menu_signal for instruction entry.")
             />
+
+            <div class="editable-instructions-wrapper">
+                <div class="editable-instructions-header">
+                    <RecipeEntryInput
+                        class=              "instructions instructions-title".to_owned()
+                        placeholder=        "Step title".to_owned()
+                        rw_entry=           rw_entry.clone()
+                        field_id=           {0}
+                        is_input=           true
+                    />
+
+                    <RecipeEntryInput
+                        class=              "instructions instructions-duration".to_owned()
+                        placeholder=        "Duration".to_owned()
+                        rw_entry=           rw_entry.clone()
+                        field_id=           {2}
+                        is_input=           true
+                    />
+                </div>
+
+                <RecipeEntryInput
+                    class=              "instructions instructions-content".to_owned()
+                    placeholder=        "".to_owned()
+                    rw_entry=           rw_entry
+                    field_id=           {1}
+                />
+            </div>
         }
         .into_any()
     }
 
-    fn update_field_from_string_input(&mut self, _field_id: Option<usize>, input: String) {
-        self.content = input;
+    fn update_field_from_string_input(&mut self, field_id: Option<usize>, input: String) {
+        match field_id {
+            Some(0) => self.title = input,
+
+            Some(1) => self.content = input,
+
+            Some(2) => self.duration = input,
+
+            None => {
+                error!("ERROR: No ID provided.")
+            }
+
+            _ => {
+                error!("ERROR: Invalid ID.")
+            }
+        }
     }
 
-    fn get_string_from_field(&self, _field_id: Option<usize>) -> String {
-        self.content.clone()
+    fn get_string_from_field(&self, field_id: Option<usize>) -> String {
+        match field_id {
+            Some(0) => self.title.clone(),
+
+            Some(1) => self.content.clone(),
+
+            Some(2) => self.duration.clone(),
+
+            None => {
+                panic!("ERROR: No ID provided.")
+            }
+
+            _ => {
+                panic!("ERROR: Invalid ID.")
+            }
+        }
     }
 }
 
@@ -481,6 +909,8 @@ pub enum RecipeContentType {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum RecipeActionDescriptor {
     Add(Recipe),
+    // Imported recipes, added in one transaction
+    AddMany(Vec<Recipe>),
     Save(Recipe),
     // With recipe ID
     Delete(u16),
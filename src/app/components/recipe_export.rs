@@ -0,0 +1,425 @@
+use crate::app::components::recipe::{Recipe, RecipeLinks, RecipeSource};
+
+/// Formats a single recipe can be downloaded in
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RecipeExportFormat {
+    // schema.org Recipe, see `schema_org::recipe_to_json_ld`
+    JsonLd,
+    // Markdown with a YAML front matter, see `recipe_to_markdown`
+    Markdown,
+    // See `recipe_to_text`
+    Text,
+}
+impl RecipeExportFormat {
+    pub const ALL: [RecipeExportFormat; 3] = [
+        RecipeExportFormat::Markdown,
+        RecipeExportFormat::Text,
+        RecipeExportFormat::JsonLd,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RecipeExportFormat::JsonLd => "json-ld",
+            RecipeExportFormat::Markdown => "markdown",
+            RecipeExportFormat::Text => "text",
+        }
+    }
+    pub fn label(&self) -> &'static str {
+        match self {
+            RecipeExportFormat::JsonLd => "JSON-LD",
+            RecipeExportFormat::Markdown => "Markdown",
+            RecipeExportFormat::Text => "Text",
+        }
+    }
+    pub fn extension(&self) -> &'static str {
+        match self {
+            RecipeExportFormat::JsonLd => "jsonld",
+            RecipeExportFormat::Markdown => "md",
+            RecipeExportFormat::Text => "txt",
+        }
+    }
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            RecipeExportFormat::JsonLd => "application/ld+json",
+            RecipeExportFormat::Markdown => "text/markdown",
+            RecipeExportFormat::Text => "text/plain",
+        }
+    }
+    pub fn url(&self, recipe_id: u16) -> String {
+        format!("/recipe-export/{}/{}", recipe_id, self.as_str())
+    }
+    /// Zip of every recipe of the book
+    pub fn book_url(&self) -> String {
+        format!("/book-export/{}", self.as_str())
+    }
+}
+impl std::str::FromStr for RecipeExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json-ld" => Ok(RecipeExportFormat::JsonLd),
+            "markdown" => Ok(RecipeExportFormat::Markdown),
+            "text" => Ok(RecipeExportFormat::Text),
+            _ => Err(format!("Unknown export format: {}", s)),
+        }
+    }
+}
+
+/// File name from the recipe name: "Crème brûlée" -> "creme_brulee"
+pub fn export_file_name(recipe_name: &str) -> String {
+    let name: String = recipe_name
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'à' | 'â' | 'ä' | 'á' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'î' | 'ï' | 'í' => 'i',
+            'ô' | 'ö' | 'ó' => 'o',
+            'ù' | 'û' | 'ü' | 'ú' => 'u',
+            'ç' => 'c',
+            c if c.is_ascii_alphanumeric() => c,
+            _ => '_',
+        })
+        .collect();
+    let name = name
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<&str>>()
+        .join("_");
+    if name.is_empty() { "recipe".to_string() } else { name }
+}
+
+/// Servings are not a recipe field, importers keep them as a "Servings: 4" note line
+pub fn servings_from_notes(recipe: &Recipe) -> Option<String> {
+    recipe
+        .notes
+        .iter()
+        .flatten()
+        .flat_map(|note| note.content.lines())
+        .find_map(|line| {
+            let (label, value) = line.split_once(':')?;
+            let label = label.trim().to_lowercase();
+            let is_servings = ["servings", "serves", "yield", "portions", "parts"].contains(&label.as_str());
+            (is_servings && !value.trim().is_empty()).then(|| value.trim().to_string())
+        })
+}
+
+/// "Author, Book, p. 12"
+fn source_credits(source: &RecipeSource) -> String {
+    let page = if source.page.is_empty() { "".to_string() } else { format!("p. {}", source.page) };
+    [source.author.clone(), source.book.clone(), page]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+// Double quoted YAML scalar
+fn yaml_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', " "))
+}
+
+// Markdown list items and numbered steps continue on indented lines
+fn indent_lines(text: &str, indent: &str) -> String {
+    text.lines().collect::<Vec<&str>>().join(&format!("\n{}", indent))
+}
+
+/// Markdown, with the tags, servings and source in a YAML front matter
+pub fn recipe_to_markdown(recipe: &Recipe) -> String {
+    let mut out = String::new();
+
+    // Front matter
+    out.push_str("---\n");
+    out.push_str(&format!("title: {}\n", yaml_string(&recipe.name)));
+    if let Some(tags) = recipe.tags.as_ref().filter(|tags| !tags.is_empty()) {
+        out.push_str("tags:\n");
+        for tag in tags {
+            out.push_str(&format!("  - {}\n", yaml_string(&tag.name)));
+        }
+    }
+    if let Some(servings) = servings_from_notes(recipe) {
+        out.push_str(&format!("servings: {}\n", yaml_string(&servings)));
+    }
+    if let Some(source) = recipe.source.as_ref().filter(|source| !source.is_empty()) {
+        out.push_str("source:\n");
+        for (key, value) in [("url", &source.url), ("author", &source.author), ("book", &source.book), ("page", &source.page)] {
+            if !value.is_empty() {
+                out.push_str(&format!("  {}: {}\n", key, yaml_string(value)));
+            }
+        }
+    }
+    out.push_str("---\n\n");
+
+    out.push_str(&format!("# {}\n", recipe.name));
+
+    if let Some(groups) = recipe.ingredients.as_ref().filter(|groups| !groups.is_empty()) {
+        out.push_str("\n## Ingredients\n");
+        for group in groups {
+            if !group.name.is_empty() {
+                out.push_str(&format!("\n### {}\n", group.name));
+            }
+            out.push('\n');
+            for ingredient in &group.ingredients {
+                let content = RecipeLinks::plain_text(&ingredient.content);
+                if ingredient.qty_unit.is_empty() {
+                    out.push_str(&format!("- {}\n", content));
+                } else {
+                    out.push_str(&format!("- **{}** {}\n", ingredient.qty_unit, content));
+                }
+            }
+        }
+    }
+
+    if let Some(steps) = recipe.instructions.as_ref().filter(|steps| !steps.is_empty()) {
+        out.push_str("\n## Instructions\n\n");
+        for (index, step) in steps.iter().enumerate() {
+            let mut header = String::new();
+            if !step.title.is_empty() {
+                header.push_str(&format!("**{}** ", RecipeLinks::plain_text(&step.title)));
+            }
+            if !step.duration.is_empty() {
+                header.push_str(&format!("*({})* ", step.duration));
+            }
+            let content = indent_lines(&RecipeLinks::plain_text(&step.content), "   ");
+            if header.is_empty() {
+                out.push_str(&format!("{}. {}\n", index + 1, content));
+            } else {
+                out.push_str(&format!("{}. {}\n   {}\n", index + 1, header.trim_end(), content));
+            }
+        }
+    }
+
+    if let Some(notes) = recipe.notes.as_ref().filter(|notes| !notes.is_empty()) {
+        out.push_str("\n## Notes\n");
+        for note in notes {
+            out.push_str(&format!("\n{}\n", RecipeLinks::plain_text(&note.content)));
+        }
+    }
+
+    if let Some(source) = recipe.source.as_ref().filter(|source| !source.is_empty()) {
+        out.push_str("\n## Source\n\n");
+        let credits = source_credits(source);
+        match (credits.is_empty(), source.url.is_empty()) {
+            (false, false) => out.push_str(&format!("{} - <{}>\n", credits, source.url)),
+            (false, true) => out.push_str(&format!("{}\n", credits)),
+            _ => out.push_str(&format!("<{}>\n", source.url)),
+        }
+    }
+
+    out
+}
+
+// Title underlined with a repeated character
+fn text_title(title: &str, underline: char) -> String {
+    format!("{}\n{}\n", title, underline.to_string().repeat(title.chars().count()))
+}
+
+/// Plain text, readable as is or printed
+pub fn recipe_to_text(recipe: &Recipe) -> String {
+    let mut out = text_title(&recipe.name.to_uppercase(), '=');
+
+    let mut details: Vec<String> = vec![];
+    if let Some(tags) = recipe.tags.as_ref().filter(|tags| !tags.is_empty()) {
+        let tags: Vec<&str> = tags.iter().map(|tag| tag.name.as_str()).collect();
+        details.push(format!("Tags: {}", tags.join(", ")));
+    }
+    if let Some(servings) = servings_from_notes(recipe) {
+        details.push(format!("Servings: {}", servings));
+    }
+    if let Some(source) = recipe.source.as_ref().filter(|source| !source.is_empty()) {
+        let source = [source_credits(source), source.url.clone()]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<String>>()
+            .join(" - ");
+        details.push(format!("Source: {}", source));
+    }
+    if !details.is_empty() {
+        out.push('\n');
+        out.push_str(&details.join("\n"));
+        out.push('\n');
+    }
+
+    if let Some(groups) = recipe.ingredients.as_ref().filter(|groups| !groups.is_empty()) {
+        out.push('\n');
+        out.push_str(&text_title("INGREDIENTS", '-'));
+        for group in groups {
+            let indent = if group.name.is_empty() { "" } else { "  " };
+            if !group.name.is_empty() {
+                out.push_str(&format!("\n{}\n", group.name));
+            }
+            for ingredient in &group.ingredients {
+                let line = [ingredient.qty_unit.clone(), RecipeLinks::plain_text(&ingredient.content)]
+                    .into_iter()
+                    .filter(|part| !part.is_empty())
+                    .collect::<Vec<String>>()
+                    .join(" ");
+                out.push_str(&format!("{}- {}\n", indent, line));
+            }
+        }
+    }
+
+    if let Some(steps) = recipe.instructions.as_ref().filter(|steps| !steps.is_empty()) {
+        out.push('\n');
+        out.push_str(&text_title("INSTRUCTIONS", '-'));
+        for (index, step) in steps.iter().enumerate() {
+            let mut header = format!("{}.", index + 1);
+            if !step.title.is_empty() {
+                header.push_str(&format!(" {}", RecipeLinks::plain_text(&step.title)));
+            }
+            if !step.duration.is_empty() {
+                header.push_str(&format!(" ({})", step.duration));
+            }
+            let content = indent_lines(&RecipeLinks::plain_text(&step.content), "   ");
+            if step.title.is_empty() && step.duration.is_empty() {
+                out.push_str(&format!("\n{} {}\n", header, content));
+            } else {
+                out.push_str(&format!("\n{}\n   {}\n", header, content));
+            }
+        }
+    }
+
+    if let Some(notes) = recipe.notes.as_ref().filter(|notes| !notes.is_empty()) {
+        out.push('\n');
+        out.push_str(&text_title("NOTES", '-'));
+        for note in notes {
+            out.push_str(&format!("\n{}\n", RecipeLinks::plain_text(&note.content)));
+        }
+    }
+
+    out
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use super::*;
+    use crate::app::components::auth::auth_utils::SharedLoginStates;
+    use crate::app::components::recipe_server_functions::{get_recipe_by_id, ssr::{all_recipes, db}};
+    use crate::app::components::schema_org::recipe_to_json_ld;
+    use actix_web::{http::header, web, HttpRequest, HttpResponse};
+    use leptos::logging::*;
+
+    /// The recipe in the given format
+    pub fn render_recipe(recipe: &Recipe, format: RecipeExportFormat) -> Result<String, String> {
+        match format {
+            RecipeExportFormat::JsonLd => serde_json::to_string_pretty(&recipe_to_json_ld(recipe)).map_err(|e| e.to_string()),
+            RecipeExportFormat::Markdown => Ok(recipe_to_markdown(recipe)),
+            RecipeExportFormat::Text => Ok(recipe_to_text(recipe)),
+        }
+    }
+
+    /// Download of a single recipe, as an attachment, only for logged in users
+    #[actix_web::get("/recipe-export/{recipe_id}/{format}")]
+    pub async fn serve_recipe_export(
+        req: HttpRequest,
+        path: web::Path<(u16, String)>,
+        login_states: web::Data<SharedLoginStates>,
+    ) -> HttpResponse {
+        let is_logged_in = req
+            .connection_info()
+            .realip_remote_addr()
+            .is_some_and(|ip| login_states.is_ip_logged_in(ip));
+        if !is_logged_in {
+            return HttpResponse::Unauthorized().body("Not logged in.");
+        }
+
+        let (recipe_id, format) = path.into_inner();
+
+        let format: RecipeExportFormat = match format.parse() {
+            Ok(format) => format,
+            Err(e) => return HttpResponse::NotFound().body(e),
+        };
+
+        let recipe = match get_recipe_by_id(recipe_id).await {
+            Ok(recipe) => recipe,
+            Err(e) => {
+                error!("ERROR: Could not export recipe {:?}: {:?}", recipe_id, e.to_string());
+                return HttpResponse::NotFound().body("Recipe not found.");
+            }
+        };
+
+        let content = match render_recipe(&recipe, format) {
+            Ok(content) => content,
+            Err(e) => return HttpResponse::InternalServerError().body(e),
+        };
+
+        let file_name = format!("{}.{}", export_file_name(&recipe.name), format.extension());
+
+        HttpResponse::Ok()
+            .content_type(format!("{}; charset=utf-8", format.mime_type()))
+            .insert_header((
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", file_name),
+            ))
+            .body(content)
+    }
+
+    // One file per recipe, named after the recipe
+    async fn book_zip(format: RecipeExportFormat) -> Result<Vec<u8>, String> {
+        use std::io::Write;
+
+        let mut conn = db().await.map_err(|e| e.to_string())?;
+        let recipes = all_recipes(&mut conn).await.map_err(|e| e.to_string())?;
+
+        let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::<u8>::new()));
+        let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+        let mut used_names: Vec<String> = vec![];
+        for recipe in &recipes {
+            // Recipes can share a name
+            let base_name = export_file_name(&recipe.name);
+            let mut file_name = format!("{}.{}", base_name, format.extension());
+            let mut count = 1;
+            while used_names.contains(&file_name) {
+                count += 1;
+                file_name = format!("{}_{}.{}", base_name, count, format.extension());
+            }
+            used_names.push(file_name.clone());
+
+            zip.start_file(file_name, options).map_err(|e| e.to_string())?;
+            zip.write_all(render_recipe(recipe, format)?.as_bytes()).map_err(|e| e.to_string())?;
+        }
+        Ok(zip.finish().map_err(|e| e.to_string())?.into_inner())
+    }
+
+    /// Download of the whole book as a zip, only for logged in users
+    #[actix_web::get("/book-export/{format}")]
+    pub async fn serve_book_export(
+        req: HttpRequest,
+        path: web::Path<String>,
+        login_states: web::Data<SharedLoginStates>,
+    ) -> HttpResponse {
+        let is_logged_in = req
+            .connection_info()
+            .realip_remote_addr()
+            .is_some_and(|ip| login_states.is_ip_logged_in(ip));
+        if !is_logged_in {
+            return HttpResponse::Unauthorized().body("Not logged in.");
+        }
+
+        let format: RecipeExportFormat = match path.into_inner().parse() {
+            Ok(format) => format,
+            Err(e) => return HttpResponse::NotFound().body(e),
+        };
+
+        let content = match book_zip(format).await {
+            Ok(content) => content,
+            Err(e) => {
+                error!("ERROR: Could not export the book: {:?}", e);
+                return HttpResponse::InternalServerError().body(e);
+            }
+        };
+
+        let date_fmt = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let file_name = format!("cook_book_{}_{}.zip", format.as_str(), date_fmt);
+
+        HttpResponse::Ok()
+            .content_type("application/zip")
+            .insert_header((
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", file_name),
+            ))
+            .body(content)
+    }
+}
@@ -0,0 +1,478 @@
+use crate::app::{elements::popups::ServerWarningPopup, LoginCheckResource, ThemeColor};
+use leptos::logging::*;
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use server_fn::codec::{MultipartData, MultipartFormData};
+use wasm_bindgen::JsCast;
+use web_sys::{FormData, HtmlFormElement, SubmitEvent};
+
+/// Maximum size of an uploaded image (10 MB)
+pub const MAX_IMAGE_SIZE: usize = 10 * 1024 * 1024;
+
+/// An image attached to a recipe. The files are stored on disk, see `ssr::image_path`.
+#[derive(Default, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct RecipeImage {
+    pub id: u32,
+    pub recipe_id: u16,
+    pub position: u16,
+}
+impl RecipeImage {
+    pub fn url(&self, size: RecipeImageSize) -> String {
+        size.url(self.id)
+    }
+}
+
+/// Every image is stored in its original form along with resized copies
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RecipeImageSize {
+    Original,
+    // Used for the hero image on RecipeSheet
+    Large,
+    // Used on RecipeCard and in the editor
+    Thumbnail,
+}
+impl RecipeImageSize {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RecipeImageSize::Original => "original",
+            RecipeImageSize::Large => "large",
+            RecipeImageSize::Thumbnail => "thumbnail",
+        }
+    }
+    /// Maximum width and height of the resized copy, None for the original
+    pub fn max_dimension(&self) -> Option<u32> {
+        match self {
+            RecipeImageSize::Original => None,
+            RecipeImageSize::Large => Some(1280),
+            RecipeImageSize::Thumbnail => Some(400),
+        }
+    }
+    pub fn url(&self, image_id: u32) -> String {
+        format!("/recipe-images/{}/{}", image_id, self.as_str())
+    }
+}
+impl std::str::FromStr for RecipeImageSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "original" => Ok(RecipeImageSize::Original),
+            "large" => Ok(RecipeImageSize::Large),
+            "thumbnail" => Ok(RecipeImageSize::Thumbnail),
+            _ => Err(format!("Unknown image size: {}", s)),
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use super::*;
+    use crate::app::components::auth::auth_utils::SharedLoginStates;
+    use crate::app::components::recipe_server_functions::ssr::db;
+    use actix_web::{web, HttpRequest};
+    use sqlx::SqliteConnection;
+    use std::path::PathBuf;
+
+    /// Images are stored next to the database
+    pub const IMAGES_DIR: &str = "recipe_images";
+
+    /// Accepted image formats, with their mime type and file extension
+    pub const ACCEPTED_FORMATS: [(image::ImageFormat, &str, &str); 4] = [
+        (image::ImageFormat::Jpeg, "image/jpeg", "jpg"),
+        (image::ImageFormat::Png, "image/png", "png"),
+        (image::ImageFormat::WebP, "image/webp", "webp"),
+        (image::ImageFormat::Gif, "image/gif", "gif"),
+    ];
+
+    pub fn image_path(image_id: u32, size: RecipeImageSize, mime_type: &str) -> PathBuf {
+        let extension = match size {
+            RecipeImageSize::Original => ACCEPTED_FORMATS
+                .iter()
+                .find(|(_, mime, _)| *mime == mime_type)
+                .map(|(_, _, ext)| *ext)
+                .unwrap_or("bin"),
+            // Resized copies are always JPEG
+            _ => "jpg",
+        };
+        PathBuf::from(IMAGES_DIR).join(format!("{}_{}.{}", image_id, size.as_str(), extension))
+    }
+
+    /// Store the original image and its resized copies
+    /// This is CPU heavy, run it on a blocking thread
+    pub fn write_image_files(
+        image_id: u32,
+        mime_type: &str,
+        bytes: &[u8],
+    ) -> Result<(), ServerFnError> {
+        use image::codecs::jpeg::JpegEncoder;
+
+        std::fs::create_dir_all(IMAGES_DIR)?;
+        std::fs::write(image_path(image_id, RecipeImageSize::Original, mime_type), bytes)?;
+
+        let decoded = image::load_from_memory(bytes)?;
+        for size in [RecipeImageSize::Large, RecipeImageSize::Thumbnail] {
+            let max_dim = size.max_dimension().expect("resized copies to have a max dimension");
+            let resized = if decoded.width() > max_dim || decoded.height() > max_dim {
+                decoded.thumbnail(max_dim, max_dim)
+            } else {
+                decoded.clone()
+            };
+            let mut file = std::fs::File::create(image_path(image_id, size, mime_type))?;
+            JpegEncoder::new_with_quality(&mut file, 85).encode_image(&resized.to_rgb8())?;
+        }
+
+        Ok(())
+    }
+
+    pub fn remove_image_files(image_id: u32, mime_type: &str) {
+        for size in [RecipeImageSize::Original, RecipeImageSize::Large, RecipeImageSize::Thumbnail] {
+            let path = image_path(image_id, size, mime_type);
+            if let Err(e) = std::fs::remove_file(&path) {
+                error!("Could not remove image file {:?}: {:?}", path, e.to_string());
+            }
+        }
+    }
+
+    #[derive(sqlx::FromRow)]
+    pub struct DbRowImageFile {
+        pub id: u32,
+        pub mime_type: String,
+    }
+
+    /// The images of a recipe, to remove their files once the recipe is gone
+    pub async fn recipe_image_files(
+        conn: &mut SqliteConnection,
+        recipe_id: u16,
+    ) -> Result<Vec<DbRowImageFile>, ServerFnError> {
+        Ok(sqlx::query_as::<_, DbRowImageFile>(
+            "SELECT id, mime_type FROM recipe_images WHERE recipe_id = $1",
+        )
+        .bind(recipe_id)
+        .fetch_all(conn)
+        .await?)
+    }
+
+    /// Serves the image files, only to logged in users
+    #[actix_web::get("/recipe-images/{image_id}/{size}")]
+    pub async fn serve_recipe_image(
+        req: HttpRequest,
+        path: web::Path<(u32, String)>,
+        login_states: web::Data<SharedLoginStates>,
+    ) -> actix_web::Result<actix_files::NamedFile> {
+        let (image_id, size) = path.into_inner();
+
+        let is_logged_in = req
+            .connection_info()
+            .realip_remote_addr()
+            .is_some_and(|ip| login_states.is_ip_logged_in(ip));
+        if !is_logged_in {
+            return Err(actix_web::error::ErrorUnauthorized("Not logged in."));
+        }
+
+        let size = size
+            .parse::<RecipeImageSize>()
+            .map_err(actix_web::error::ErrorNotFound)?;
+
+        let mut conn = db()
+            .await
+            .map_err(|e| actix_web::error::ErrorInternalServerError(e.to_string()))?;
+        let row = sqlx::query_as::<_, DbRowImageFile>(
+            "SELECT id, mime_type FROM recipe_images WHERE id = $1",
+        )
+        .bind(image_id)
+        .fetch_optional(&mut conn)
+        .await
+        .map_err(|e| actix_web::error::ErrorInternalServerError(e.to_string()))?
+        .ok_or_else(|| actix_web::error::ErrorNotFound("Image not found."))?;
+
+        Ok(actix_files::NamedFile::open(image_path(row.id, size, &row.mime_type))?)
+    }
+}
+
+#[server(input = MultipartFormData)]
+pub async fn upload_recipe_image(data: MultipartData) -> Result<RecipeImage, ServerFnError> {
+    use self::ssr::*;
+    use crate::app::components::auth::auth_server_functions::check_login;
+    use crate::app::components::recipe_server_functions::ssr::db;
+
+    if !check_login().await? {
+        return Err(ServerFnError::ServerError("Not logged in.".to_string()));
+    }
+
+    // Read the form fields
+    let mut data = data.into_inner().expect("Expected multipart data on the server.");
+    let mut recipe_id: Option<u16> = None;
+    let mut bytes: Vec<u8> = vec![];
+    while let Some(mut field) = data.next_field().await? {
+        match field.name() {
+            Some("recipe_id") => recipe_id = Some(field.text().await?.parse()?),
+            Some("image") => {
+                while let Some(chunk) = field.chunk().await? {
+                    if bytes.len() + chunk.len() > MAX_IMAGE_SIZE {
+                        return Err(ServerFnError::ServerError(format!(
+                            "Image is too big, the maximum size is {} MB.",
+                            MAX_IMAGE_SIZE / (1024 * 1024)
+                        )));
+                    }
+                    bytes.extend_from_slice(&chunk);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    let Some(recipe_id) = recipe_id else {
+        return Err(ServerFnError::ServerError("No recipe ID for image upload.".to_string()));
+    };
+    if bytes.is_empty() {
+        return Err(ServerFnError::ServerError("No image was uploaded.".to_string()));
+    }
+
+    // Check the actual file content rather than the name or the declared type
+    let format = image::guess_format(&bytes)?;
+    let Some(mime_type) = ACCEPTED_FORMATS
+        .iter()
+        .find(|(accepted, _, _)| *accepted == format)
+        .map(|(_, mime, _)| mime.to_string())
+    else {
+        return Err(ServerFnError::ServerError(
+            "Only JPEG, PNG, WebP and GIF images are accepted.".to_string(),
+        ));
+    };
+
+    let mut conn = db().await?;
+
+    let image_id = sqlx::query(
+        "INSERT INTO recipe_images (recipe_id, mime_type, position)
+        SELECT $1, $2, COALESCE(MAX(position) + 1, 0) FROM recipe_images WHERE recipe_id = $1;",
+    )
+    .bind(recipe_id)
+    .bind(mime_type.clone())
+    .execute(&mut conn)
+    .await?
+    .last_insert_rowid() as u32;
+
+    let files_mime_type = mime_type.clone();
+    let written = tokio::task::spawn_blocking(move || {
+        write_image_files(image_id, &files_mime_type, &bytes)
+    })
+    .await?;
+
+    if let Err(e) = written {
+        error!("ERROR: Could not store image {:?}: {:?}", image_id, e.to_string());
+        remove_image_files(image_id, &mime_type);
+        sqlx::query("DELETE FROM recipe_images WHERE id = $1")
+            .bind(image_id)
+            .execute(&mut conn)
+            .await?;
+        return Err(e);
+    }
+
+    log!("Image {:?} was added to recipe {:?}", image_id, recipe_id);
+
+    Ok(sqlx::query_as::<_, RecipeImage>(
+        "SELECT id, recipe_id, position FROM recipe_images WHERE id = $1",
+    )
+    .bind(image_id)
+    .fetch_one(&mut conn)
+    .await?)
+}
+
+#[server]
+pub async fn get_recipe_images(recipe_id: u16) -> Result<Vec<RecipeImage>, ServerFnError> {
+    use crate::app::components::recipe_server_functions::ssr::db;
+
+    let mut conn = db().await?;
+
+    Ok(sqlx::query_as::<_, RecipeImage>(
+        "SELECT id, recipe_id, position FROM recipe_images WHERE recipe_id = $1 ORDER BY position, id",
+    )
+    .bind(recipe_id)
+    .fetch_all(&mut conn)
+    .await?)
+}
+
+#[server]
+pub async fn delete_recipe_image(image_id: u32) -> Result<(), ServerFnError> {
+    use self::ssr::*;
+    use crate::app::components::auth::auth_server_functions::check_login;
+    use crate::app::components::recipe_server_functions::ssr::db;
+
+    if !check_login().await? {
+        return Err(ServerFnError::ServerError("Not logged in.".to_string()));
+    }
+
+    let mut conn = db().await?;
+
+    let row = sqlx::query_as::<_, DbRowImageFile>(
+        "SELECT id, mime_type FROM recipe_images WHERE id = $1",
+    )
+    .bind(image_id)
+    .fetch_one(&mut conn)
+    .await?;
+
+    sqlx::query("DELETE FROM recipe_images WHERE id = $1")
+        .bind(image_id)
+        .execute(&mut conn)
+        .await?;
+
+    remove_image_files(row.id, &row.mime_type);
+
+    log!("Image {:?} was deleted", image_id);
+
+    Ok(())
+}
+
+/// The main image of a recipe, on top of RecipeSheet
+#[component]
+pub fn RecipeHeroImage(recipe_id: u16) -> impl IntoView {
+    // Images are only served to logged in users
+    let check_login_resource = use_context::<LoginCheckResource>()
+        .expect("Expected to find LoginCheckAction in context")
+        .0;
+
+    let images = Resource::new(
+        move || recipe_id,
+        move |recipe_id| async move {
+            match get_recipe_images(recipe_id).await {
+                Ok(images) => images,
+                Err(e) => {
+                    error!("Error fetching recipe images: {:?}", e.to_string());
+                    vec![]
+                }
+            }
+        },
+    );
+
+    view! {
+        <Transition fallback=|| ()>
+            {move || {
+                let hero = images.get().and_then(|images| images.into_iter().next());
+                match (check_login_resource.get(), hero) {
+                    (Some(true), Some(hero)) => view! {
+                        <img
+                            class="display-recipe hero-image"
+                            src=hero.url(RecipeImageSize::Large)
+                        />
+                    }.into_any(),
+                    _ => ().into_any(),
+                }
+            }}
+        </Transition>
+    }
+}
+
+/// Upload and delete the images of a recipe, in EditableRecipeSheet
+#[component]
+pub fn EditableRecipeImages(recipe_id: u16, theme_color: RwSignal<ThemeColor>) -> impl IntoView {
+    // FormData is not Send, so this action must stay local
+    let upload_action = Action::new_local(|data: &FormData| upload_recipe_image(data.clone().into()));
+    let upload_pending = upload_action.pending();
+    let upload_value = upload_action.value();
+
+    let delete_action = Action::new(|image_id: &u32| delete_recipe_image(*image_id));
+
+    let images = Resource::new(
+        move || (upload_action.version().get(), delete_action.version().get()),
+        move |_| get_recipe_images(recipe_id),
+    );
+
+    let on_submit = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        let form = ev
+            .target()
+            .expect("Expected the upload form as event target.")
+            .unchecked_into::<HtmlFormElement>();
+        match FormData::new_with_form(&form) {
+            Ok(form_data) => {
+                upload_action.dispatch_local(form_data);
+                form.reset();
+            }
+            Err(e) => error!("ERROR: Could not read the upload form: {:?}", e),
+        }
+    };
+
+    view! {
+        <div class="images container editable">
+
+            <h3
+                id="field-title"
+                class="images"
+                style=move || theme_color.get().as_visible_color()
+            >
+                "Photos"
+            </h3>
+
+            <Transition
+                fallback=move || view! {
+                    <ServerWarningPopup
+                        text="Loading Photos...".to_string()
+                    />
+                }
+            >
+                <ul class="editable-images">
+                    {move || {
+                        images.get().map(|images| match images {
+                            Ok(images) => images
+                                .into_iter()
+                                .map(|image| {
+                                    let image_id = image.id;
+                                    view! {
+                                        <li class="editable-image">
+                                            <img
+                                                class="editable-image-thumbnail"
+                                                src=image.url(RecipeImageSize::Thumbnail)
+                                            />
+                                            <button
+                                                class="remove-image-button"
+                                                on:click=move |ev| {
+                                                    ev.stop_propagation();
+                                                    delete_action.dispatch(image_id);
+                                                }
+                                            >
+                                                "×"
+                                            </button>
+                                        </li>
+                                    }
+                                })
+                                .collect_view()
+                                .into_any(),
+                            Err(e) => view! {
+                                <p class="error">"Server Error: " {e.to_string()}</p>
+                            }.into_any(),
+                        })
+                    }}
+                </ul>
+            </Transition>
+
+            <form
+                class="image-upload-form"
+                on:submit=on_submit
+            >
+                <input type="hidden" name="recipe_id" value=recipe_id />
+                <input
+                    class="image-upload-input"
+                    type="file"
+                    name="image"
+                    accept="image/jpeg,image/png,image/webp,image/gif"
+                />
+                <button
+                    class="image-upload-button"
+                    type="submit"
+                    disabled=upload_pending
+                >
+                    { move || if upload_pending.get() { "Uploading..." } else { "Upload" } }
+                </button>
+            </form>
+
+            {move || {
+                if let Some(Err(e)) = upload_value.get() {
+                    view! { <p class="image-upload-error">{e.to_string()}</p> }.into_any()
+                } else {
+                    ().into_any()
+                }
+            }}
+        </div>
+    }
+}
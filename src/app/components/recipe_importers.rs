@@ -0,0 +1,705 @@
+use crate::app::{components::recipe::*, RecipeServerAction};
+use leptos::logging::*;
+use leptos::prelude::*;
+use server_fn::codec::{MultipartData, MultipartFormData};
+use wasm_bindgen::JsCast;
+use web_sys::{FormData, HtmlFormElement, SubmitEvent};
+
+/// Maximum size of the imported files together (50 MB, Paprika archives contain the photos)
+pub const MAX_IMPORT_SIZE: usize = 50 * 1024 * 1024;
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use super::*;
+    use crate::app::components::schema_org::ssr::split_quantity;
+    use serde_json::Value;
+    use std::io::Read;
+
+    /// Read every recipe from a file exported by another app.
+    /// The format is found from the file name, then from the content.
+    pub fn read_recipes_from_file(file_name: &str, bytes: &[u8]) -> Result<Vec<JsonRecipe>, String> {
+        let lower_name = file_name.to_lowercase();
+
+        if lower_name.ends_with(".paprikarecipes") || lower_name.ends_with(".zip") || bytes.starts_with(b"PK") {
+            read_zip_archive(bytes)
+        } else {
+            read_recipe_file(file_name, bytes)
+        }
+    }
+
+    // A file holding recipes, never an archive: archives are only read at the top level
+    fn read_recipe_file(file_name: &str, bytes: &[u8]) -> Result<Vec<JsonRecipe>, String> {
+        let lower_name = file_name.to_lowercase();
+        let stem = file_stem(file_name);
+
+        if lower_name.ends_with(".paprikarecipe") || bytes.starts_with(&[0x1f, 0x8b]) {
+            Ok(vec![read_paprika_recipe(bytes)?])
+        } else if lower_name.ends_with(".cook") {
+            let text = String::from_utf8_lossy(bytes);
+            Ok(vec![read_cooklang_recipe(&text, &stem)])
+        } else if lower_name.ends_with(".json") {
+            read_mealie_json(bytes)
+        } else {
+            Err(format!("{}: unknown format, expected .paprikarecipes, .json, .zip or .cook", file_name))
+        }
+    }
+
+    fn file_stem(file_name: &str) -> String {
+        let name = file_name.rsplit(['/', '\\']).next().unwrap_or(file_name);
+        name.rsplit_once('.').map(|(stem, _)| stem).unwrap_or(name).to_string()
+    }
+
+    // At most `max_size` bytes, an error past it
+    fn read_limited(reader: impl Read, max_size: usize) -> Result<Vec<u8>, String> {
+        let mut bytes: Vec<u8> = vec![];
+        reader
+            .take(max_size as u64 + 1)
+            .read_to_end(&mut bytes)
+            .map_err(|e| e.to_string())?;
+        if bytes.len() > max_size {
+            Err(format!("The import unpacks to more than {} MB", MAX_IMPORT_SIZE / 1024 / 1024))
+        } else {
+            Ok(bytes)
+        }
+    }
+
+    // Paprika archives and Mealie exports are zip files, each entry is read by its extension.
+    // Everything unpacked, Paprika recipes included, counts towards MAX_IMPORT_SIZE.
+    fn read_zip_archive(bytes: &[u8]) -> Result<Vec<JsonRecipe>, String> {
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).map_err(|e| e.to_string())?;
+        let mut recipes: Vec<JsonRecipe> = vec![];
+        let mut unpacked_size: usize = 0;
+        for index in 0..archive.len() {
+            let mut entry = archive.by_index(index).map_err(|e| e.to_string())?;
+            if entry.is_dir() {
+                continue;
+            }
+            let entry_name = entry.name().to_string();
+            let lower_name = entry_name.to_lowercase();
+            if !(lower_name.ends_with(".paprikarecipe") || lower_name.ends_with(".json") || lower_name.ends_with(".cook")) {
+                continue;
+            }
+            let mut entry_bytes = read_limited(entry.by_ref(), MAX_IMPORT_SIZE - unpacked_size)?;
+            unpacked_size += entry_bytes.len();
+            if entry_bytes.starts_with(&[0x1f, 0x8b]) {
+                entry_bytes = read_limited(flate2::read::GzDecoder::new(entry_bytes.as_slice()), MAX_IMPORT_SIZE - unpacked_size)?;
+                unpacked_size += entry_bytes.len();
+            }
+            match read_recipe_file(&entry_name, &entry_bytes) {
+                Ok(entry_recipes) => recipes.extend(entry_recipes),
+                // Other json files can be in an export
+                Err(e) => log!("Skipping {:?}: {:?}", entry_name, e),
+            }
+        }
+        Ok(recipes)
+    }
+
+    // PAPRIKA -----
+
+    // Each recipe is a gzipped JSON object, already unpacked when it comes from an archive
+    fn read_paprika_recipe(bytes: &[u8]) -> Result<JsonRecipe, String> {
+        let json = if bytes.starts_with(&[0x1f, 0x8b]) {
+            read_limited(flate2::read::GzDecoder::new(bytes), MAX_IMPORT_SIZE)?
+        } else {
+            bytes.to_vec()
+        };
+        let paprika: Value = serde_json::from_slice(&json).map_err(|e| e.to_string())?;
+
+        let mut notes: Vec<String> = vec![];
+        push_text(&mut notes, &paprika, "description");
+        push_text(&mut notes, &paprika, "notes");
+        push_labeled(&mut notes, &paprika, &[("servings", "Servings"), ("prep_time", "Prep time"), ("cook_time", "Cook time"), ("total_time", "Total time")]);
+
+        let source = RecipeSource {
+            url: text_field(&paprika, "source_url"),
+            // Paprika's source is the name of the site or the book
+            author: text_field(&paprika, "source"),
+            ..Default::default()
+        };
+
+        Ok(JsonRecipe::from_recipe(Recipe {
+            id: None,
+            name: text_field(&paprika, "name"),
+            tags: tags_from_names(paprika.get("categories").map(string_list).unwrap_or_default()),
+            ingredients: ingredient_groups_from_lines(&text_field(&paprika, "ingredients")),
+            instructions: instructions_from_text(&text_field(&paprika, "directions")),
+            notes: notes_from_texts(notes),
+            source: source.cleaned(),
+        }))
+    }
+
+    // MEALIE -----
+
+    // A single recipe, a list of recipes, or an object holding the list
+    fn read_mealie_json(bytes: &[u8]) -> Result<Vec<JsonRecipe>, String> {
+        let value: Value = serde_json::from_slice(bytes).map_err(|e| e.to_string())?;
+        let recipes: Vec<Value> = match value {
+            Value::Array(recipes) => recipes,
+            Value::Object(mut object) => match object.remove("recipes").or_else(|| object.remove("items")) {
+                Some(Value::Array(recipes)) => recipes,
+                _ => vec![Value::Object(object)],
+            },
+            _ => vec![],
+        };
+        let recipes: Vec<JsonRecipe> = recipes
+            .iter()
+            .filter(|recipe| recipe.get("name").is_some() && recipe.get("recipeIngredient").is_some())
+            .map(read_mealie_recipe)
+            .collect();
+        if recipes.is_empty() {
+            Err("No Mealie recipe found in this file.".to_string())
+        } else {
+            Ok(recipes)
+        }
+    }
+
+    fn read_mealie_recipe(mealie: &Value) -> JsonRecipe {
+        // An ingredient with a title starts a new group
+        let mut groups: Vec<RecipeIngredientGroup> = vec![];
+        for ingredient in mealie.get("recipeIngredient").and_then(Value::as_array).into_iter().flatten() {
+            let title = text_field(ingredient, "title");
+            if groups.is_empty() || !title.is_empty() {
+                groups.push(RecipeIngredientGroup { name: title, ingredients: vec![] });
+            }
+            let food = ingredient.get("food").map(|food| text_field(food, "name")).unwrap_or_default();
+            let recipe_ingredient = if !food.is_empty() {
+                let quantity = ingredient.get("quantity").and_then(Value::as_f64).filter(|q| *q > 0.0);
+                let unit = ingredient.get("unit").map(|unit| text_field(unit, "name")).unwrap_or_default();
+                let qty_unit = [quantity.map(format_quantity).unwrap_or_default(), unit]
+                    .into_iter()
+                    .filter(|part| !part.is_empty())
+                    .collect::<Vec<String>>()
+                    .join(" ");
+                let note = text_field(ingredient, "note");
+                let content = if note.is_empty() { food } else { format!("{}, {}", food, note) };
+                RecipeIngredient { qty_unit, content }
+            } else {
+                let line = ["originalText", "display", "note"]
+                    .iter()
+                    .map(|key| text_field(ingredient, key))
+                    .find(|line| !line.is_empty())
+                    .unwrap_or_default();
+                split_quantity(&line)
+            };
+            if !recipe_ingredient.is_empty() {
+                groups.last_mut().expect("a group was pushed").ingredients.push(recipe_ingredient);
+            }
+        }
+        groups.retain(|group| !group.ingredients.is_empty());
+
+        let instructions: Vec<RecipeInstruction> = match mealie.get("recipeInstructions") {
+            Some(Value::Array(steps)) => steps
+                .iter()
+                .map(|step| match step {
+                    Value::String(text) => RecipeInstruction { content: text.trim().to_string(), ..Default::default() },
+                    _ => RecipeInstruction {
+                        title: text_field(step, "title"),
+                        content: text_field(step, "text"),
+                        ..Default::default()
+                    },
+                })
+                .filter(|step| !step.is_empty())
+                .collect(),
+            Some(Value::String(text)) => RecipeInstruction::from_text_blob(text),
+            _ => vec![],
+        };
+
+        let mut notes: Vec<String> = vec![];
+        push_text(&mut notes, mealie, "description");
+        for note in mealie.get("notes").and_then(Value::as_array).into_iter().flatten() {
+            let title = text_field(note, "title");
+            let text = text_field(note, "text");
+            notes.push(if title.is_empty() { text } else { format!("{}\n{}", title, text) });
+        }
+        push_labeled(&mut notes, mealie, &[("recipeYield", "Yield"), ("prepTime", "Prep time"), ("performTime", "Cook time"), ("totalTime", "Total time")]);
+
+        let mut tag_names: Vec<String> = vec![];
+        for key in ["tags", "recipeCategory"] {
+            for tag in mealie.get(key).and_then(Value::as_array).into_iter().flatten() {
+                tag_names.push(match tag {
+                    Value::String(name) => name.clone(),
+                    _ => text_field(tag, "name"),
+                });
+            }
+        }
+
+        let source = RecipeSource { url: text_field(mealie, "orgURL"), ..Default::default() };
+
+        JsonRecipe::from_recipe(Recipe {
+            id: None,
+            name: text_field(mealie, "name"),
+            tags: tags_from_names(tag_names),
+            ingredients: (!groups.is_empty()).then_some(groups),
+            instructions: (!instructions.is_empty()).then_some(instructions),
+            notes: notes_from_texts(notes),
+            source: source.cleaned(),
+        })
+    }
+
+    // COOKLANG -----
+
+    /// Cooklang: metadata, then steps separated by blank lines, where
+    /// @ingredient{qty%unit}, #cookware{} and ~timer{qty%unit} are marked inline
+    pub fn read_cooklang_recipe(text: &str, file_stem: &str) -> JsonRecipe {
+        use regex::Regex;
+
+        let mut metadata: Vec<(String, String)> = vec![];
+
+        // YAML front matter
+        let mut body = text.replace("\r\n", "\n");
+        if let Some(rest) = body.strip_prefix("---\n") {
+            if let Some((front_matter, after)) = rest.split_once("\n---") {
+                for line in front_matter.lines() {
+                    if let Some((key, value)) = line.split_once(':') {
+                        metadata.push((key.trim().to_lowercase(), value.trim().to_string()));
+                    }
+                }
+                body = after.trim_start_matches('-').to_string();
+            }
+        }
+
+        // Comments
+        let block_comment = Regex::new(r"(?s)\[-.*?-\]").unwrap();
+        let body = block_comment.replace_all(&body, "").to_string();
+        let line_comment = Regex::new(r"--.*$").unwrap();
+
+        let ingredient_re = Regex::new(r"@(?:([^@#~{}\n]+?)\{([^}]*)\}|([^\s@#~{}.,;:!?()]+))(\([^)]*\))?").unwrap();
+        let cookware_re = Regex::new(r"#(?:([^@#~{}\n]+?)\{[^}]*\}|([^\s@#~{}.,;:!?()]+))").unwrap();
+        let timer_re = Regex::new(r"~([^@#~{}\s]*)\{([^}]*)\}").unwrap();
+        let section_re = Regex::new(r"^=+\s*(.*?)\s*=*$").unwrap();
+
+        let mut groups: Vec<RecipeIngredientGroup> = vec![RecipeIngredientGroup::default()];
+        let mut instructions: Vec<RecipeInstruction> = vec![];
+        let mut section_title = String::new();
+
+        let mut paragraphs: Vec<Vec<String>> = vec![vec![]];
+        for line in body.lines() {
+            let line = line_comment.replace(line, "").trim().to_string();
+            if let Some(meta) = line.strip_prefix(">>") {
+                if let Some((key, value)) = meta.split_once(':') {
+                    metadata.push((key.trim().to_lowercase(), value.trim().to_string()));
+                }
+            } else if let Some(caps) = section_re.captures(&line) {
+                paragraphs.push(vec![format!("=SECTION={}", &caps[1])]);
+                paragraphs.push(vec![]);
+            } else if line.is_empty() {
+                paragraphs.push(vec![]);
+            } else {
+                paragraphs.last_mut().expect("there is always a paragraph").push(line);
+            }
+        }
+
+        for paragraph in paragraphs.into_iter().filter(|p| !p.is_empty()) {
+            if let Some(section) = paragraph[0].strip_prefix("=SECTION=") {
+                section_title = section.to_string();
+                groups.push(RecipeIngredientGroup { name: section.to_string(), ingredients: vec![] });
+                continue;
+            }
+            let step_text = paragraph.join(" ");
+
+            // Collect the ingredients, then keep only their names in the text
+            for caps in ingredient_re.captures_iter(&step_text) {
+                let name = caps.get(1).or(caps.get(3)).map(|m| m.as_str().trim()).unwrap_or_default();
+                let preparation = caps.get(4).map(|m| m.as_str()).unwrap_or_default();
+                let amount = caps.get(2).map(|m| m.as_str()).unwrap_or_default();
+                let content = if preparation.is_empty() { name.to_string() } else { format!("{} {}", name, preparation) };
+                groups.last_mut().expect("there is always a group").ingredients.push(RecipeIngredient {
+                    qty_unit: cooklang_amount(amount),
+                    content,
+                });
+            }
+            let step_text = ingredient_re.replace_all(&step_text, |caps: &regex::Captures| {
+                caps.get(1).or(caps.get(3)).map(|m| m.as_str().trim().to_string()).unwrap_or_default()
+            });
+            let step_text = cookware_re.replace_all(&step_text, |caps: &regex::Captures| {
+                caps.get(1).or(caps.get(2)).map(|m| m.as_str().trim().to_string()).unwrap_or_default()
+            });
+            let step_text = timer_re.replace_all(&step_text, |caps: &regex::Captures| cooklang_amount(&caps[2]));
+
+            instructions.push(RecipeInstruction {
+                title: std::mem::take(&mut section_title),
+                content: step_text.trim().to_string(),
+                duration: "".to_string(),
+            });
+        }
+        groups.retain(|group| !group.ingredients.is_empty());
+
+        let get_meta = |keys: &[&str]| {
+            metadata
+                .iter()
+                .find(|(key, _)| keys.contains(&key.as_str()))
+                .map(|(_, value)| value.trim_matches('"').to_string())
+                .unwrap_or_default()
+        };
+
+        let name = get_meta(&["title", "name"]);
+        let tag_names: Vec<String> = get_meta(&["tags", "tag"])
+            .trim_matches(['[', ']'])
+            .split(',')
+            .map(|tag| tag.trim().trim_matches('"').to_string())
+            .collect();
+
+        let mut notes: Vec<String> = vec![];
+        let description = get_meta(&["description", "introduction"]);
+        if !description.is_empty() {
+            notes.push(description);
+        }
+        let times: Vec<String> = [("servings", "Servings"), ("prep time", "Prep time"), ("cook time", "Cook time"), ("time", "Time"), ("duration", "Time")]
+            .iter()
+            .filter_map(|(key, label)| {
+                let value = get_meta(&[key]);
+                (!value.is_empty()).then(|| format!("{}: {}", label, value))
+            })
+            .collect();
+        if !times.is_empty() {
+            notes.push(times.join("\n"));
+        }
+
+        let source = RecipeSource {
+            url: get_meta(&["source", "source.url", "url"]).trim().to_string(),
+            author: get_meta(&["author", "source.author"]),
+            ..Default::default()
+        };
+        // "source" can be a name rather than an address
+        let source = if source.url.starts_with("http") {
+            source
+        } else {
+            RecipeSource { url: "".to_string(), author: [source.author, source.url].join(" ").trim().to_string(), ..source }
+        };
+
+        JsonRecipe::from_recipe(Recipe {
+            id: None,
+            name: if name.is_empty() { file_stem.to_string() } else { name },
+            tags: tags_from_names(tag_names),
+            ingredients: (!groups.is_empty()).then_some(groups),
+            instructions: (!instructions.is_empty()).then_some(instructions),
+            notes: notes_from_texts(notes),
+            source: source.cleaned(),
+        })
+    }
+
+    // "250%g" -> "250 g", "=1" (fixed quantity) -> "1"
+    fn cooklang_amount(amount: &str) -> String {
+        amount
+            .trim()
+            .trim_start_matches('=')
+            .split('%')
+            .map(|part| part.trim())
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+
+    // HELPERS -----
+
+    fn text_field(value: &Value, key: &str) -> String {
+        match value.get(key) {
+            Some(Value::String(text)) => text.trim().to_string(),
+            Some(Value::Number(number)) => number.to_string(),
+            _ => "".to_string(),
+        }
+    }
+
+    fn string_list(value: &Value) -> Vec<String> {
+        value
+            .as_array()
+            .map(|values| values.iter().filter_map(|v| v.as_str().map(|s| s.trim().to_string())).collect())
+            .unwrap_or_default()
+    }
+
+    fn push_text(notes: &mut Vec<String>, value: &Value, key: &str) {
+        let text = text_field(value, key);
+        if !text.is_empty() {
+            notes.push(text);
+        }
+    }
+
+    // Servings and times, in a single note
+    fn push_labeled(notes: &mut Vec<String>, value: &Value, keys: &[(&str, &str)]) {
+        let lines: Vec<String> = keys
+            .iter()
+            .filter_map(|(key, label)| {
+                let text = text_field(value, key);
+                (!text.is_empty()).then(|| format!("{}: {}", label, text))
+            })
+            .collect();
+        if !lines.is_empty() {
+            notes.push(lines.join("\n"));
+        }
+    }
+
+    fn format_quantity(quantity: f64) -> String {
+        format!("{:.2}", quantity)
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    }
+
+    fn tags_from_names(names: Vec<String>) -> Option<Vec<RecipeTag>> {
+        let mut tags: Vec<RecipeTag> = vec![];
+        for name in names.into_iter().map(|name| name.trim().to_string()).filter(|name| !name.is_empty()) {
+            if !tags.iter().any(|tag| tag.name.to_lowercase() == name.to_lowercase()) {
+                tags.push(RecipeTag { name });
+            }
+        }
+        (!tags.is_empty()).then_some(tags)
+    }
+
+    fn notes_from_texts(texts: Vec<String>) -> Option<Vec<RecipeNote>> {
+        let notes: Vec<RecipeNote> = texts
+            .into_iter()
+            .filter(|text| !text.trim().is_empty())
+            .map(|content| RecipeNote { content })
+            .collect();
+        (!notes.is_empty()).then_some(notes)
+    }
+
+    fn instructions_from_text(text: &str) -> Option<Vec<RecipeInstruction>> {
+        let steps = RecipeInstruction::from_text_blob(text);
+        (!steps.is_empty()).then_some(steps)
+    }
+
+    // One ingredient per line, a line ending with ':' starts a new group
+    fn ingredient_groups_from_lines(text: &str) -> Option<Vec<RecipeIngredientGroup>> {
+        let mut groups: Vec<RecipeIngredientGroup> = vec![RecipeIngredientGroup::default()];
+        for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            if let Some(name) = line.strip_suffix(':') {
+                groups.push(RecipeIngredientGroup { name: name.trim().to_string(), ingredients: vec![] });
+            } else {
+                groups.last_mut().expect("there is always a group").ingredients.push(split_quantity(line));
+            }
+        }
+        groups.retain(|group| !group.ingredients.is_empty());
+        (!groups.is_empty()).then_some(groups)
+    }
+}
+
+/// Read the recipes of files exported by other apps, for review before they are added
+#[server(input = MultipartFormData)]
+pub async fn read_recipes_to_import(data: MultipartData) -> Result<Vec<JsonRecipe>, ServerFnError> {
+    use self::ssr::*;
+    use crate::app::components::auth::auth_server_functions::check_login;
+
+    if !check_login().await? {
+        return Err(ServerFnError::ServerError("Not logged in.".to_string()));
+    }
+
+    let mut data = data.into_inner().expect("Expected multipart data on the server.");
+    let mut recipes: Vec<JsonRecipe> = vec![];
+    let mut errors: Vec<String> = vec![];
+    // Every file counts towards the same limit
+    let mut total_size: usize = 0;
+    while let Some(mut field) = data.next_field().await? {
+        if field.name() != Some("import_files") {
+            continue;
+        }
+        let file_name = field.file_name().unwrap_or_default().to_string();
+        let mut bytes: Vec<u8> = vec![];
+        while let Some(chunk) = field.chunk().await? {
+            if total_size + chunk.len() > MAX_IMPORT_SIZE {
+                return Err(ServerFnError::ServerError(format!(
+                    "The files are too big, the maximum size is {} MB in total.",
+                    MAX_IMPORT_SIZE / (1024 * 1024)
+                )));
+            }
+            total_size += chunk.len();
+            bytes.extend_from_slice(&chunk);
+        }
+        if bytes.is_empty() {
+            continue;
+        }
+
+        match read_recipes_from_file(&file_name, &bytes) {
+            Ok(file_recipes) => {
+                log!("{:?} recipes read from {:?}", file_recipes.len(), file_name);
+                recipes.extend(file_recipes);
+            }
+            Err(e) => errors.push(format!("{}: {}", file_name, e)),
+        }
+    }
+
+    if recipes.is_empty() {
+        let reason = if errors.is_empty() { "No recipe found.".to_string() } else { errors.join("\n") };
+        return Err(ServerFnError::ServerError(reason));
+    }
+    if !errors.is_empty() {
+        error!("ERROR: Some files could not be read: {:?}", errors);
+    }
+
+    Ok(recipes)
+}
+
+/// Import mode of the backup page: read files, then accept or skip each recipe
+#[component]
+pub fn ImportRecipes() -> impl IntoView {
+    // Recipe Action, accepted recipes are added like any new recipe
+    let recipe_action = use_context::<RecipeServerAction>()
+        .expect("To find RecipeServerAction in context.")
+        .0;
+    let add_pending = recipe_action.pending();
+    let add_value = recipe_action.value();
+
+    // FormData is not Send, so this action must stay local
+    let read_action = Action::new_local(|data: &FormData| read_recipes_to_import(data.clone().into()));
+    let read_pending = read_action.pending();
+    let read_value = read_action.value();
+
+    // Recipes waiting for review, with their accepted state
+    let to_review: RwSignal<Vec<(usize, Recipe, RwSignal<bool>)>> = RwSignal::new(vec![]);
+    let added_count: RwSignal<Option<usize>> = RwSignal::new(None);
+    Effect::new(move |_| {
+        if let Some(Ok(recipes)) = read_value.get() {
+            added_count.set(None);
+            to_review.set(
+                recipes
+                    .into_iter()
+                    .map(JsonRecipe::to_new_recipe)
+                    .enumerate()
+                    .map(|(index, recipe)| (index, recipe, RwSignal::new(true)))
+                    .collect(),
+            );
+        }
+    });
+
+    let on_submit = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        let form = ev
+            .target()
+            .expect("Expected the import form as event target.")
+            .unchecked_into::<HtmlFormElement>();
+        match FormData::new_with_form(&form) {
+            Ok(form_data) => {
+                read_action.dispatch_local(form_data);
+            }
+            Err(e) => error!("ERROR: Could not read the import form: {:?}", e),
+        }
+    };
+
+    let accepted_count = move || to_review.read().iter().filter(|(_, _, accepted)| accepted.get()).count();
+
+    let on_add_click = move |_| {
+        let accepted: Vec<Recipe> = to_review
+            .get_untracked()
+            .into_iter()
+            .filter(|(_, _, accepted)| accepted.get_untracked())
+            .map(|(_, recipe, _)| recipe)
+            .collect();
+        if accepted.is_empty() {
+            return;
+        }
+        added_count.set(Some(accepted.len()));
+        recipe_action.dispatch(RecipeActionDescriptor::AddMany(accepted));
+        to_review.set(vec![]);
+    };
+
+    let set_all = move |accept: bool| {
+        to_review.read_untracked().iter().for_each(|(_, _, accepted)| accepted.set(accept));
+    };
+
+    view! {
+        <div class="import-recipes">
+
+            <form
+                class="import-recipes-form"
+                on:submit=on_submit
+            >
+                <p class="import-recipes-info">
+                    "Paprika (.paprikarecipes), Mealie (.json or .zip) and Cooklang (.cook) files"
+                </p>
+                <input
+                    class="import-recipes-input"
+                    type="file"
+                    name="import_files"
+                    multiple=true
+                    accept=".paprikarecipes,.paprikarecipe,.json,.zip,.cook"
+                />
+                <button
+                    class="import-recipes-button"
+                    type="submit"
+                    disabled=read_pending
+                >
+                    { move || if read_pending.get() { "Reading..." } else { "Read files" } }
+                </button>
+            </form>
+
+            {move || {
+                if let Some(Err(e)) = read_value.get() {
+                    view! { <p class="import-recipes-error">{e.to_string()}</p> }.into_any()
+                } else {
+                    ().into_any()
+                }
+            }}
+
+            {move || added_count.get().map(|count| {
+                if add_pending.get() {
+                    view! { <p class="import-recipes-info">"Adding recipes..."</p> }.into_any()
+                } else {
+                    match add_value.get() {
+                        Some(Ok(())) => view! {
+                            <p class="import-recipes-info">{ format!("{} recipes were added.", count) }</p>
+                        }.into_any(),
+                        Some(Err(e)) => view! {
+                            <p class="import-recipes-error">"The recipes were not added: " { e.to_string() }</p>
+                        }.into_any(),
+                        None => ().into_any(),
+                    }
+                }
+            })}
+
+            <Show
+                when=move || !to_review.read().is_empty()
+            >
+                <div class="import-recipes-select">
+                    <button class="import-recipes-select-button" on:click=move |_| set_all(true)>"Accept all"</button>
+                    <button class="import-recipes-select-button" on:click=move |_| set_all(false)>"Skip all"</button>
+                </div>
+
+                <ul class="import-recipes-preview">
+                    <For
+                        each=move || to_review.get()
+                        key=|(index, _, _)| *index
+                        children=move |(_, recipe, accepted)| {
+                            let ingredients_count = RecipeIngredientGroup::flatten(recipe.ingredients.clone())
+                                .map(|ingredients| ingredients.len())
+                                .unwrap_or(0);
+                            let steps_count = recipe.instructions.as_ref().map(|steps| steps.len()).unwrap_or(0);
+                            let tags = recipe.tags
+                                .unwrap_or_default()
+                                .into_iter()
+                                .map(|tag| tag.name)
+                                .collect::<Vec<String>>()
+                                .join(", ");
+                            view! {
+                                <li
+                                    class="import-recipes-entry"
+                                    class:skipped=move || !accepted.get()
+                                >
+                                    <label class="import-recipes-entry-label">
+                                        <input
+                                            type="checkbox"
+                                            prop:checked=accepted
+                                            on:change=move |ev| accepted.set(event_target_checked(&ev))
+                                        />
+                                        <span class="import-recipes-entry-name">{ recipe.name }</span>
+                                    </label>
+                                    <span class="import-recipes-entry-details">
+                                        { format!("{} ingredients, {} steps", ingredients_count, steps_count) }
+                                    </span>
+                                    <span class="import-recipes-entry-details">{ tags }</span>
+                                </li>
+                            }
+                        }
+                    />
+                </ul>
+
+                <button
+                    class="import-recipes-button add"
+                    on:click=on_add_click
+                    disabled=move || accepted_count() == 0
+                >
+                    { move || format!("Add {} recipes", accepted_count()) }
+                </button>
+            </Show>
+        </div>
+    }
+}
@@ -0,0 +1,839 @@
+use crate::app::{components::recipe::*, AllTagsSignal, RecipesLightResource, SelectedTagsRwSignal};
+use leptos::prelude::*;
+
+/// Recipes of a PDF book: the chosen recipes, and the recipes having one of the tags.
+/// Nothing chosen means the whole Cook Book.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PdfBookSelection {
+    pub title: String,
+    pub recipe_ids: Vec<u16>,
+    pub tags: Vec<String>,
+}
+impl PdfBookSelection {
+    pub fn is_everything(&self) -> bool {
+        self.recipe_ids.is_empty() && self.tags.is_empty()
+    }
+
+    pub fn includes(&self, recipe_id: u16, recipe_tags: &Option<Vec<RecipeTag>>) -> bool {
+        self.is_everything()
+            || self.recipe_ids.contains(&recipe_id)
+            || recipe_tags
+                .iter()
+                .flatten()
+                .any(|tag| self.tags.iter().any(|t| RecipeLinks::same_name(t, &tag.name)))
+    }
+
+    // Repeated "id" and "tag" parameters, so tags can hold any character
+    pub fn to_query(&self) -> String {
+        let mut params: Vec<String> = vec![];
+        if !self.title.trim().is_empty() {
+            params.push(format!("title={}", urlencoding::encode(self.title.trim())));
+        }
+        params.extend(self.recipe_ids.iter().map(|id| format!("id={}", id)));
+        params.extend(self.tags.iter().map(|tag| format!("tag={}", urlencoding::encode(tag))));
+        params.join("&")
+    }
+
+    pub fn from_query(query: &str) -> Self {
+        let mut selection = PdfBookSelection::default();
+        for (key, value) in query.split('&').filter_map(|param| param.split_once('=')) {
+            let value = urlencoding::decode(&value.replace('+', " ")).map(|v| v.into_owned()).unwrap_or_default();
+            match key {
+                "title" => selection.title = value,
+                "id" => selection.recipe_ids.extend(value.parse::<u16>().ok()),
+                "tag" if !value.trim().is_empty() => selection.tags.push(value),
+                _ => (),
+            }
+        }
+        selection
+    }
+
+    pub fn url(&self) -> String {
+        format!("/book-pdf?{}", self.to_query())
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use super::*;
+    use crate::app::components::auth::auth_utils::SharedLoginStates;
+    use crate::app::components::recipe_export::export_file_name;
+    use crate::app::components::recipe_server_functions::ssr::{all_recipes, db};
+    use actix_web::{http::header, web, HttpRequest, HttpResponse};
+    use leptos::logging::*;
+    use std::collections::{BTreeMap, BTreeSet};
+
+    // A4, in points
+    const PAGE_WIDTH: f32 = 595.0;
+    const PAGE_HEIGHT: f32 = 842.0;
+    const MARGIN: f32 = 56.0;
+    const CONTENT_WIDTH: f32 = PAGE_WIDTH - 2.0 * MARGIN;
+
+    // FONTS -----
+
+    // The standard PDF fonts, every reader has them so nothing is embedded
+    #[derive(Clone, Copy, PartialEq)]
+    enum Font {
+        Regular,
+        Bold,
+        Italic,
+    }
+    impl Font {
+        const ALL: [Font; 3] = [Font::Regular, Font::Bold, Font::Italic];
+
+        fn resource(&self) -> &'static str {
+            match self {
+                Font::Regular => "F1",
+                Font::Bold => "F2",
+                Font::Italic => "F3",
+            }
+        }
+        fn base_font(&self) -> &'static str {
+            match self {
+                Font::Regular => "Helvetica",
+                Font::Bold => "Helvetica-Bold",
+                Font::Italic => "Helvetica-Oblique",
+            }
+        }
+    }
+
+    // Helvetica widths of the printable ASCII characters, in 1/1000 of the font size
+    const HELVETICA_WIDTHS: [u16; 95] = [
+        278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+        556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+        1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+        667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+        333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+        556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+    ];
+    const HELVETICA_BOLD_WIDTHS: [u16; 95] = [
+        278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278,
+        556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611,
+        975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778,
+        667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556,
+        333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611,
+        611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+    ];
+
+    /// Text in the WinAnsi encoding of the standard fonts, unknown characters become '?'
+    fn encode(text: &str) -> Vec<u8> {
+        let mut bytes: Vec<u8> = vec![];
+        for c in text.chars() {
+            match c {
+                ' '..='~' => bytes.push(c as u8),
+                '\t' | '\u{2009}' | '\u{202F}' => bytes.push(b' '),
+                '\u{A0}'..='\u{FF}' => bytes.push(c as u32 as u8),
+                '€' => bytes.push(0x80),
+                '…' => bytes.push(0x85),
+                'Œ' => bytes.push(0x8C),
+                '‘' => bytes.push(0x91),
+                '’' => bytes.push(0x92),
+                '“' => bytes.push(0x93),
+                '”' => bytes.push(0x94),
+                '•' => bytes.push(0x95),
+                '–' => bytes.push(0x96),
+                '—' => bytes.push(0x97),
+                'œ' => bytes.push(0x9C),
+                '⅓' => bytes.extend(b"1/3"),
+                '⅔' => bytes.extend(b"2/3"),
+                '⅛' => bytes.extend(b"1/8"),
+                '›' => bytes.push(0x9B),
+                '\u{0300}'..='\u{036F}' => (),
+                _ => bytes.push(b'?'),
+            }
+        }
+        bytes
+    }
+
+    fn byte_width(font: Font, byte: u8) -> u16 {
+        let widths = if font == Font::Bold { &HELVETICA_BOLD_WIDTHS } else { &HELVETICA_WIDTHS };
+        // Accented letters are as wide as their base letter
+        let base = match byte {
+            0xC0..=0xC5 => b'A',
+            0xC7 => b'C',
+            0xC8..=0xCB => b'E',
+            0xCC..=0xCF => b'I',
+            0xD1 => b'N',
+            0xD2..=0xD6 | 0xD8 => b'O',
+            0xD9..=0xDC => b'U',
+            0xDD => b'Y',
+            0xE0..=0xE5 => b'a',
+            0xE7 => b'c',
+            0xE8..=0xEB => b'e',
+            0xEC..=0xEF => b'i',
+            0xF1 => b'n',
+            0xF2..=0xF6 | 0xF8 => b'o',
+            0xF9..=0xFC => b'u',
+            0xFD | 0xFF => b'y',
+            _ => byte,
+        };
+        match base {
+            0x20..=0x7E => widths[(base - 0x20) as usize],
+            0x85 | 0x97 | 0x8C | 0xC6 => 1000,
+            0x9C | 0xE6 => 944,
+            0x91 | 0x92 | 0xB7 => 278,
+            0x95 => 350,
+            0xB0 => 400,
+            0xBC..=0xBE => 834,
+            _ => 556,
+        }
+    }
+
+    fn text_width(font: Font, size: f32, text: &str) -> f32 {
+        encode(text).into_iter().map(|b| byte_width(font, b) as f32).sum::<f32>() * size / 1000.0
+    }
+
+    // A PDF literal string, with the non ASCII bytes escaped
+    fn pdf_string(text: &str) -> String {
+        let mut out = "(".to_string();
+        for byte in encode(text) {
+            match byte {
+                b'(' | b')' | b'\\' => {
+                    out.push('\\');
+                    out.push(byte as char);
+                }
+                0x20..=0x7E => out.push(byte as char),
+                _ => out.push_str(&format!("\\{:03o}", byte)),
+            }
+        }
+        out.push(')');
+        out
+    }
+
+    /// Lines of at most `max_width`, words too long for a line are cut
+    fn wrap(text: &str, font: Font, size: f32, max_width: f32) -> Vec<String> {
+        let mut lines: Vec<String> = vec![];
+        for paragraph in text.lines() {
+            let mut line = String::new();
+            for word in paragraph.split_whitespace() {
+                let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+                if text_width(font, size, &candidate) <= max_width {
+                    line = candidate;
+                    continue;
+                }
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+                for c in word.chars() {
+                    line.push(c);
+                    if text_width(font, size, &line) > max_width && line.chars().count() > 1 {
+                        line.pop();
+                        lines.push(std::mem::replace(&mut line, c.to_string()));
+                    }
+                }
+            }
+            if !line.is_empty() || paragraph.trim().is_empty() {
+                lines.push(line);
+            }
+        }
+        lines
+    }
+
+    // Cut with an ellipsis to fit
+    fn truncate(text: &str, font: Font, size: f32, max_width: f32) -> String {
+        if text_width(font, size, text) <= max_width {
+            return text.to_string();
+        }
+        let mut out = text.to_string();
+        while !out.is_empty() && text_width(font, size, &format!("{}…", out)) > max_width {
+            out.pop();
+        }
+        format!("{}…", out.trim_end())
+    }
+
+    // LAYOUT -----
+
+    /// Pages being filled from top to bottom
+    struct Layout {
+        pages: Vec<String>,
+        y: f32,
+        // Repeated on top of the pages a recipe continues on
+        running_title: Option<String>,
+    }
+    impl Layout {
+        fn new() -> Self {
+            Layout { pages: vec![], y: 0.0, running_title: None }
+        }
+
+        fn new_page(&mut self) {
+            self.pages.push(String::new());
+            self.y = PAGE_HEIGHT - MARGIN;
+            if let Some(title) = self.running_title.clone() {
+                let title = format!("{} (continued)", title);
+                self.text(MARGIN, self.y, Font::Italic, 9.0, &title);
+                self.y -= 24.0;
+            }
+        }
+
+        // Starts a new page if the next block does not fit
+        fn reserve(&mut self, height: f32) {
+            if self.pages.is_empty() || self.y - height < MARGIN + 20.0 {
+                self.new_page();
+            }
+        }
+
+        fn text(&mut self, x: f32, y: f32, font: Font, size: f32, text: &str) {
+            let page = self.pages.last_mut().expect("a page was started");
+            page.push_str(&format!(
+                "BT /{} {:.1} Tf {:.2} {:.2} Td {} Tj ET\n",
+                font.resource(),
+                size,
+                x,
+                y,
+                pdf_string(text)
+            ));
+        }
+
+        fn rule(&mut self, width: f32) {
+            self.reserve(10.0);
+            let y = self.y;
+            let page = self.pages.last_mut().expect("a page was started");
+            page.push_str(&format!("0.6 G 0.5 w {:.2} {:.2} m {:.2} {:.2} l S 0 G\n", MARGIN, y, MARGIN + width, y));
+            self.y -= 10.0;
+        }
+
+        fn space(&mut self, height: f32) {
+            self.y -= height;
+        }
+
+        /// Wrapped text, continued on the next page if needed
+        fn paragraph(&mut self, text: &str, font: Font, size: f32, indent: f32) {
+            let leading = size * 1.35;
+            for line in wrap(text, font, size, CONTENT_WIDTH - indent) {
+                self.reserve(leading);
+                self.y -= size;
+                self.text(MARGIN + indent, self.y, font, size, &line);
+                self.y -= leading - size;
+            }
+        }
+
+        fn heading(&mut self, text: &str, size: f32) {
+            // Keep the heading with the first lines after it
+            self.reserve(size * 1.35 + 40.0);
+            self.space(size * 0.5);
+            self.paragraph(text, Font::Bold, size, 0.0);
+            self.space(size * 0.3);
+        }
+
+        // "Name ........ 12"
+        fn dotted_line(&mut self, indent: f32, font: Font, size: f32, name: &str, page_numbers: &str) {
+            let leading = size * 1.4;
+            self.reserve(leading);
+            self.y -= size;
+            let numbers_width = text_width(Font::Regular, size, page_numbers);
+            let name = truncate(name, font, size, CONTENT_WIDTH - indent - numbers_width - 20.0);
+            let name_width = text_width(font, size, &name);
+            let dot_width = text_width(Font::Regular, size, ".");
+            let dots_count = ((CONTENT_WIDTH - indent - name_width - numbers_width - 8.0) / dot_width).max(0.0) as usize;
+            let y = self.y;
+            self.text(MARGIN + indent, y, font, size, &name);
+            let dots_x = MARGIN + CONTENT_WIDTH - numbers_width - 4.0 - dots_count as f32 * dot_width;
+            self.text(dots_x, y, Font::Regular, size, &".".repeat(dots_count));
+            self.text(MARGIN + CONTENT_WIDTH - numbers_width, y, Font::Regular, size, page_numbers);
+            self.y -= leading - size;
+        }
+    }
+
+    // BOOK -----
+
+    fn layout_cover(layout: &mut Layout, title: &str, recipes_count: usize) {
+        layout.new_page();
+        layout.y = PAGE_HEIGHT * 0.62;
+        for line in wrap(title, Font::Bold, 34.0, CONTENT_WIDTH) {
+            let x = (PAGE_WIDTH - text_width(Font::Bold, 34.0, &line)) / 2.0;
+            layout.y -= 34.0;
+            layout.text(x, layout.y, Font::Bold, 34.0, &line);
+            layout.y -= 12.0;
+        }
+        layout.y -= 20.0;
+        let subtitle = format!(
+            "{} recipe{} - {}",
+            recipes_count,
+            if recipes_count == 1 { "" } else { "s" },
+            chrono::Local::now().format("%B %Y")
+        );
+        let x = (PAGE_WIDTH - text_width(Font::Italic, 13.0, &subtitle)) / 2.0;
+        layout.text(x, layout.y, Font::Italic, 13.0, &subtitle);
+    }
+
+    /// Table of contents grouped by tag, a recipe is listed under each of its tags
+    fn layout_contents(
+        layout: &mut Layout,
+        recipes: &[Recipe],
+        start_pages: &[usize],
+        selection: &PdfBookSelection,
+    ) {
+        // Lowercase tag -> (tag, [recipe indexes])
+        let mut groups: BTreeMap<String, (String, Vec<usize>)> = BTreeMap::new();
+        let mut others: Vec<usize> = vec![];
+        for (index, recipe) in recipes.iter().enumerate() {
+            let tags: Vec<&RecipeTag> = recipe
+                .tags
+                .iter()
+                .flatten()
+                // With a tag filter, only the chosen tags make chapters
+                .filter(|tag| selection.tags.is_empty() || selection.tags.iter().any(|t| RecipeLinks::same_name(t, &tag.name)))
+                .collect();
+            if tags.is_empty() {
+                others.push(index);
+            }
+            for tag in tags {
+                groups
+                    .entry(tag.name.trim().to_lowercase())
+                    .or_insert_with(|| (tag.name.trim().to_string(), vec![]))
+                    .1
+                    .push(index);
+            }
+        }
+        let mut chapters: Vec<(String, Vec<usize>)> = groups.into_values().collect();
+        if !others.is_empty() {
+            let name = if chapters.is_empty() { "Recipes" } else { "Other recipes" };
+            chapters.push((name.to_string(), others));
+        }
+
+        layout.running_title = None;
+        layout.new_page();
+        layout.heading("Contents", 24.0);
+        layout.space(6.0);
+        for (chapter, indexes) in chapters {
+            layout.heading(&chapter, 13.0);
+            for index in indexes {
+                let page = start_pages.get(index).map(|p| p.to_string()).unwrap_or_default();
+                layout.dotted_line(12.0, Font::Regular, 11.0, &recipes[index].name, &page);
+            }
+        }
+    }
+
+    /// One recipe, starting on a new page
+    fn layout_recipe(layout: &mut Layout, recipe: &Recipe) {
+        layout.running_title = None;
+        layout.new_page();
+
+        layout.paragraph(&recipe.name, Font::Bold, 22.0, 0.0);
+        layout.space(4.0);
+
+        if let Some(tags) = recipe.tags.as_ref().filter(|tags| !tags.is_empty()) {
+            let tags: Vec<&str> = tags.iter().map(|tag| tag.name.as_str()).collect();
+            layout.paragraph(&tags.join(" · "), Font::Italic, 10.0, 0.0);
+        }
+        if let Some(source) = recipe.source.as_ref().filter(|source| !source.is_empty()) {
+            let page = if source.page.is_empty() { "".to_string() } else { format!("p. {}", source.page) };
+            let credits = [source.author.clone(), source.book.clone(), page, source.url.clone()]
+                .into_iter()
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<String>>()
+                .join(", ");
+            layout.paragraph(&format!("Source: {}", credits), Font::Italic, 10.0, 0.0);
+        }
+        layout.space(4.0);
+        layout.rule(CONTENT_WIDTH);
+        layout.running_title = Some(recipe.name.clone());
+
+        if let Some(groups) = recipe.ingredients.as_ref().filter(|groups| !groups.is_empty()) {
+            layout.heading("Ingredients", 14.0);
+            for group in groups {
+                if !group.name.is_empty() {
+                    layout.space(4.0);
+                    layout.paragraph(&group.name, Font::Bold, 11.0, 0.0);
+                }
+                for ingredient in &group.ingredients {
+                    // Quantities in a column, the ingredient wraps next to it
+                    let qty_width = (text_width(Font::Bold, 11.0, &ingredient.qty_unit) + 10.0).clamp(70.0, 160.0);
+                    let lines = wrap(&RecipeLinks::plain_text(&ingredient.content), Font::Regular, 11.0, CONTENT_WIDTH - 12.0 - qty_width);
+                    for (line_index, line) in lines.iter().enumerate() {
+                        layout.reserve(15.0);
+                        layout.y -= 11.0;
+                        let y = layout.y;
+                        if line_index == 0 {
+                            layout.text(MARGIN, y, Font::Regular, 11.0, "•");
+                            layout.text(MARGIN + 12.0, y, Font::Bold, 11.0, &truncate(&ingredient.qty_unit, Font::Bold, 11.0, qty_width - 6.0));
+                        }
+                        layout.text(MARGIN + 12.0 + qty_width, y, Font::Regular, 11.0, line);
+                        layout.y -= 4.0;
+                    }
+                }
+            }
+        }
+
+        if let Some(steps) = recipe.instructions.as_ref().filter(|steps| !steps.is_empty()) {
+            layout.heading("Instructions", 14.0);
+            for (index, step) in steps.iter().enumerate() {
+                layout.reserve(40.0);
+                layout.y -= 11.0;
+                let y = layout.y;
+                layout.text(MARGIN, y, Font::Bold, 11.0, &format!("{}.", index + 1));
+                let mut header = RecipeLinks::plain_text(&step.title);
+                if !step.duration.is_empty() {
+                    header = format!("{} ({})", header, step.duration).trim().to_string();
+                }
+                if header.is_empty() {
+                    layout.y += 11.0;
+                } else {
+                    layout.text(MARGIN + 20.0, y, Font::Bold, 11.0, &truncate(&header, Font::Bold, 11.0, CONTENT_WIDTH - 20.0));
+                    layout.y -= 4.0;
+                }
+                layout.paragraph(&RecipeLinks::plain_text(&step.content), Font::Regular, 11.0, 20.0);
+                layout.space(5.0);
+            }
+        }
+
+        if let Some(notes) = recipe.notes.as_ref().filter(|notes| !notes.is_empty()) {
+            layout.heading("Notes", 14.0);
+            for note in notes {
+                layout.paragraph(&RecipeLinks::plain_text(&note.content), Font::Italic, 10.5, 0.0);
+                layout.space(6.0);
+            }
+        }
+        layout.running_title = None;
+    }
+
+    /// "Flour, sifted (200 g)" -> "flour"
+    fn index_key(content: &str) -> Option<(String, String)> {
+        let name = RecipeLinks::plain_text(content);
+        let name = name.split([',', '(', ';']).next().unwrap_or_default().trim().to_string();
+        if name.is_empty() {
+            return None;
+        }
+        let key: String = name
+            .to_lowercase()
+            .chars()
+            .map(|c| match c {
+                'à' | 'â' | 'ä' | 'á' => 'a',
+                'é' | 'è' | 'ê' | 'ë' => 'e',
+                'î' | 'ï' | 'í' => 'i',
+                'ô' | 'ö' | 'ó' => 'o',
+                'ù' | 'û' | 'ü' | 'ú' => 'u',
+                'ç' => 'c',
+                c => c,
+            })
+            .collect();
+        Some((key, name))
+    }
+
+    /// Index of the ingredients, with the pages of the recipes using them
+    fn layout_index(layout: &mut Layout, recipes: &[Recipe], start_pages: &[usize]) {
+        let mut entries: BTreeMap<String, (String, BTreeSet<usize>)> = BTreeMap::new();
+        for (recipe, page) in recipes.iter().zip(start_pages) {
+            for ingredient in recipe.ingredients.iter().flatten().flat_map(|group| &group.ingredients) {
+                if let Some((key, name)) = index_key(&ingredient.content) {
+                    entries.entry(key).or_insert_with(|| (name, BTreeSet::new())).1.insert(*page);
+                }
+            }
+        }
+
+        layout.running_title = None;
+        layout.new_page();
+        layout.heading("Index of ingredients", 24.0);
+        let mut letter: Option<char> = None;
+        for (key, (name, pages)) in entries {
+            let first = key.chars().next().map(|c| c.to_ascii_uppercase());
+            if first != letter {
+                letter = first;
+                layout.heading(&first.map(String::from).unwrap_or_default(), 13.0);
+            }
+            let pages = pages.iter().map(|p| p.to_string()).collect::<Vec<String>>().join(", ");
+            let mut name_chars = name.chars();
+            let name: String = name_chars.next().map(|c| c.to_uppercase().collect::<String>()).unwrap_or_default() + name_chars.as_str();
+            layout.dotted_line(12.0, Font::Regular, 10.0, &name, &pages);
+        }
+    }
+
+    /// The whole book, as PDF bytes
+    pub fn cookbook_pdf(title: &str, recipes: &[Recipe], selection: &PdfBookSelection) -> Vec<u8> {
+        // Recipes first, the page numbers are needed for the contents
+        let mut body = Layout::new();
+        let mut recipe_pages: Vec<usize> = vec![];
+        for recipe in recipes {
+            recipe_pages.push(body.pages.len());
+            layout_recipe(&mut body, recipe);
+        }
+
+        // The contents length does not depend on the page numbers
+        let mut contents = Layout::new();
+        layout_contents(&mut contents, recipes, &recipe_pages, selection);
+        let first_recipe_page = 1 + contents.pages.len() + 1;
+        let start_pages: Vec<usize> = recipe_pages.iter().map(|p| p + first_recipe_page).collect();
+        let mut contents = Layout::new();
+        layout_contents(&mut contents, recipes, &start_pages, selection);
+
+        let mut index = Layout::new();
+        layout_index(&mut index, recipes, &start_pages);
+
+        let mut cover = Layout::new();
+        layout_cover(&mut cover, title, recipes.len());
+
+        let mut pages: Vec<String> = vec![];
+        pages.extend(cover.pages);
+        pages.extend(contents.pages);
+        pages.extend(body.pages);
+        pages.extend(index.pages);
+
+        // Page numbers, not on the cover
+        for (number, page) in pages.iter_mut().enumerate().skip(1) {
+            let label = (number + 1).to_string();
+            let x = (PAGE_WIDTH - text_width(Font::Regular, 9.0, &label)) / 2.0;
+            page.push_str(&format!("BT /{} 9.0 Tf {:.2} {:.2} Td {} Tj ET\n", Font::Regular.resource(), x, MARGIN / 2.0, pdf_string(&label)));
+        }
+
+        write_pdf(title, &pages)
+    }
+
+    // PDF FILE -----
+
+    fn compress(content: &str) -> Vec<u8> {
+        use std::io::Write;
+        let mut encoder = flate2::write::ZlibEncoder::new(vec![], flate2::Compression::default());
+        encoder.write_all(content.as_bytes()).expect("to compress in memory");
+        encoder.finish().expect("to compress in memory")
+    }
+
+    /// Objects: catalog, page tree, fonts, info, then a page and its content for each page
+    fn write_pdf(title: &str, pages: &[String]) -> Vec<u8> {
+        let mut out: Vec<u8> = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets: Vec<usize> = vec![];
+
+        let fonts_start = 3;
+        let info_id = fonts_start + Font::ALL.len();
+        let first_page_id = info_id + 1;
+        let page_ids: Vec<usize> = (0..pages.len()).map(|i| first_page_id + 2 * i).collect();
+
+        let mut objects: Vec<Vec<u8>> = vec![];
+        objects.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+        let kids = page_ids.iter().map(|id| format!("{} 0 R", id)).collect::<Vec<String>>().join(" ");
+        objects.push(format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids, pages.len()).into_bytes());
+        for font in Font::ALL {
+            objects.push(
+                format!("<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>", font.base_font()).into_bytes(),
+            );
+        }
+        objects.push(format!("<< /Title {} /Producer (Home Cook Book) >>", pdf_string(title)).into_bytes());
+
+        let font_resources = Font::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, font)| format!("/{} {} 0 R", font.resource(), fonts_start + i))
+            .collect::<Vec<String>>()
+            .join(" ");
+        for (page, page_id) in pages.iter().zip(&page_ids) {
+            objects.push(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << {} >> >> /Contents {} 0 R >>",
+                    PAGE_WIDTH,
+                    PAGE_HEIGHT,
+                    font_resources,
+                    page_id + 1
+                )
+                .into_bytes(),
+            );
+            let stream = compress(page);
+            let mut object = format!("<< /Length {} /Filter /FlateDecode >>\nstream\n", stream.len()).into_bytes();
+            object.extend(stream);
+            object.extend(b"\nendstream");
+            objects.push(object);
+        }
+
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.extend(format!("{} 0 obj\n", index + 1).into_bytes());
+            out.extend(object);
+            out.extend(b"\nendobj\n");
+        }
+
+        let xref_offset = out.len();
+        out.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).into_bytes());
+        for offset in offsets {
+            out.extend(format!("{:010} 00000 n \n", offset).into_bytes());
+        }
+        out.extend(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R /Info {} 0 R >>\nstartxref\n{}\n%%EOF\n",
+                objects.len() + 1,
+                info_id,
+                xref_offset
+            )
+            .into_bytes(),
+        );
+        out
+    }
+
+    /// PDF book of the recipes chosen in the query, only for logged in users
+    #[actix_web::get("/book-pdf")]
+    pub async fn serve_book_pdf(req: HttpRequest, login_states: web::Data<SharedLoginStates>) -> HttpResponse {
+        let is_logged_in = req
+            .connection_info()
+            .realip_remote_addr()
+            .is_some_and(|ip| login_states.is_ip_logged_in(ip));
+        if !is_logged_in {
+            return HttpResponse::Unauthorized().body("Not logged in.");
+        }
+
+        let selection = PdfBookSelection::from_query(req.query_string());
+
+        let recipes = match db().await {
+            Ok(mut conn) => all_recipes(&mut conn).await,
+            Err(e) => Err(e),
+        };
+        let recipes: Vec<Recipe> = match recipes {
+            Ok(recipes) => recipes
+                .into_iter()
+                .filter(|recipe| selection.includes(recipe.id.unwrap_or_default(), &recipe.tags))
+                .collect(),
+            Err(e) => {
+                error!("ERROR: Could not load the recipes of the PDF book: {:?}", e.to_string());
+                return HttpResponse::InternalServerError().body(e.to_string());
+            }
+        };
+        if recipes.is_empty() {
+            return HttpResponse::NotFound().body("No recipe matches this selection.");
+        }
+
+        let title = if selection.title.trim().is_empty() { "Cook Book".to_string() } else { selection.title.trim().to_string() };
+        let content = cookbook_pdf(&title, &recipes, &selection);
+        log!("PDF book {:?} made with {:?} recipes", title, recipes.len());
+
+        HttpResponse::Ok()
+            .content_type("application/pdf")
+            .insert_header((
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}.pdf\"", export_file_name(&title)),
+            ))
+            .body(content)
+    }
+}
+
+/// Choice of the recipes of a PDF book, by tag or one by one
+#[component]
+pub fn PdfBookForm() -> impl IntoView {
+    let all_recipes_light = use_context::<RecipesLightResource>()
+        .expect("To find RecipesLightResource in context.")
+        .0;
+    let all_tags = use_context::<AllTagsSignal>()
+        .expect("To find AllTagsSignal in context.")
+        .0;
+    // Start from the tags chosen on the home page
+    let home_selected_tags = use_context::<SelectedTagsRwSignal>()
+        .expect("To find SelectedTagsRwSignal in context.")
+        .0;
+
+    let selection = RwSignal::new(PdfBookSelection {
+        title: "Family Cook Book".to_string(),
+        recipe_ids: vec![],
+        tags: home_selected_tags.get_untracked(),
+    });
+
+    let recipes_count = move || {
+        let selection = selection.read();
+        match all_recipes_light.get() {
+            Some(Ok(recipes)) => recipes.iter().filter(|r| selection.includes(r.id, &r.tags)).count(),
+            _ => 0,
+        }
+    };
+
+    let tag_elems = move || {
+        all_tags
+            .get()
+            .into_iter()
+            .map(|tag| {
+                let tag_name = tag.clone();
+                let is_selected = move || selection.read().tags.contains(&tag_name);
+                let tag_name = tag.clone();
+                view! {
+                    <button
+                        class="pdf-book-tag"
+                        class:selected=is_selected
+                        on:click=move |_| selection.update(|s| {
+                            if s.tags.contains(&tag_name) {
+                                s.tags.retain(|t| t != &tag_name);
+                            } else {
+                                s.tags.push(tag_name.clone());
+                            }
+                        })
+                    >
+                        { tag }
+                    </button>
+                }
+            })
+            .collect_view()
+    };
+
+    let recipe_elems = move || {
+        let mut recipes = match all_recipes_light.get() {
+            Some(Ok(recipes)) => recipes,
+            _ => vec![],
+        };
+        recipes.sort_by_key(|r| r.name.to_lowercase());
+        recipes
+            .into_iter()
+            .map(|recipe| {
+                let id = recipe.id;
+                view! {
+                    <li class="pdf-book-recipe">
+                        <label>
+                            <input
+                                type="checkbox"
+                                prop:checked=move || selection.read().recipe_ids.contains(&id)
+                                on:change=move |ev| {
+                                    let checked = event_target_checked(&ev);
+                                    selection.update(|s| {
+                                        s.recipe_ids.retain(|i| *i != id);
+                                        if checked {
+                                            s.recipe_ids.push(id);
+                                        }
+                                    });
+                                }
+                            />
+                            { recipe.name }
+                        </label>
+                    </li>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <details class="pdf-book">
+            <summary class="pdf-book-title">"PDF Cook Book"</summary>
+
+            <label class="pdf-book-label">
+                "Title"
+                <input
+                    id="text-input"
+                    type="text"
+                    prop:value=move || selection.read().title.clone()
+                    on:input=move |ev| selection.update(|s| s.title = event_target_value(&ev))
+                />
+            </label>
+
+            <p class="pdf-book-label">"Recipes with the tags"</p>
+            <div class="pdf-book-tags">
+                <Transition fallback=|| ()>
+                    { tag_elems }
+                </Transition>
+            </div>
+
+            <p class="pdf-book-label">"And these recipes"</p>
+            <ul class="pdf-book-recipes">
+                <Transition fallback=|| ()>
+                    { recipe_elems }
+                </Transition>
+            </ul>
+
+            <Transition fallback=|| ()>
+                <p class="pdf-book-info">
+                    {move || if selection.read().is_everything() {
+                        format!("All {} recipes", recipes_count())
+                    } else {
+                        format!("{} recipes", recipes_count())
+                    }}
+                </p>
+            </Transition>
+
+            <a
+                class="pdf-book-button"
+                href=move || selection.read().url()
+            >
+                "Make the PDF"
+            </a>
+        </details>
+    }
+}
@@ -13,6 +13,63 @@ pub mod ssr {
     pub async fn db() -> Result<SqliteConnection, ServerFnError> {
         Ok(SqliteConnection::connect("sqlite:cook-book.db").await?)
     }
+
+    use crate::app::components::recipe::*;
+
+    /// Every recipe, sorted by name
+    pub async fn all_recipes(conn: &mut SqliteConnection) -> Result<Vec<Recipe>, ServerFnError> {
+        use futures::TryStreamExt;
+
+        let mut recipes: Vec<Recipe> = vec![];
+        let mut rows = sqlx::query_as::<_, DbRowRecipe>("SELECT * FROM recipes").fetch(conn);
+        while let Some(row) = rows.try_next().await? {
+            let id = row.id;
+            recipes.push(row.to_json_recipe()?.to_recipe(id));
+        }
+        recipes.sort_by_key(|recipe| recipe.name.to_lowercase());
+        Ok(recipes)
+    }
+
+    /// Replace the content of a recipe, its ID and images stay
+    pub async fn update_recipe(conn: &mut SqliteConnection, id: u16, recipe: Recipe) -> Result<sqlx::sqlite::SqliteQueryResult, sqlx::Error> {
+        let string_name =           recipe.name;
+        let string_tags =           serde_json::to_string(&JsonRecipeTags::from_recipe_tags(recipe.tags)).expect("to serialize JsonRecipeTags into String");
+        let string_ingredients =    serde_json::to_string(&JsonRecipeIngredients::from_recipe_ingredients(recipe.ingredients)).expect("to serialize JsonRecipeIngredients into String");
+        let string_instructions =   serde_json::to_string(&JsonRecipeInstructions::from_recipe_instructions(recipe.instructions)).expect("to serialize JsonRecipeInstructions into String");
+        let string_notes =          serde_json::to_string(&JsonRecipeNotes::from_recipe_notes(recipe.notes)).expect("to serialize JsonRecipeNotes into String");
+        let string_source =         serde_json::to_string(&JsonRecipeSource::from_recipe_source(recipe.source)).expect("to serialize JsonRecipeSource into String");
+
+        sqlx::query( "UPDATE recipes SET recipe_name = $1, recipe_tags = $2, recipe_ingredients = $3, recipe_instructions = $4, recipe_notes = $5, recipe_source = $6 WHERE id = $7;" )
+            .bind(string_name)
+            .bind(string_tags)
+            .bind(string_ingredients)
+            .bind(string_instructions)
+            .bind(string_notes)
+            .bind(string_source)
+            .bind(id)
+            .execute(conn)
+            .await
+    }
+
+    /// Insert a new recipe, its ID is set by the database
+    pub async fn insert_recipe(conn: &mut SqliteConnection, recipe: Recipe) -> Result<sqlx::sqlite::SqliteQueryResult, sqlx::Error> {
+        let string_name =           recipe.name;
+        let string_tags =           serde_json::to_string(&JsonRecipeTags::from_recipe_tags(recipe.tags)).expect("to serialize JsonRecipeTags into String");
+        let string_ingredients =    serde_json::to_string(&JsonRecipeIngredients::from_recipe_ingredients(recipe.ingredients)).expect("to serialize JsonRecipeIngredients into String");
+        let string_instructions =   serde_json::to_string(&JsonRecipeInstructions::from_recipe_instructions(recipe.instructions)).expect("to serialize JsonRecipeInstructions into String");
+        let string_notes =          serde_json::to_string(&JsonRecipeNotes::from_recipe_notes(recipe.notes)).expect("to serialize JsonRecipeNotes into String");
+        let string_source =         serde_json::to_string(&JsonRecipeSource::from_recipe_source(recipe.source)).expect("to serialize JsonRecipeSource into String");
+
+        sqlx::query("INSERT INTO recipes (recipe_name, recipe_tags, recipe_ingredients, recipe_instructions, recipe_notes, recipe_source) VALUES ($1, $2, $3, $4, $5, $6)")
+            .bind(string_name)
+            .bind(string_tags)
+            .bind(string_ingredients)
+            .bind(string_instructions)
+            .bind(string_notes)
+            .bind(string_source)
+            .execute(conn)
+            .await
+    }
 }
 
 #[allow(dead_code)]
@@ -31,6 +88,7 @@ pub async fn recipe_function(
 
     match &recipe_action_desc {
         RecipeActionDescriptor::Add(r) => log!("Action received: ADD -> {:?}", r.name),
+        RecipeActionDescriptor::AddMany(r) => log!("Action received: ADD MANY -> {:?} recipes", r.len()),
         RecipeActionDescriptor::Save(r) => log!("Action received: SAVE -> {:?}", r.name),
         RecipeActionDescriptor::Delete(i) => log!("Action received: DELETE -> id: {:?}", i),
         RecipeActionDescriptor::Duplicate(i) => log!("Action received: DUPLICATE -> id: {:?}", i),
@@ -41,52 +99,39 @@ pub async fn recipe_function(
     match recipe_action_desc {
 
         RecipeActionDescriptor::Add(recipe) => {
-
-            let string_name =           recipe.name;
-            let string_tags =           serde_json::to_string(&JsonRecipeTags::from_recipe_tags(recipe.tags)).expect("to serialize JsonRecipeTags into String");
-            let string_ingredients =    serde_json::to_string(&JsonRecipeIngredients::from_recipe_ingredients(recipe.ingredients)).expect("to serialize JsonRecipeIngredients into String");
-            let string_instructions =   serde_json::to_string(&JsonRecipeInstructions::from_recipe_instructions(recipe.instructions)).expect("to serialize JsonRecipeInstructions into String");
-            let string_notes =          serde_json::to_string(&JsonRecipeNotes::from_recipe_notes(recipe.notes)).expect("to serialize JsonRecipeNotes into String");
-
-            match sqlx::query("INSERT INTO recipes (recipe_name, recipe_tags, recipe_ingredients, recipe_instructions, recipe_notes) VALUES ($1, $2, $3, $4, $5)")
-                .bind(string_name.clone())
-                .bind(string_tags.clone())
-                .bind(string_ingredients.clone())
-                .bind(string_instructions.clone())
-                .bind(string_notes.clone())
-                .execute(&mut conn)
-                .await
-            {
+            let recipe_name = recipe.name.clone();
+            match insert_recipe(&mut conn, recipe).await {
                 Ok(_row) => {
-                    log!("\nThe Recipe: {:?} was ADDED Successfully!\n\n", string_name);
+                    log!("\nThe Recipe: {:?} was ADDED Successfully!\n\n", recipe_name);
                     Ok(())
                 },
                 Err(e) => Err(ServerFnError::ServerError(e.to_string())),
             }
         },
 
+        RecipeActionDescriptor::AddMany(recipes) => {
+            // All recipes are added, or none
+            let mut tx = conn.begin().await?;
+            let recipes_count = recipes.len();
+            for recipe in recipes {
+                let recipe_name = recipe.name.clone();
+                if let Err(e) = insert_recipe(&mut tx, recipe).await {
+                    error!("ERROR: Could not add the recipe {:?}: {:?}", recipe_name, e.to_string());
+                    return Err(ServerFnError::ServerError(format!("Could not add {}: {}", recipe_name, e)));
+                }
+            }
+            tx.commit().await?;
+            log!("\n{:?} Recipes were ADDED Successfully!\n\n", recipes_count);
+            Ok(())
+        },
+
         RecipeActionDescriptor::Save(recipe) => {
 
-            let id: Option<u16> =       recipe.id;
-            let string_name =           recipe.name;
-            let string_tags =           serde_json::to_string(&JsonRecipeTags::from_recipe_tags(recipe.tags)).expect("to serialize JsonRecipeTags into String");
-            let string_ingredients =    serde_json::to_string(&JsonRecipeIngredients::from_recipe_ingredients(recipe.ingredients)).expect("to serialize JsonRecipeIngredients into String");
-            let string_instructions =   serde_json::to_string(&JsonRecipeInstructions::from_recipe_instructions(recipe.instructions)).expect("to serialize JsonRecipeInstructions into String");
-            let string_notes =          serde_json::to_string(&JsonRecipeNotes::from_recipe_notes(recipe.notes)).expect("to serialize JsonRecipeNotes into String");
-
-            if let Some(id) = id {
-                match sqlx::query( "UPDATE recipes SET recipe_name = $1, recipe_tags = $2, recipe_ingredients = $3, recipe_instructions = $4, recipe_notes = $5 WHERE id = $6;" )
-                    .bind(string_name.clone())
-                    .bind(string_tags.clone())
-                    .bind(string_ingredients.clone())
-                    .bind(string_instructions.clone())
-                    .bind(string_notes.clone())
-                    .bind(id.clone())
-                    .execute(&mut conn)
-                    .await
-                {
+            if let Some(id) = recipe.id {
+                let recipe_name = recipe.name.clone();
+                match update_recipe(&mut conn, id, recipe).await {
                     Ok(_row) => {
-                        log!("\nThe Recipe: {:?} was ADDED Successfully!\n", string_name);
+                        log!("\nThe Recipe: {:?} was ADDED Successfully!\n", recipe_name);
                         Ok(())
                     },
                     Err(e) => Err(ServerFnError::ServerError(e.to_string())),
@@ -98,12 +143,19 @@ pub async fn recipe_function(
 
         RecipeActionDescriptor::Delete(id) => { 
 
+            use crate::app::components::recipe_images::ssr::{recipe_image_files, remove_image_files};
+
+            // The image rows are deleted along with the recipe, but not the files:
+            // they are removed once the recipe is gone
+            let images = recipe_image_files(&mut conn, id).await?;
+
             match sqlx::query("DELETE FROM recipes WHERE id = $1")
                 .bind(id)
                 .execute(&mut conn)
                 .await
             {
                 Ok(_)   => {
+                    images.iter().for_each(|image| remove_image_files(image.id, &image.mime_type));
                     log!("The Recipe with ID :\n {:?} \n was DELETED successfully", id);
                     Ok(())
                 },
@@ -115,8 +167,8 @@ pub async fn recipe_function(
 
         RecipeActionDescriptor::Duplicate(id) => {
             match sqlx::query(
-                "INSERT INTO recipes (recipe_name, recipe_tags, recipe_ingredients, recipe_instructions, recipe_notes)
-                SELECT recipe_name, recipe_tags, recipe_ingredients, recipe_instructions, recipe_notes
+                "INSERT INTO recipes (recipe_name, recipe_tags, recipe_ingredients, recipe_instructions, recipe_notes, recipe_source)
+                SELECT recipe_name, recipe_tags, recipe_ingredients, recipe_instructions, recipe_notes, recipe_source
                 FROM recipes
                 WHERE id = $1;"
             )
@@ -148,7 +200,9 @@ pub async fn get_all_recipes_light() -> Result<Vec<RecipeLight>, ServerFnError>
 
     let mut all_recipe_light: Vec<RecipeLight> = vec![];
     let mut rows = sqlx::query_as::<_, DbRowRecipeLight>(
-        "SELECT id, recipe_name, recipe_tags, recipe_ingredients FROM recipes",
+        "SELECT id, recipe_name, recipe_tags, recipe_ingredients, recipe_source,
+            (SELECT i.id FROM recipe_images i WHERE i.recipe_id = recipes.id ORDER BY i.position, i.id LIMIT 1) AS image_id
+        FROM recipes",
     )
     .fetch(&mut conn);
 
@@ -157,14 +211,19 @@ pub async fn get_all_recipes_light() -> Result<Vec<RecipeLight>, ServerFnError>
         let recipe_name: String = row.recipe_name;
         let recipe_tags: Option<Vec<RecipeTag>> =
             serde_json::from_str::<JsonRecipeTags>(&row.recipe_tags)?.to_recipe_tags();
-        let recipe_ingredients: Option<Vec<RecipeIngredient>> =
+        let recipe_ingredients: Option<Vec<RecipeIngredient>> = RecipeIngredientGroup::flatten(
             serde_json::from_str::<JsonRecipeIngredients>(&row.recipe_ingredients)?
-                .to_recipe_ingredients();
+                .to_recipe_ingredients()
+        );
+        let recipe_source: Option<RecipeSource> =
+            serde_json::from_str::<JsonRecipeSource>(&row.recipe_source)?.to_recipe_source();
         let recipe_light: RecipeLight = RecipeLight {
             id: row.id,
             name: recipe_name,
             tags: recipe_tags,
             ingredients: recipe_ingredients,
+            image_id: row.image_id,
+            source: recipe_source,
         };
 
         all_recipe_light.push(recipe_light);
@@ -194,16 +253,8 @@ pub async fn get_recipe_by_id(recipe_id: u16) -> Result<Recipe, ServerFnError> {
         .fetch_one(&mut conn)
         .await?;
 
-    let json_recipe = JsonRecipe {
-        name: recipe_row.recipe_name,
-        tags: serde_json::from_str::<JsonRecipeTags>(&recipe_row.recipe_tags)?,
-        ingredients: serde_json::from_str::<JsonRecipeIngredients>(&recipe_row.recipe_ingredients)?,
-        instructions: serde_json::from_str::<JsonRecipeInstructions>(
-            &recipe_row.recipe_instructions,
-        )?,
-        notes: serde_json::from_str::<JsonRecipeNotes>(&recipe_row.recipe_notes)?,
-    };
-    let recipe = json_recipe.to_recipe(recipe_row.id);
+    let recipe_id_row = recipe_row.id;
+    let recipe = recipe_row.to_json_recipe()?.to_recipe(recipe_id_row);
 
     log!("Recipe from id: {:?} fetched Successfully.", recipe_id);
 
@@ -252,94 +303,55 @@ pub async fn get_recipe_id_by_name(name: String) -> Result<Option<u16>, ServerFn
 }
 
 #[server]
-pub async fn get_all_recipes_as_json_string() -> Result<String, ServerFnError> {
+pub async fn get_recipe_links(recipe_id: u16) -> Result<RecipeLinks, ServerFnError> {
     use self::ssr::*;
 
-    let mut conn = db().await?;
-
     // fake API delay
     if FAKE_API_DELAY {
         std::thread::sleep(std::time::Duration::from_millis(1250));
     }
 
-    // Fetch all
+    let mut conn = db().await?;
+
+    // Links are written by name, so every recipe is needed to resolve them
     let mut rows = sqlx::query_as::<_, DbRowRecipe>("SELECT * FROM recipes").fetch(&mut conn);
 
     use futures::TryStreamExt;
-    let mut all_recipes_json = JsonRecipeCollection(vec![]);
+    let mut all_recipes: Vec<Recipe> = vec![];
     while let Some(row) = rows.try_next().await? {
-        let json_recipe = JsonRecipe {
-            name: row.recipe_name,
-            tags: serde_json::from_str::<JsonRecipeTags>(&row.recipe_tags)?,
-            ingredients: serde_json::from_str::<JsonRecipeIngredients>(&row.recipe_ingredients)?,
-            instructions: serde_json::from_str::<JsonRecipeInstructions>(&row.recipe_instructions)?,
-            notes: serde_json::from_str::<JsonRecipeNotes>(&row.recipe_notes)?,
-        };
-        all_recipes_json.0.push(json_recipe);
-    }
-
-    // Sort recipes alphabetically
-    all_recipes_json.0.sort_by_key(|r| r.name.to_lowercase());
-
-    // Turn into a String
-    let out: String = serde_json::to_string_pretty(&all_recipes_json)?;
-
-    Ok(out)
-}
-
-#[server]
-pub async fn apply_json_save(save: String) -> Result<(), ServerFnError> {
-    use self::ssr::*;
-
-    // fake API delay
-    if FAKE_API_DELAY {
-        std::thread::sleep(std::time::Duration::from_millis(1250));
+        let id = row.id;
+        all_recipes.push(row.to_json_recipe()?.to_recipe(id));
     }
 
-    let save_json = serde_json::from_str::<JsonRecipeCollection>(&save)?;
-
-    let mut conn = db().await?;
-
-    let _ = sqlx::query("DELETE FROM recipes;")
-        .execute(&mut conn)
-        .await?;
-
-    for recipe in save_json.0 {
-        let string_name = recipe.name;
-        let string_tags = serde_json::to_string(&JsonRecipeTags::from_recipe_tags(
-            recipe.tags.to_recipe_tags(),
-        ))
-        .expect("to serialize JsonRecipeTags into String");
-        let string_ingredients =
-            serde_json::to_string(&JsonRecipeIngredients::from_recipe_ingredients(
-                recipe.ingredients.to_recipe_ingredients(),
-            ))
-            .expect("to serialize JsonRecipeIngredients into String");
-        let string_instructions =
-            serde_json::to_string(&JsonRecipeInstructions::from_recipe_instructions(
-                recipe.instructions.to_recipe_instructions(),
-            ))
-            .expect("to serialize JsonRecipeInstructions into String");
-        let string_notes = serde_json::to_string(&JsonRecipeNotes::from_recipe_notes(
-            recipe.notes.to_recipe_notes(),
-        ))
-        .expect("to serialize JsonRecipeNotes into String");
-
-        match sqlx::query(
-            "INSERT INTO recipes (recipe_name, recipe_tags, recipe_ingredients, recipe_instructions, recipe_notes) VALUES ($1, $2, $3, $4, $5);"
-        )
-            .bind(string_name.clone())
-            .bind(string_tags.clone())
-            .bind(string_ingredients.clone())
-            .bind(string_instructions.clone())
-            .bind(string_notes.clone())
-            .execute(&mut conn)
-            .await
-        {
-            Ok(_) => log!("Recipe: {:?}  was added succesfully !", string_name),
-            Err(e) => return Err(ServerFnError::ServerError(e.to_string())),
-        }
-    }
+    let Some(recipe) = all_recipes.iter().find(|r| r.id == Some(recipe_id)) else {
+        return Err(ServerFnError::ServerError(format!("No recipe with ID: {}", recipe_id)));
+    };
 
-    Ok(())
+    // Referenced recipes
+    let linked: Vec<(String, u16)> = recipe
+        .linked_recipe_names()
+        .into_iter()
+        .filter_map(|name| {
+            all_recipes
+                .iter()
+                .find(|r| RecipeLinks::same_name(&r.name, &name))
+                .and_then(|r| r.id)
+                .map(|id| (name, id))
+        })
+        .collect();
+
+    // Recipes referencing this one
+    let mut used_in: Vec<(u16, String)> = all_recipes
+        .iter()
+        .filter(|r| r.id != Some(recipe_id))
+        .filter(|r| {
+            r.linked_recipe_names()
+                .iter()
+                .any(|name| RecipeLinks::same_name(name, &recipe.name))
+        })
+        .filter_map(|r| r.id.map(|id| (id, r.name.clone())))
+        .collect();
+    used_in.sort_by_key(|(_, name)| name.to_lowercase());
+
+    Ok(RecipeLinks { linked, used_in })
 }
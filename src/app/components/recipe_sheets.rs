@@ -1,8 +1,15 @@
+use crate::app::components::collections::CollectionMembership;
+use crate::app::components::cook_log::{CookLogList, CookStats};
+use crate::app::components::cooking_timers::{extract_durations, TimerLink};
+use crate::app::components::meal_planner::AddToMealPlan;
+use crate::app::components::recipe_images::{EditableRecipeImages, RecipeHeroImage, RecipeImageSize};
+use crate::app::components::recipe_server_functions::get_recipe_links;
+use crate::app::components::schema_org::RecipeJsonLd;
 use crate::app::elements::popups::ServerWarningPopup;
-use crate::app::{IsPageDirtySignal, LoginCheckResource, PageColor, SelectedTagsRwSignal};
+use crate::app::{CookStatsResource, IsPageDirtySignal, LoginCheckResource, PageColor, SelectedTagsRwSignal};
 use crate::app::{
     elements::recipe_elements::*, Recipe, RecipeActionDescriptor, RecipeEntry,
-    RecipeEntryType, RecipeIngredient, RecipeInstruction, RecipeLight, RecipeNote,
+    RecipeEntryType, RecipeIngredient, RecipeIngredientGroup, RecipeInstruction, RecipeLight, RecipeLinks, RecipeNote, RecipeSource,
     RecipeServerAction, RecipeTag, ThemeColor,
 };
 use leptos::ev::MouseEvent;
@@ -22,11 +29,16 @@ pub fn RecipeCard(recipe_light: RecipeLight, color: ThemeColor) -> impl IntoView
         .expect("To find RecipeServerAction in context.")
         .0;
 
+    // Times cooked
+    let cook_stats = use_context::<CookStatsResource>()
+        .expect("To find CookStatsResource in context.")
+        .0;
+
     // Setup context with the recipe light getter
     let (recipe_id_getter, _) = signal(recipe_light.id);
 
-    let (recipe_id, recipe_name, recipe_tags) =
-        (recipe_light.id, recipe_light.name, recipe_light.tags);
+    let (recipe_id, recipe_name, recipe_tags, recipe_image_id) =
+        (recipe_light.id, recipe_light.name, recipe_light.tags, recipe_light.image_id);
 
 
     // Closure that updates PageColor in context
@@ -50,8 +62,14 @@ pub fn RecipeCard(recipe_light: RecipeLight, color: ThemeColor) -> impl IntoView
     };
 
     let is_menu_open = RwSignal::new(false);
+    // Add to the meal planner, inside the menu
+    let is_plan_open = RwSignal::new(false);
+    // Favorite and collections, inside the menu
+    let is_collections_open = RwSignal::new(false);
     let on_menu_click = move |ev: MouseEvent| {
         ev.stop_propagation();
+        is_plan_open.set(false);
+        is_collections_open.set(false);
         is_menu_open.update(|b| *b = !*b);
     };
 
@@ -77,9 +95,29 @@ pub fn RecipeCard(recipe_light: RecipeLight, color: ThemeColor) -> impl IntoView
                 })
                 .collect_view();
 
+            // Images are only served to logged in users
+            let thumbnail = recipe_image_id
+                .filter(|_| check_login_resource.get() == Some(true))
+                .map(|image_id| view! {
+                    <img
+                        class="recipe-card-thumbnail"
+                        src=RecipeImageSize::Thumbnail.url(image_id)
+                    />
+                });
+
+            let cooked = cook_stats
+                .get()
+                .and_then(|stats| stats.ok())
+                .and_then(|stats| CookStats::find(&stats, recipe_id).map(CookStats::label))
+                .map(|label| view! { <p class="recipe-light cooked">{ label }</p> });
+
             view! {
+                { thumbnail }
+
                 <h3 class="recipe-light name">{ recipe_name.clone() }</h3>
 
+                { cooked }
+
                 <ul class= "recipe-light">
                     {tag_list}
                 </ul>
@@ -172,6 +210,35 @@ pub fn RecipeCard(recipe_light: RecipeLight, color: ThemeColor) -> impl IntoView
                             }
                         >{"Duplicate"}</span>
 
+                        <span
+                            class= "sub-menu-option"
+                            class:selected=is_plan_open
+                            style=color.as_visible_color()
+                            on:click=move |ev| {
+                                ev.stop_propagation();
+                                is_collections_open.set(false);
+                                is_plan_open.update(|b| *b = !*b);
+                            }
+                        >{"Plan"}</span>
+
+                        <Show when=is_plan_open>
+                            <AddToMealPlan recipe_id=recipe_id />
+                        </Show>
+
+                        <span
+                            class= "sub-menu-option"
+                            class:selected=is_collections_open
+                            style=color.as_visible_color()
+                            on:click=move |ev| {
+                                ev.stop_propagation();
+                                is_plan_open.set(false);
+                                is_collections_open.update(|b| *b = !*b);
+                            }
+                        >{"Collections"}</span>
+
+                        <Show when=is_collections_open>
+                            <CollectionMembership recipe_id=recipe_id />
+                        </Show>
 
                     </Show>
                 </Transition>
@@ -236,60 +303,122 @@ pub fn RecipeSheet(recipe: Recipe) -> impl IntoView {
     }
 
 
+    // Recipe Action
+    let recipe_action = use_context::<RecipeServerAction>()
+        .expect("To find RecipeServerAction in context.")
+        .0;
+
+    // For the schema.org JSON-LD in the page head
+    let json_ld_recipe = recipe.clone();
+
+    // Links to other recipes, and recipes linking to this one
+    let recipe_id = recipe.id;
+    let links_resource = Resource::new(
+        move || recipe_action.version().get(),
+        move |_| async move {
+            match recipe_id {
+                Some(recipe_id) => get_recipe_links(recipe_id).await.unwrap_or_else(|e| {
+                    error!("Error fetching recipe links: {:?}", e.to_string());
+                    RecipeLinks::default()
+                }),
+                None => RecipeLinks::default(),
+            }
+        },
+    );
+    let links = move || links_resource.get().unwrap_or_default();
+
     let are_ingrs_empty: bool = recipe.ingredients.is_none();
 
-    let multiplier: RwSignal<f32> = RwSignal::new(1.0);
+    // A parent recipe passes its multiplier along to its sub-recipes
+    let initial_multiplier = leptos_router::hooks::use_query_map()
+        .get_untracked()
+        .get("mult")
+        .and_then(|m| m.parse::<f32>().ok())
+        .filter(|m| *m > 0.0)
+        .unwrap_or(1.0);
+    let multiplier: RwSignal<f32> = RwSignal::new(initial_multiplier);
 
     let ingredient_list = move || {
         let mult_value = multiplier.get();
+        let links = links();
         recipe
             .ingredients
             .clone()
             .unwrap_or(vec![])
             .into_iter()
-            .map(|ingredient| {
-                
-                let qty_unit: String = if mult_value != 1.0 {
-                    match extract_number(&ingredient.qty_unit) {
-                        Some((pre, num, suf)) => {
-                            let mult_num = num * mult_value;
-
-                            pre.to_string()
-                            + format!("{:.2}", mult_num)
-                                .trim_end_matches('0')
-                                .trim_end_matches('.')
-                            + suf
-                        },
-                        None => {
-                            log!("Could not parse f32 value.");
-                            ingredient.qty_unit
-                        },
-                    }
-                } else {
-                    ingredient.qty_unit
-                };
+            .flat_map(|group| {
+                // The group heading, if the group is named
+                let group_heading = (!group.name.trim().is_empty()).then(|| view! {
+                    <li class="display-recipe ingredients group-name">{ group.name }</li>
+                }.into_any());
+
+                let links = links.clone();
+                group_heading.into_iter().chain(group.ingredients.into_iter().map(move |ingredient| {
+                    let qty_unit: String = if mult_value != 1.0 {
+                        match extract_number(&ingredient.qty_unit) {
+                            Some((pre, num, suf)) => {
+                                let mult_num = num * mult_value;
+
+                                pre.to_string()
+                                + format!("{:.2}", mult_num)
+                                    .trim_end_matches('0')
+                                    .trim_end_matches('.')
+                                + suf
+                            },
+                            None => {
+                                log!("Could not parse f32 value.");
+                                ingredient.qty_unit
+                            },
+                        }
+                    } else {
+                        ingredient.qty_unit
+                    };
 
-                view! {
-                    <li class="display-recipe ingredients">
-                        <span class="display-recipe ingredients units">{ qty_unit }</span>
-                        <span class="display-recipe ingredients content">{ ingredient.content }</span>
-                    </li>
-                }.into_any()
+                    view! {
+                        <li class="display-recipe ingredients">
+                            <span class="display-recipe ingredients units">{ qty_unit }</span>
+                            <span class="display-recipe ingredients content">
+                                { linked_text_view(&ingredient.content, &links, "display-recipe", Some(mult_value)) }
+                            </span>
+                        </li>
+                    }.into_any()
+                }))
             })
             .collect_view()
     };
     
 
-    let are_insts_empty = recipe.instructions.content.is_empty();
+    let mut are_insts_empty: bool = false;
+    let instructions = recipe
+        .instructions
+        .unwrap_or_else(|| {
+            are_insts_empty = true;
+            vec![]
+        });
+    let timer_recipe_name = recipe.name.clone();
+    let instruction_list = move || {
+        let links = links();
+        instructions
+            .clone()
+            .into_iter()
+            .enumerate()
+            .map(|(index, step)| {
+                instruction_step_view(index, step, "display-recipe", &links, Some(&timer_recipe_name))
+            })
+            .collect_view()
+    };
 
     let mut are_notes_empty: bool = false;
-    let note_list = {
-        recipe
-            .notes
-            .unwrap_or_else(|| {
-                are_notes_empty = true;
-                vec![]
-            })
+    let notes = recipe
+        .notes
+        .unwrap_or_else(|| {
+            are_notes_empty = true;
+            vec![]
+        });
+    let note_list = move || {
+        let links = links();
+        notes
+            .clone()
             .into_iter()
             .map(|note| {
                 view! {
@@ -297,7 +426,32 @@ pub fn RecipeSheet(recipe: Recipe) -> impl IntoView {
                         style=move || { ThemeColor::random().as_border_main_color() }
                         class="display-recipe notes"
                     >
-                        <span class="display-recipe notes">{note.content}</span>
+                        <span class="display-recipe notes">
+                            { linked_text_view(&note.content, &links, "display-recipe", None) }
+                        </span>
+                    </li>
+                }
+            })
+            .collect_view()
+    };
+
+    let source = recipe.source;
+    let is_source_empty = source.is_none();
+    let source_view = source.map(|source| source_view(source, "display-recipe"));
+
+    let used_in_list = move || {
+        links()
+            .used_in
+            .into_iter()
+            .map(|(id, name)| {
+                view! {
+                    <li class="display-recipe used-in">
+                        <a
+                            class="display-recipe recipe-link"
+                            href=format!("/recipe/{}/display", id)
+                        >
+                            { name }
+                        </a>
                     </li>
                 }
             })
@@ -334,6 +488,8 @@ pub fn RecipeSheet(recipe: Recipe) -> impl IntoView {
     };
 
     view! {
+        <RecipeJsonLd recipe=json_ld_recipe />
+
         <RecipeMenu
             editable=false
             recipe_static_name=recipe.name
@@ -342,6 +498,8 @@ pub fn RecipeSheet(recipe: Recipe) -> impl IntoView {
 
         <div class="display-recipe-container">
 
+            { recipe.id.map(|recipe_id| view! { <RecipeHeroImage recipe_id=recipe_id /> }) }
+
             <Show
                 when=move || { !are_ingrs_empty }
             >
@@ -371,11 +529,9 @@ pub fn RecipeSheet(recipe: Recipe) -> impl IntoView {
                         style=move || { theme_color.get().as_visible_color() }
                         class="display-recipe instructions title"
                     >"Instructions"</h3>
-                    <ul class="display-recipe instructions">
-                        <li class="display-recipe instructions content">
-                            { recipe.instructions.content.clone() }
-                        </li>
-                    </ul>
+                    <ol class="display-recipe instructions">
+                        { instruction_list() }
+                    </ol>
                 </div>
             </Show>
 
@@ -388,11 +544,23 @@ pub fn RecipeSheet(recipe: Recipe) -> impl IntoView {
                         class="display-recipe notes title"
                     >"Notes"</h3>
                     <ul class="display-recipe notes">
-                        {note_list.clone()}
+                        { note_list() }
                     </ul>
                 </div>
             </Show>
 
+            <Show
+                when=move || { !is_source_empty }
+            >
+                <div class="display-recipe source container">
+                    <h3
+                        style=move || { theme_color.get().as_visible_color() }
+                        class="display-recipe source title"
+                    >"Source"</h3>
+                    { source_view.clone() }
+                </div>
+            </Show>
+
             <Show
                 when=move || { !are_tags_empty }
             >
@@ -407,6 +575,22 @@ pub fn RecipeSheet(recipe: Recipe) -> impl IntoView {
                 </div>
             </Show>
 
+            <Show
+                when=move || { !links().used_in.is_empty() }
+            >
+                <div class="display-recipe used-in container">
+                    <h3
+                        style=move || { theme_color.get().as_visible_color() }
+                        class="display-recipe used-in title"
+                    >"Used in"</h3>
+                    <ul class="display-recipe used-in">
+                        { used_in_list() }
+                    </ul>
+                </div>
+            </Show>
+
+            { recipe.id.map(|recipe_id| view! { <CookLogList recipe_id=recipe_id /> }) }
+
         </div>
     }
 }
@@ -415,46 +599,79 @@ pub fn RecipeSheet(recipe: Recipe) -> impl IntoView {
 pub fn PrintRecipeSheet(recipe: Recipe) -> impl IntoView {
 
     let mut are_ingrs_empty = false;
-    let ingredient_list = {
-        recipe
-            .ingredients
-            .unwrap_or_else(|| {
-                are_ingrs_empty = true;
-                vec![]
-            })
+    let ingredient_groups = recipe
+        .ingredients
+        .unwrap_or_else(|| {
+            are_ingrs_empty = true;
+            vec![]
+        });
+    let ingredient_list = move || {
+        ingredient_groups
+            .clone()
             .into_iter()
-            .map(|ingredient| {
-                view! {
-                    <li class="display-recipe ingredients">
-                        <span class="display-recipe ingredients units">{ingredient.qty_unit}</span>
-                        <span class="display-recipe ingredients content">{ingredient.content}</span>
-                    </li>
-                }
+            .flat_map(|group| {
+                // The group heading, if the group is named
+                let group_heading = (!group.name.trim().is_empty()).then(|| view! {
+                    <li class="print-recipe ingredients group-name">{ group.name }</li>
+                }.into_any());
+
+                group_heading.into_iter().chain(group.ingredients.into_iter().map(|ingredient| {
+                    view! {
+                        <li class="display-recipe ingredients">
+                            <span class="display-recipe ingredients units">{ingredient.qty_unit}</span>
+                            <span class="display-recipe ingredients content">
+                                { linked_text_view(&ingredient.content, &RecipeLinks::default(), "print-recipe", None) }
+                            </span>
+                        </li>
+                    }.into_any()
+                }))
             })
             .collect_view()
     };
 
-    let are_insts_empty = recipe.instructions.content.is_empty();
+    let mut are_insts_empty = false;
+    let instructions = recipe
+        .instructions
+        .unwrap_or_else(|| {
+            are_insts_empty = true;
+            vec![]
+        });
+    let instruction_list = move || {
+        instructions
+            .clone()
+            .into_iter()
+            .enumerate()
+            .map(|(index, step)| instruction_step_view(index, step, "print-recipe", &RecipeLinks::default(), None))
+            .collect_view()
+    };
 
     let mut are_notes_empty = false;
-    let note_list = {
-        recipe
-            .notes
-            .unwrap_or_else(|| {
-                are_notes_empty = true;
-                vec![]
-            })
+    let notes = recipe
+        .notes
+        .unwrap_or_else(|| {
+            are_notes_empty = true;
+            vec![]
+        });
+    let note_list = move || {
+        notes
+            .clone()
             .into_iter()
             .map(|note| {
                 view! {
                     <li class="display-recipe notes" >
-                        <span class="display-recipe notes">{note.content}</span>
+                        <span class="display-recipe notes">
+                            { linked_text_view(&note.content, &RecipeLinks::default(), "print-recipe", None) }
+                        </span>
                     </li>
                 }
             })
             .collect_view()
     };
 
+    let source = recipe.source;
+    let is_source_empty = source.is_none();
+    let source_view = source.map(|source| source_view(source, "print-recipe"));
+
     view! {
 
         <div class="print-recipe-container">
@@ -471,7 +688,7 @@ pub fn PrintRecipeSheet(recipe: Recipe) -> impl IntoView {
                         "Ingredients"
                     </h3>
                     <ul class="print-recipe ingredients">
-                        { ingredient_list.clone() }
+                        { ingredient_list() }
                     </ul>
                 </div>
             </Show>
@@ -483,11 +700,9 @@ pub fn PrintRecipeSheet(recipe: Recipe) -> impl IntoView {
                     <h3 class="print-recipe instructions title" >
                         "Instructions"
                     </h3>
-                    <ul class="print-recipe instructions">
-                        <li class="display-recipe instructions content">
-                            { recipe.instructions.content.clone() }
-                        </li>
-                    </ul>
+                    <ol class="print-recipe instructions">
+                        { instruction_list() }
+                    </ol>
                 </div>
             </Show>
 
@@ -499,11 +714,22 @@ pub fn PrintRecipeSheet(recipe: Recipe) -> impl IntoView {
                         "Notes"
                     </h3>
                     <ul class="print-recipe notes">
-                        { note_list.clone() }
+                        { note_list() }
                     </ul>
                 </div>
             </Show>
 
+            <Show
+                when=move || { !is_source_empty }
+            >
+                <div class="print-recipe source container">
+                    <h3 class="print-recipe source title" >
+                        "Source"
+                    </h3>
+                    { source_view.clone() }
+                </div>
+            </Show>
+
         </div>
 
         {move || {
@@ -517,17 +743,200 @@ pub fn PrintRecipeSheet(recipe: Recipe) -> impl IntoView {
     }
 }
 
+/// A numbered instruction step, with its optional title and duration.
+/// With a recipe name, the durations in the step start timers when tapped.
+fn instruction_step_view(
+    index: usize,
+    step: RecipeInstruction,
+    sheet_class: &'static str,
+    links: &RecipeLinks,
+    timer_recipe_name: Option<&str>,
+) -> impl IntoView {
+    let has_header = !step.title.trim().is_empty() || !step.duration.trim().is_empty();
+
+    let timer_label = timer_recipe_name.map(|name| {
+        if step.title.trim().is_empty() {
+            format!("{} · step {}", name, index + 1)
+        } else {
+            format!("{} · {}", name, step.title.replace(['[', ']'], ""))
+        }
+    });
+    let text_view = |text: &str| match &timer_label {
+        Some(label) => timed_text_view(text, links, sheet_class, label),
+        None => linked_text_view(text, links, sheet_class, None),
+    };
+
+    let step_header = has_header.then(|| view! {
+        <div class=format!("{sheet_class} instructions step-header")>
+            <span class=format!("{sheet_class} instructions step-title")>
+                { linked_text_view(&step.title, links, sheet_class, None) }
+            </span>
+            <span class=format!("{sheet_class} instructions step-duration")>
+                { text_view(&step.duration) }
+            </span>
+        </div>
+    });
+
+    view! {
+        <li class=format!("{sheet_class} instructions step")>
+            <span class=format!("{sheet_class} instructions step-number")>
+                { index + 1 }
+            </span>
+            <div class=format!("{sheet_class} instructions step-body")>
+                { step_header }
+                <span class=format!("{sheet_class} instructions content")>
+                    { text_view(&step.content) }
+                </span>
+            </div>
+        </li>
+    }
+}
+
+/// Author, book and page on one line, then the website.
+/// The printed sheet shows the full URL since it can't be clicked.
+fn source_view(source: RecipeSource, sheet_class: &'static str) -> impl IntoView + Clone {
+    let mut credits: Vec<String> = vec![];
+    if !source.author.is_empty() {
+        credits.push(source.author.clone());
+    }
+    if !source.book.is_empty() {
+        credits.push(source.book.clone());
+    }
+    if !source.page.is_empty() {
+        credits.push(format!("p. {}", source.page));
+    }
+    let credits = (!credits.is_empty()).then(|| view! {
+        <p class=format!("{sheet_class} source credits")>{ credits.join(", ") }</p>
+    });
+
+    let has_url = !source.url.is_empty();
+    let is_print = sheet_class == "print-recipe";
+    let safe_url = source.safe_url().filter(|_| !is_print).map(|url| url.to_string());
+    let url_text = (has_url && safe_url.is_none()).then(|| view! {
+        <p class=format!("{sheet_class} source url")>{ source.url.clone() }</p>
+    });
+    let url_link = safe_url.map(|url| view! {
+        <a
+            class=format!("{sheet_class} source url")
+            href=url
+            target="_blank"
+            rel="noopener noreferrer"
+        >
+            { source.url_host() }
+        </a>
+    });
+
+    view! {
+        <div class=format!("{sheet_class} source")>
+            { credits }
+            { url_text }
+            { url_link }
+        </div>
+    }
+}
+
+/// Text where every `[[Recipe Name]]` becomes a link to the referenced recipe.
+/// Unknown recipes are only shown by name.
+/// Ingredient lines pass their multiplier so the sub-recipe opens scaled.
+fn linked_text_view(
+    text: &str,
+    links: &RecipeLinks,
+    sheet_class: &'static str,
+    multiplier: Option<f32>,
+) -> Vec<AnyView> {
+    RecipeLinks::split_text(text)
+        .into_iter()
+        .map(|(part, is_link)| {
+            if is_link {
+                recipe_link_view(part, links, sheet_class, multiplier)
+            } else {
+                part.into_any()
+            }
+        })
+        .collect()
+}
+
+/// Like `linked_text_view`, where every duration also becomes a button starting a timer
+fn timed_text_view(
+    text: &str,
+    links: &RecipeLinks,
+    sheet_class: &'static str,
+    timer_label: &str,
+) -> Vec<AnyView> {
+    RecipeLinks::split_text(text)
+        .into_iter()
+        .flat_map(|(part, is_link)| {
+            if is_link {
+                return vec![recipe_link_view(part, links, sheet_class, None)];
+            }
+
+            let mut views = vec![];
+            let mut last_end = 0;
+            for duration in extract_durations(&part) {
+                views.push(part[last_end..duration.start].to_string().into_any());
+                views.push(view! {
+                    <TimerLink
+                        text=           part[duration.start..duration.end].to_string()
+                        label=          timer_label.to_string()
+                        seconds=        duration.seconds
+                        sheet_class=    sheet_class
+                    />
+                }.into_any());
+                last_end = duration.end;
+            }
+            views.push(part[last_end..].to_string().into_any());
+            views
+        })
+        .collect()
+}
+
+fn recipe_link_view(
+    part: String,
+    links: &RecipeLinks,
+    sheet_class: &'static str,
+    multiplier: Option<f32>,
+) -> AnyView {
+    match links.get_id(&part) {
+        Some(id) => {
+            let scale = multiplier.filter(|m| *m != 1.0);
+            let href = match scale {
+                Some(m) => format!("/recipe/{}/display?mult={}", id, m),
+                None => format!("/recipe/{}/display", id),
+            };
+            view! {
+                <a
+                    class=format!("{sheet_class} recipe-link")
+                    href=href
+                >
+                    { part }
+                    { scale.map(|m| view! {
+                        <span class=format!("{sheet_class} recipe-link-scale")>
+                            { format!(" ×{}", m) }
+                        </span>
+                    }) }
+                </a>
+            }.into_any()
+        },
+        None => view! {
+            <span class=format!("{sheet_class} recipe-link not-found")>{ part }</span>
+        }.into_any(),
+    }
+}
+
+/// Signals of an ingredient group being edited
+#[derive(Clone, Debug)]
+pub struct IngredientGroupSignals {
+    pub name: RwSignal<String>,
+    pub ingredients: RwSignal<Vec<(u16, ArcRwSignal<RecipeIngredient>)>>,
+}
+
 pub type RecipeSignals = RwSignal<(
     RwSignal<String>,
     RwSignal<Vec<(u16, ArcRwSignal<RecipeTag>)>>,
-    RwSignal<
-        Vec<(
-            u16,
-            ArcRwSignal<RecipeIngredient>,
-        )>,
-    >,
-    RwSignal<RecipeInstruction>,
+    RwSignal<Vec<(u16, IngredientGroupSignals)>>,
+    RwSignal<Vec<(u16, ArcRwSignal<RecipeInstruction>)>>,
     RwSignal<Vec<(u16, ArcRwSignal<RecipeNote>)>>,
+    RwSignal<RecipeSource>,
 )>;
 
 #[component]
@@ -544,9 +953,14 @@ pub fn EditableRecipeSheet(
     // If this is a new recipe, then add a default empty ingredient and set default name
     if is_new_recipe {
         recipe.ingredients = Some(vec![
-            RecipeIngredient {
-                qty_unit: "".to_string(),
-                content: "".to_string()
+            RecipeIngredientGroup {
+                name: "".to_string(),
+                ingredients: vec![
+                    RecipeIngredient {
+                        qty_unit: "".to_string(),
+                        content: "".to_string()
+                    }
+                ],
             }
         ]);
     }
@@ -558,15 +972,16 @@ pub fn EditableRecipeSheet(
 
     // Needed for move into closure view
     // for each category, make a Signal<Vec<(u16, (ReadSignal<T>, WriteSignal<T>))>>
-    // 0.tags, 1.ingredients, 2.instructions, 3.notes
+    // 0.name, 1.tags, 2.ingredients, 3.instructions, 4.notes, 5.source
     let recipe_signals: RecipeSignals = RwSignal::new((
         RwSignal::new(recipe.name),
         RwSignal::new(entries_into_signals(recipe.tags)),
-        RwSignal::new(entries_into_signals(recipe.ingredients)),
-        RwSignal::new(recipe.instructions),
+        RwSignal::new(ingredient_groups_into_signals(recipe.ingredients)),
+        RwSignal::new(entries_into_signals(recipe.instructions)),
         RwSignal::new(entries_into_signals(recipe.notes)),
+        RwSignal::new(recipe.source.unwrap_or_default()),
     ));
-    let (_, tags_signal, ingredients_signal, instructions_signal, notes_signal) =
+    let (_, tags_signal, ingredients_signal, instructions_signal, notes_signal, source_signal) =
         recipe_signals.get_untracked();
 
     // Is page Dirty Signal (to know if we need to save it before leaving)
@@ -581,16 +996,21 @@ pub fn EditableRecipeSheet(
                     // Subscribe to all events
                     let sigs = recipe_signals.read();
                     //Subscribe to signals
-                    let (_, t, ig, _, no) = (
+                    let (_, t, ig, is, no, _) = (
                         sigs.0.track(),
                         sigs.1.read(),
                         sigs.2.read(),
-                        sigs.3.track(),
-                        sigs.4.read()
+                        sigs.3.read(),
+                        sigs.4.read(),
+                        sigs.5.track(),
                     );
                     // Subscribe to every inner signal
                     t.iter().for_each(|s| { s.1.track(); });
-                    ig.iter().for_each(|s| { s.1.track(); });
+                    ig.iter().for_each(|(_, group)| {
+                        group.name.track();
+                        group.ingredients.read().iter().for_each(|s| { s.1.track(); });
+                    });
+                    is.iter().for_each(|s| { s.1.track(); });
                     no.iter().for_each(|s| { s.1.track(); });
                 },
                 // Make the page dirty whenever they change
@@ -617,15 +1037,14 @@ pub fn EditableRecipeSheet(
             {move || view! {
 
                 // Ingredients
-                <EditableEntryList
-                    rw_entries=         ingredients_signal
-                    entry_type=         RecipeEntryType::Ingredients
+                <EditableIngredientGroups
+                    rw_groups=          ingredients_signal
                     theme_color=        theme_color
                 />
 
                 // Instructions
-                <EditableInstructions
-                    entry_signal=       instructions_signal
+                <EditableEntryList
+                    rw_entries=         instructions_signal
                     entry_type=         RecipeEntryType::Instructions
                     theme_color=        theme_color
                 />
@@ -640,8 +1059,24 @@ pub fn EditableRecipeSheet(
                 // Tags
                 <EditableTags
                     rw_entries=         tags_signal
+                    rw_groups=          ingredients_signal
+                    recipe_id=          recipe.id
                     theme_color=        theme_color
                 />
+
+                // Source
+                <EditableSource
+                    rw_source=          source_signal
+                    theme_color=        theme_color
+                />
+
+                // Photos, only once the recipe exists
+                { recipe.id.map(|recipe_id| view! {
+                    <EditableRecipeImages
+                        recipe_id=          recipe_id
+                        theme_color=        theme_color
+                    />
+                }) }
             }}
 
         </div>
@@ -667,6 +1102,49 @@ fn entries_into_signals<T: RecipeEntry>(
     }
 }
 
+// helper function for EditableRecipeSheet
+// There is always at least one group, so that ingredients can be added
+fn ingredient_groups_into_signals(
+    groups: Option<Vec<RecipeIngredientGroup>>,
+) -> Vec<(u16, IngredientGroupSignals)> {
+    let mut groups = groups.unwrap_or_default();
+    if groups.is_empty() {
+        groups.push(RecipeIngredientGroup::default());
+    }
+    let length = groups.len() as u16;
+    groups
+        .into_iter()
+        .zip(0..length)
+        .map(|(group, id)| {
+            let group_signals = IngredientGroupSignals {
+                name: RwSignal::new(group.name),
+                ingredients: RwSignal::new(entries_into_signals(Some(group.ingredients))),
+            };
+            (id, group_signals)
+        })
+        .collect()
+}
+
+pub fn fetch_ingredient_groups_from_signals(
+    groups: Vec<(u16, IngredientGroupSignals)>,
+) -> Option<Vec<RecipeIngredientGroup>> {
+    let groups = groups
+        .iter()
+        .map(|(_, group)| RecipeIngredientGroup {
+            name: group.name.get_untracked().trim().to_string(),
+            ingredients: fetch_entries_from_signals(group.ingredients.get_untracked())
+                .unwrap_or_default(),
+        })
+        // Drop groups with no name and no ingredients
+        .filter(|group| !group.name.is_empty() || !group.ingredients.is_empty())
+        .collect::<Vec<RecipeIngredientGroup>>();
+    if !groups.is_empty() {
+        Some(groups)
+    } else {
+        None
+    }
+}
+
 pub fn fetch_entries_from_signals<T: RecipeEntry>(
     signals: Vec<(u16, ArcRwSignal<T>)>,
 ) -> Option<Vec<T>> {
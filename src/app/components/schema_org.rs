@@ -0,0 +1,675 @@
+use crate::app::components::recipe::*;
+use leptos::logging::*;
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use server_fn::codec::{MultipartData, MultipartFormData};
+use wasm_bindgen::JsCast;
+use web_sys::{FormData, HtmlFormElement, SubmitEvent};
+
+/// Maximum size of an imported web page (5 MB)
+pub const MAX_HTML_SIZE: usize = 5 * 1024 * 1024;
+
+/// A recipe read from a web page, to be reviewed before it is saved
+#[derive(Default, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportedRecipe {
+    pub recipe: Recipe,
+    // Images can only be attached to a saved recipe, so they are only listed
+    pub image_urls: Vec<String>,
+}
+
+/// A recipe as schema.org `Recipe` JSON-LD
+pub fn recipe_to_json_ld(recipe: &Recipe) -> serde_json::Value {
+    use serde_json::{json, Map, Value};
+
+    let mut schema = Map::new();
+    schema.insert("@context".to_string(), json!("https://schema.org"));
+    schema.insert("@type".to_string(), json!("Recipe"));
+    schema.insert("name".to_string(), json!(recipe.name));
+
+    // schema.org has no ingredient groups, the group name goes before each of its lines
+    let ingredients: Vec<String> = recipe
+        .ingredients
+        .iter()
+        .flatten()
+        .flat_map(|group| {
+            group.ingredients.iter().map(move |ingredient| {
+                let line = [ingredient.qty_unit.trim(), ingredient.content.trim()]
+                    .into_iter()
+                    .filter(|part| !part.is_empty())
+                    .collect::<Vec<&str>>()
+                    .join(" ");
+                let line = RecipeLinks::plain_text(&line);
+                if group.name.trim().is_empty() {
+                    line
+                } else {
+                    format!("{} ({})", line, group.name.trim())
+                }
+            })
+        })
+        .collect();
+    if !ingredients.is_empty() {
+        schema.insert("recipeIngredient".to_string(), json!(ingredients));
+    }
+
+    let steps: Vec<Value> = recipe
+        .instructions
+        .iter()
+        .flatten()
+        .map(|step| {
+            let mut how_to_step = Map::new();
+            how_to_step.insert("@type".to_string(), json!("HowToStep"));
+            if !step.title.trim().is_empty() {
+                how_to_step.insert("name".to_string(), json!(RecipeLinks::plain_text(step.title.trim())));
+            }
+            let mut text = RecipeLinks::plain_text(step.content.trim());
+            // Durations are free text, so they can't be an ISO 8601 "totalTime"
+            if !step.duration.trim().is_empty() {
+                text = format!("{} ({})", text, step.duration.trim());
+            }
+            how_to_step.insert("text".to_string(), json!(text));
+            Value::Object(how_to_step)
+        })
+        .collect();
+    if !steps.is_empty() {
+        schema.insert("recipeInstructions".to_string(), json!(steps));
+    }
+
+    if let Some(tags) = recipe.tags.as_ref().filter(|tags| !tags.is_empty()) {
+        let keywords: Vec<&str> = tags.iter().map(|t| t.name.as_str()).collect();
+        schema.insert("keywords".to_string(), json!(keywords.join(", ")));
+    }
+
+    let comments: Vec<Value> = recipe
+        .notes
+        .iter()
+        .flatten()
+        .filter(|note| !note.content.trim().is_empty())
+        .map(|note| json!({ "@type": "Comment", "text": RecipeLinks::plain_text(note.content.trim()) }))
+        .collect();
+    if !comments.is_empty() {
+        schema.insert("comment".to_string(), json!(comments));
+    }
+
+    if let Some(source) = &recipe.source {
+        if !source.author.is_empty() {
+            schema.insert("author".to_string(), json!({ "@type": "Person", "name": source.author }));
+        }
+        if !source.book.is_empty() {
+            let mut book = Map::new();
+            book.insert("@type".to_string(), json!("Book"));
+            book.insert("name".to_string(), json!(source.book));
+            if !source.page.is_empty() {
+                book.insert("pagination".to_string(), json!(source.page));
+            }
+            schema.insert("isBasedOn".to_string(), Value::Object(book));
+        } else if !source.url.is_empty() {
+            schema.insert("isBasedOn".to_string(), json!(source.url));
+        }
+    }
+
+    Value::Object(schema)
+}
+
+/// JSON-LD that is safe to put inside a `<script>` tag
+pub fn recipe_to_json_ld_script(recipe: &Recipe) -> String {
+    recipe_to_json_ld(recipe)
+        .to_string()
+        .replace('<', "\\u003c")
+}
+
+/// Puts the recipe JSON-LD in the head of the page, for other tools to read
+#[component]
+pub fn RecipeJsonLd(recipe: Recipe) -> impl IntoView {
+    use leptos_meta::Script;
+
+    view! {
+        <Script type_="application/ld+json">
+            { recipe_to_json_ld_script(&recipe) }
+        </Script>
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use super::*;
+    use scraper::{ElementRef, Html, Selector};
+    use serde_json::{Map, Value};
+
+    /// Find a schema.org `Recipe` in a page, as JSON-LD first, then as microdata
+    pub fn extract_recipe_from_html(html: &str) -> Option<ImportedRecipe> {
+        let document = Html::parse_document(html);
+
+        let schema_recipe = find_json_ld_recipe(&document).or_else(|| find_microdata_recipe(&document))?;
+
+        let mut imported = recipe_from_schema(&schema_recipe);
+
+        // Saved pages usually keep their address in the head
+        if let Some(source) = imported.recipe.source.as_mut() {
+            if source.url.is_empty() {
+                source.url = find_page_url(&document).unwrap_or_default();
+            }
+        } else if let Some(url) = find_page_url(&document) {
+            imported.recipe.source = Some(RecipeSource { url, ..Default::default() });
+        }
+
+        Some(imported)
+    }
+
+    fn find_page_url(document: &Html) -> Option<String> {
+        let canonical = Selector::parse(r#"link[rel="canonical"]"#).unwrap();
+        let og_url = Selector::parse(r#"meta[property="og:url"]"#).unwrap();
+        document
+            .select(&canonical)
+            .find_map(|el| el.value().attr("href"))
+            .or_else(|| document.select(&og_url).find_map(|el| el.value().attr("content")))
+            .map(|url| url.trim().to_string())
+            .filter(|url| url.starts_with("http"))
+    }
+
+    // JSON-LD -----
+
+    fn find_json_ld_recipe(document: &Html) -> Option<Value> {
+        let scripts = Selector::parse(r#"script[type="application/ld+json"]"#).unwrap();
+        document.select(&scripts).find_map(|script| {
+            let text = script.text().collect::<String>();
+            match serde_json::from_str::<Value>(text.trim()) {
+                Ok(value) => find_recipe_in_value(value),
+                Err(e) => {
+                    log!("Skipping unreadable JSON-LD: {:?}", e.to_string());
+                    None
+                }
+            }
+        })
+    }
+
+    // The recipe can be at the root, in a list, in a "@graph" or be the "mainEntity" of the page
+    fn find_recipe_in_value(value: Value) -> Option<Value> {
+        match value {
+            Value::Array(values) => values.into_iter().find_map(find_recipe_in_value),
+            Value::Object(mut object) => {
+                if object.get("@type").is_some_and(is_recipe_type) {
+                    return Some(Value::Object(object));
+                }
+                ["@graph", "mainEntity", "mainEntityOfPage"]
+                    .iter()
+                    .find_map(|key| object.remove(*key).and_then(find_recipe_in_value))
+            }
+            _ => None,
+        }
+    }
+
+    fn is_recipe_type(schema_type: &Value) -> bool {
+        match schema_type {
+            Value::String(t) => t == "Recipe" || t.ends_with("/Recipe") || t.ends_with(":Recipe"),
+            Value::Array(types) => types.iter().any(is_recipe_type),
+            _ => false,
+        }
+    }
+
+    // Microdata -----
+
+    // Microdata is turned into the same shape as JSON-LD, so that it is mapped only once
+    fn find_microdata_recipe(document: &Html) -> Option<Value> {
+        let items = Selector::parse("[itemscope][itemtype]").unwrap();
+        document
+            .select(&items)
+            .find(|el| el.value().attr("itemtype").is_some_and(|t| is_recipe_type(&Value::String(t.trim().to_string()))))
+            .map(microdata_item)
+    }
+
+    fn microdata_item(item: ElementRef) -> Value {
+        let mut map = Map::new();
+        if let Some(item_type) = item.value().attr("itemtype") {
+            let item_type = item_type.trim().rsplit('/').next().unwrap_or_default();
+            map.insert("@type".to_string(), Value::String(item_type.to_string()));
+        }
+        collect_microdata_props(item, &mut map);
+        Value::Object(map)
+    }
+
+    fn collect_microdata_props(parent: ElementRef, map: &mut Map<String, Value>) {
+        for child in parent.children().filter_map(ElementRef::wrap) {
+            let is_item = child.value().attr("itemscope").is_some();
+            if let Some(props) = child.value().attr("itemprop") {
+                let value = if is_item { microdata_item(child) } else { microdata_value(child) };
+                for prop in props.split_whitespace() {
+                    match map.remove(prop) {
+                        None => map.insert(prop.to_string(), value.clone()),
+                        Some(Value::Array(mut values)) => {
+                            values.push(value.clone());
+                            map.insert(prop.to_string(), Value::Array(values))
+                        }
+                        Some(previous) => map.insert(prop.to_string(), Value::Array(vec![previous, value.clone()])),
+                    };
+                }
+            }
+            // Properties of a nested item belong to that item
+            if !is_item {
+                collect_microdata_props(child, map);
+            }
+        }
+    }
+
+    fn microdata_value(el: ElementRef) -> Value {
+        let element = el.value();
+        let attr = match element.name() {
+            "meta" => element.attr("content"),
+            "img" | "audio" | "video" | "source" => element.attr("src"),
+            "a" | "link" | "area" => element.attr("href"),
+            "time" => element.attr("datetime"),
+            "data" | "meter" => element.attr("value"),
+            _ => element.attr("content"),
+        };
+        let text = match attr {
+            Some(attr) => attr.to_string(),
+            None => el.inner_html(),
+        };
+        Value::String(clean_text(&text))
+    }
+
+    // Mapping -----
+
+    pub fn recipe_from_schema(schema: &Value) -> ImportedRecipe {
+        let name = schema.get("name").map(value_to_text).unwrap_or_default();
+
+        // Ingredients
+        let ingredients: Vec<RecipeIngredient> = schema
+            .get("recipeIngredient")
+            .or_else(|| schema.get("ingredients"))
+            .map(value_to_list)
+            .unwrap_or_default()
+            .iter()
+            .map(|line| split_quantity(line))
+            .collect();
+
+        // Instructions
+        let instructions: Vec<RecipeInstruction> = schema
+            .get("recipeInstructions")
+            .map(instructions_from_value)
+            .unwrap_or_default();
+
+        // Notes: description, yield and times
+        let mut notes: Vec<RecipeNote> = vec![];
+        if let Some(description) = schema.get("description").map(value_to_text).filter(|d| !d.is_empty()) {
+            notes.push(RecipeNote { content: description });
+        }
+        // Sites often give both "8" and "8 servings"
+        let yields = schema.get("recipeYield").map(value_to_list).unwrap_or_default();
+        if let Some(recipe_yield) = yields
+            .iter()
+            .find(|y| y.parse::<f32>().is_err())
+            .or(yields.first())
+        {
+            notes.push(RecipeNote { content: format!("Yield: {}", recipe_yield) });
+        }
+        let times: Vec<String> = [("prepTime", "Prep time"), ("cookTime", "Cook time"), ("totalTime", "Total time")]
+            .iter()
+            .filter_map(|(key, label)| {
+                schema
+                    .get(*key)
+                    .map(value_to_text)
+                    .map(|time| format!("{}: {}", label, format_iso_duration(&time)))
+            })
+            .collect();
+        if !times.is_empty() {
+            notes.push(RecipeNote { content: times.join("\n") });
+        }
+
+        // Tags from keywords, category and cuisine
+        let mut tags: Vec<RecipeTag> = vec![];
+        for key in ["keywords", "recipeCategory", "recipeCuisine"] {
+            for tag in schema.get(key).map(value_to_list).unwrap_or_default() {
+                for tag in tag.split(',').map(|t| t.trim()).filter(|t| !t.is_empty()) {
+                    if !tags.iter().any(|t| t.name.to_lowercase() == tag.to_lowercase()) {
+                        tags.push(RecipeTag { name: tag.to_string() });
+                    }
+                }
+            }
+        }
+
+        // Source
+        let source = RecipeSource {
+            url: schema.get("url").map(value_to_text).filter(|url| url.starts_with("http")).unwrap_or_default(),
+            author: schema.get("author").map(value_to_list).unwrap_or_default().join(", "),
+            ..Default::default()
+        };
+
+        let image_urls: Vec<String> = schema
+            .get("image")
+            .map(value_to_list)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|url| url.starts_with("http://") || url.starts_with("https://"))
+            .collect();
+
+        ImportedRecipe {
+            recipe: Recipe {
+                id: None,
+                name,
+                tags: (!tags.is_empty()).then_some(tags),
+                ingredients: (!ingredients.is_empty()).then(|| vec![RecipeIngredientGroup {
+                    name: "".to_string(),
+                    ingredients,
+                }]),
+                instructions: (!instructions.is_empty()).then_some(instructions),
+                notes: (!notes.is_empty()).then_some(notes),
+                source: source.cleaned(),
+            },
+            image_urls,
+        }
+    }
+
+    // Instructions can be a text, a list of texts, of HowToStep, or of HowToSection
+    fn instructions_from_value(value: &Value) -> Vec<RecipeInstruction> {
+        match value {
+            Value::String(text) => RecipeInstruction::from_text_blob(&clean_text(text)),
+            Value::Array(values) => values.iter().flat_map(instructions_from_value).collect(),
+            Value::Object(object) => {
+                if let Some(section_steps) = object.get("itemListElement") {
+                    let mut steps = instructions_from_value(section_steps);
+                    // The section name goes on its first step
+                    let section_name = object.get("name").map(value_to_text).unwrap_or_default();
+                    if let Some(first) = steps.first_mut() {
+                        if first.title.is_empty() {
+                            first.title = section_name;
+                        }
+                    }
+                    steps
+                } else {
+                    let content = object.get("text").map(value_to_text).unwrap_or_default();
+                    let name = object.get("name").map(value_to_text).unwrap_or_default();
+                    let duration = object
+                        .get("totalTime")
+                        .or_else(|| object.get("performTime"))
+                        .map(|d| format_iso_duration(&value_to_text(d)))
+                        .unwrap_or_default();
+                    if content.is_empty() && name.is_empty() {
+                        return vec![];
+                    }
+                    // Sites often repeat the text as the name
+                    let (title, content) = if content.is_empty() {
+                        ("".to_string(), name)
+                    } else if content.starts_with(name.trim_end_matches("...").trim_end_matches('…')) {
+                        ("".to_string(), content)
+                    } else {
+                        (name, content)
+                    };
+                    vec![RecipeInstruction { title, content, duration }]
+                }
+            }
+            _ => vec![],
+        }
+    }
+
+    // Texts can be a string, a number, a list, or an object with a name or an url
+    fn value_to_list(value: &Value) -> Vec<String> {
+        match value {
+            Value::Array(values) => values.iter().flat_map(value_to_list).collect(),
+            Value::Null => vec![],
+            _ => Some(value_to_text(value)).filter(|t| !t.is_empty()).into_iter().collect(),
+        }
+    }
+
+    fn value_to_text(value: &Value) -> String {
+        match value {
+            Value::String(text) => clean_text(text),
+            Value::Number(number) => number.to_string(),
+            Value::Array(values) => values.first().map(value_to_text).unwrap_or_default(),
+            Value::Object(object) => ["name", "url", "text", "@id"]
+                .iter()
+                .find_map(|key| object.get(*key))
+                .map(value_to_text)
+                .unwrap_or_default(),
+            _ => "".to_string(),
+        }
+    }
+
+    // Remove markup and entities, and collapse white spaces (but keep line breaks)
+    fn clean_text(text: &str) -> String {
+        use regex::Regex;
+        // Block elements become line breaks, inline ones are dropped
+        let block_tags = Regex::new(r"(?i)<br\s*/?>|</?(?:p|div|li|ul|ol|h[1-6])(?:\s[^>]*)?>").unwrap();
+        let text = block_tags.replace_all(text, "\n");
+        let tags = Regex::new(r"<[^>]*>").unwrap();
+        let text = tags.replace_all(&text, "");
+        let text = decode_entities(&text);
+        text.lines()
+            .map(|line| line.split_whitespace().collect::<Vec<&str>>().join(" "))
+            .collect::<Vec<String>>()
+            .join("\n")
+            .trim()
+            .to_string()
+    }
+
+    fn decode_entities(text: &str) -> String {
+        use regex::Regex;
+        let entity = Regex::new(r"&(#[0-9]+|#[xX][0-9a-fA-F]+|[a-zA-Z]+);").unwrap();
+        entity
+            .replace_all(text, |caps: &regex::Captures| {
+                let code = &caps[1];
+                let decoded = if let Some(hex) = code.strip_prefix("#x").or_else(|| code.strip_prefix("#X")) {
+                    u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+                } else if let Some(dec) = code.strip_prefix('#') {
+                    dec.parse::<u32>().ok().and_then(char::from_u32)
+                } else {
+                    match code {
+                        "amp" => Some('&'),
+                        "lt" => Some('<'),
+                        "gt" => Some('>'),
+                        "quot" => Some('"'),
+                        "apos" => Some('\''),
+                        "nbsp" => Some(' '),
+                        "frac12" => Some('½'),
+                        "frac14" => Some('¼'),
+                        "frac34" => Some('¾'),
+                        "deg" => Some('°'),
+                        _ => None,
+                    }
+                };
+                decoded.map(|c| c.to_string()).unwrap_or_else(|| caps[0].to_string())
+            })
+            .to_string()
+    }
+
+    /// "PT1H30M" -> "1 h 30 min". Anything else is kept as it is.
+    pub fn format_iso_duration(duration: &str) -> String {
+        use regex::Regex;
+        let re = Regex::new(r"^P(?:(\d+)D)?(?:T(?:(\d+)H)?(?:(\d+)M)?(?:(\d+(?:\.\d+)?)S)?)?$").unwrap();
+        let Some(caps) = re.captures(duration.trim()) else {
+            return duration.to_string();
+        };
+        let mut parts: Vec<String> = vec![];
+        let mut minutes_total: u64 = 0;
+        for (index, unit) in [(1, "d"), (2, "h"), (3, "min")] {
+            if let Some(number) = caps.get(index).and_then(|m| m.as_str().parse::<u64>().ok()) {
+                // Some sites write "PT90M"
+                minutes_total += number * match unit { "d" => 1440, "h" => 60, _ => 1 };
+            }
+        }
+        let seconds = caps.get(4).and_then(|m| m.as_str().parse::<f32>().ok()).unwrap_or(0.0);
+        let (days, hours, minutes) = (minutes_total / 1440, (minutes_total % 1440) / 60, minutes_total % 60);
+        if days > 0 { parts.push(format!("{} d", days)); }
+        if hours > 0 { parts.push(format!("{} h", hours)); }
+        if minutes > 0 { parts.push(format!("{} min", minutes)); }
+        if seconds > 0.0 { parts.push(format!("{} s", seconds)); }
+        if parts.is_empty() { duration.to_string() } else { parts.join(" ") }
+    }
+
+    /// "200 g flour" -> ("200 g", "flour")
+    pub fn split_quantity(line: &str) -> RecipeIngredient {
+        use regex::Regex;
+        let re = Regex::new(concat!(
+            r"(?i)^\s*(",
+            // number, fraction or range: 2, 1.5, 1/2, 1 1/2, ½, 2-3
+            r"(?:\d+(?:[.,]\d+)?(?:\s*/\s*\d+)?(?:\s+\d+/\d+)?|[½¼¾⅓⅔⅛])(?:\s*[½¼¾⅓⅔⅛])?",
+            r"(?:\s*(?:-|–|to|à)\s*\d+(?:[.,]\d+)?)?",
+            // optional unit
+            r"(?:\s*(?:kg|g|mg|ml|cl|dl|l|oz|lbs?|pounds?|ounces?|cups?|tbsps?|tsps?|tablespoons?|teaspoons?",
+            r"|pinch(?:es)?|cloves?|cans?|sticks?|slices?|c\.\s*à\s*(?:soupe|café|s\.|c\.)|cuillères?\s*à\s*(?:soupe|café)|pincées?|gousses?|tranches?|sachets?)\b\.?)?",
+            r")\s+(?:of\s+|d'|de\s+)?(.+)$",
+        )).unwrap();
+        match re.captures(line) {
+            Some(caps) => RecipeIngredient {
+                qty_unit: caps[1].trim().to_string(),
+                content: caps[2].trim().to_string(),
+            },
+            None => RecipeIngredient {
+                qty_unit: "".to_string(),
+                content: line.trim().to_string(),
+            },
+        }
+    }
+}
+
+#[server(input = MultipartFormData)]
+pub async fn import_recipe_from_html(data: MultipartData) -> Result<ImportedRecipe, ServerFnError> {
+    use self::ssr::*;
+    use crate::app::components::auth::auth_server_functions::check_login;
+
+    if !check_login().await? {
+        return Err(ServerFnError::ServerError("Not logged in.".to_string()));
+    }
+
+    // Read the form fields, a saved page file or pasted HTML
+    let mut data = data.into_inner().expect("Expected multipart data on the server.");
+    let mut file_bytes: Vec<u8> = vec![];
+    let mut pasted_bytes: Vec<u8> = vec![];
+    while let Some(mut field) = data.next_field().await? {
+        // Both are read chunk by chunk, with the same size limit
+        let bytes = match field.name() {
+            Some("html_file") => &mut file_bytes,
+            Some("html_text") => &mut pasted_bytes,
+            _ => continue,
+        };
+        while let Some(chunk) = field.chunk().await? {
+            if bytes.len() + chunk.len() > MAX_HTML_SIZE {
+                return Err(ServerFnError::ServerError(format!(
+                    "Page is too big, the maximum size is {} MB.",
+                    MAX_HTML_SIZE / (1024 * 1024)
+                )));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+    }
+
+    let html = if !file_bytes.is_empty() {
+        String::from_utf8_lossy(&file_bytes).to_string()
+    } else {
+        String::from_utf8_lossy(&pasted_bytes).to_string()
+    };
+    if html.trim().is_empty() {
+        return Err(ServerFnError::ServerError("No page was given to import.".to_string()));
+    }
+
+    match extract_recipe_from_html(&html) {
+        Some(imported) => {
+            log!("Recipe {:?} was read from a web page.", imported.recipe.name);
+            Ok(imported)
+        }
+        None => Err(ServerFnError::ServerError(
+            "No schema.org recipe was found in this page.".to_string(),
+        )),
+    }
+}
+
+#[component]
+pub fn RecipeImportForm(imported_recipe: RwSignal<Option<ImportedRecipe>>) -> impl IntoView {
+    // FormData is not Send, so this action must stay local
+    let import_action = Action::new_local(|data: &FormData| import_recipe_from_html(data.clone().into()));
+    let import_pending = import_action.pending();
+    let import_value = import_action.value();
+
+    Effect::new(move |_| {
+        if let Some(Ok(imported)) = import_value.get() {
+            imported_recipe.set(Some(imported));
+        }
+    });
+
+    let on_submit = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        let form = ev
+            .target()
+            .expect("Expected the import form as event target.")
+            .unchecked_into::<HtmlFormElement>();
+        match FormData::new_with_form(&form) {
+            Ok(form_data) => {
+                import_action.dispatch_local(form_data);
+            }
+            Err(e) => error!("ERROR: Could not read the import form: {:?}", e),
+        }
+    };
+
+    // Only the addresses: nothing is loaded from the page's site until one is opened
+    let image_list = move || {
+        imported_recipe
+            .get()
+            .map(|imported| imported.image_urls)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|url| {
+                let href = url.clone();
+                view! {
+                <li class="recipe-import-image">
+                    <a class="recipe-import-image-url" href=href target="_blank" rel="noopener noreferrer">
+                        { url }
+                    </a>
+                </li>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <details class="recipe-import">
+            <summary class="recipe-import-title">"Import from a web page"</summary>
+
+            <form
+                class="recipe-import-form"
+                on:submit=on_submit
+            >
+                <label class="recipe-import-label">
+                    "Saved page"
+                    <input
+                        class="recipe-import-input"
+                        type="file"
+                        name="html_file"
+                        accept=".html,.htm,text/html"
+                    />
+                </label>
+                <textarea
+                    id="text-input"
+                    class="recipe-import-text"
+                    name="html_text"
+                    placeholder="...or paste the page source"
+                    rows="4"
+                ></textarea>
+                <button
+                    class="recipe-import-button"
+                    type="submit"
+                    disabled=import_pending
+                >
+                    { move || if import_pending.get() { "Importing..." } else { "Import" } }
+                </button>
+            </form>
+
+            {move || {
+                if let Some(Err(e)) = import_value.get() {
+                    view! { <p class="recipe-import-error">{e.to_string()}</p> }.into_any()
+                } else {
+                    ().into_any()
+                }
+            }}
+
+            <Show
+                when=move || imported_recipe.get().is_some_and(|imported| !imported.image_urls.is_empty())
+            >
+                <p class="recipe-import-info">
+                    "Photos found on the page, they can be uploaded once the recipe is saved:"
+                </p>
+                <ul class="recipe-import-images">
+                    { image_list }
+                </ul>
+            </Show>
+        </details>
+    }
+}
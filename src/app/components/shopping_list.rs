@@ -0,0 +1,677 @@
+use crate::app::{LoginCheckResource, RecipesLightResource, ShoppingListServerAction};
+use leptos::logging::*;
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// How often the list is checked for changes made on other devices
+const REFRESH_INTERVAL_MS: u64 = 3000;
+
+/// Where the lines are in the shop, in the order they are walked through
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Aisle {
+    Produce,
+    Bakery,
+    MeatFish,
+    DairyEggs,
+    Pantry,
+    Spices,
+    Frozen,
+    Drinks,
+    Other,
+}
+impl Aisle {
+    pub const ALL: [Aisle; 9] = [
+        Aisle::Produce,
+        Aisle::Bakery,
+        Aisle::MeatFish,
+        Aisle::DairyEggs,
+        Aisle::Pantry,
+        Aisle::Spices,
+        Aisle::Frozen,
+        Aisle::Drinks,
+        Aisle::Other,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Aisle::Produce => "produce",
+            Aisle::Bakery => "bakery",
+            Aisle::MeatFish => "meat_fish",
+            Aisle::DairyEggs => "dairy_eggs",
+            Aisle::Pantry => "pantry",
+            Aisle::Spices => "spices",
+            Aisle::Frozen => "frozen",
+            Aisle::Drinks => "drinks",
+            Aisle::Other => "other",
+        }
+    }
+    pub fn label(&self) -> &'static str {
+        match self {
+            Aisle::Produce => "Fruits & Vegetables",
+            Aisle::Bakery => "Bakery",
+            Aisle::MeatFish => "Meat & Fish",
+            Aisle::DairyEggs => "Dairy & Eggs",
+            Aisle::Pantry => "Pantry",
+            Aisle::Spices => "Spices & Condiments",
+            Aisle::Frozen => "Frozen",
+            Aisle::Drinks => "Drinks",
+            Aisle::Other => "Other",
+        }
+    }
+    // Singular words, see `normalize_ingredient_name`
+    fn keywords(&self) -> &'static [&'static str] {
+        match self {
+            Aisle::Produce => &[
+                "apple", "apricot", "avocado", "banana", "basil", "bean sprout", "beet", "berry", "broccoli", "cabbage",
+                "carrot", "cauliflower", "celery", "cherry", "chive", "coriander", "cilantro", "courgette", "cucumber",
+                "eggplant", "aubergine", "fennel", "garlic", "ginger", "grape", "herb", "kale", "leek", "lemon", "lettuce",
+                "lime", "mango", "melon", "mint", "mushroom", "onion", "orange", "parsley", "pea", "peach", "pear", "pepper",
+                "potato", "pumpkin", "radish", "raspberry", "salad", "shallot", "spinach", "squash", "strawberry",
+                "thyme", "tomato", "zucchini",
+            ],
+            Aisle::Bakery => &["bread", "baguette", "bun", "brioche", "croissant", "pita", "tortilla", "loaf"],
+            Aisle::MeatFish => &[
+                "bacon", "beef", "chicken", "chorizo", "cod", "duck", "fish", "ham", "lamb", "meat", "mince", "mussel",
+                "pork", "prawn", "salmon", "sausage", "shrimp", "steak", "tuna", "turkey", "veal",
+            ],
+            Aisle::DairyEggs => &[
+                "butter", "cheese", "cream", "egg", "feta", "milk", "mozzarella", "parmesan", "ricotta", "yogurt",
+                "yoghurt", "mascarpone",
+            ],
+            Aisle::Pantry => &[
+                "almond", "baking", "bean", "chickpea", "chocolate", "cocoa", "coconut", "couscous", "flour", "honey",
+                "lentil", "noodle", "nut", "oat", "oil", "pasta", "quinoa", "rice", "semolina", "stock", "broth", "sugar",
+                "syrup", "vinegar", "yeast",
+            ],
+            Aisle::Spices => &[
+                "cinnamon", "cumin", "curry", "ketchup", "mayonnaise", "mustard", "nutmeg", "oregano", "paprika", "salt",
+                "sauce", "spice", "turmeric", "vanilla", "chili", "chilli",
+            ],
+            Aisle::Frozen => &["ice cream", "sorbet"],
+            Aisle::Drinks => &["beer", "cider", "juice", "water", "wine", "rum", "coffee", "tea"],
+            Aisle::Other => &[],
+        }
+    }
+
+    /// The aisle of a normalized ingredient name, by its words
+    pub fn of(normalized_name: &str) -> Aisle {
+        let words: Vec<&str> = normalized_name.split_whitespace().collect();
+        if words.first() == Some(&"frozen") {
+            return Aisle::Frozen;
+        }
+        // "tomato sauce" is a sauce, the last words are the most telling
+        (0..words.len())
+            .rev()
+            .find_map(|start| {
+                let tail = words[start..].join(" ");
+                Aisle::ALL.into_iter().find(|aisle| {
+                    aisle
+                        .keywords()
+                        .iter()
+                        .any(|keyword| tail == *keyword || tail.starts_with(&format!("{} ", keyword)))
+                })
+            })
+            .unwrap_or(Aisle::Other)
+    }
+}
+impl std::str::FromStr for Aisle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Aisle::ALL
+            .into_iter()
+            .find(|aisle| aisle.as_str() == s)
+            .ok_or_else(|| format!("Unknown aisle: {}", s))
+    }
+}
+
+/// A line of the shopping list
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ShoppingListItem {
+    pub id: u32,
+    pub aisle: Aisle,
+    pub name: String,
+    // The total of every recipe, none when it was written without a quantity
+    pub quantity: Option<String>,
+    // The recipes the line comes from
+    pub recipe_names: Vec<String>,
+    // A recipe line that could not be read, kept as written
+    pub is_verbatim: bool,
+    pub checked: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ShoppingList {
+    pub id: u32,
+    pub title: String,
+    pub created_at: String,
+    // Increased on each change, to refresh the other devices
+    pub version: u32,
+    pub items: Vec<ShoppingListItem>,
+}
+
+/// What the list is made from
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum ShoppingListSource {
+    // (recipe ID, servings multiplier)
+    Recipes(Vec<(u16, f64)>),
+    // The meal plan of the week starting at this date, with the planned servings
+    Week(String),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum ShoppingListActionDescriptor {
+    // Replaces the current list
+    Generate(ShoppingListSource),
+    // With item ID
+    SetChecked(u32, bool),
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use super::*;
+    use crate::app::components::ingredient_units::*;
+    use crate::app::components::recipe::{Recipe, RecipeIngredientGroup};
+    use sqlx::SqliteConnection;
+
+    #[derive(sqlx::FromRow)]
+    pub struct DbRowShoppingList {
+        pub id: u32,
+        pub title: String,
+        pub created_at: String,
+        pub version: u32,
+    }
+
+    #[derive(sqlx::FromRow)]
+    pub struct DbRowShoppingListItem {
+        pub id: u32,
+        pub aisle: String,
+        pub item_name: String,
+        pub quantity: Option<String>,
+        pub recipe_names: String,
+        pub is_verbatim: bool,
+        pub checked: bool,
+    }
+    impl DbRowShoppingListItem {
+        pub fn to_item(self) -> Result<ShoppingListItem, ServerFnError> {
+            let aisle = match self.aisle.parse() {
+                Ok(aisle) => aisle,
+                Err(e) => return Err(ServerFnError::ServerError(e)),
+            };
+            Ok(ShoppingListItem {
+                id: self.id,
+                aisle,
+                name: self.item_name,
+                quantity: self.quantity,
+                recipe_names: serde_json::from_str(&self.recipe_names)?,
+                is_verbatim: self.is_verbatim,
+                checked: self.checked,
+            })
+        }
+    }
+
+    /// A line before it is stored
+    pub struct NewShoppingListItem {
+        pub aisle: Aisle,
+        pub name: String,
+        pub quantity: Option<String>,
+        pub recipe_names: Vec<String>,
+        pub is_verbatim: bool,
+    }
+
+    // Lines of the same ingredient that add up: same name, and quantities of the same dimension
+    struct IngredientTotal {
+        normalized_name: String,
+        // None for the lines written without a quantity
+        dimension: Option<Dimension>,
+        name: String,
+        quantities: Vec<Quantity>,
+        recipe_names: Vec<String>,
+    }
+
+    /// The ingredients of the recipes, with their multiplier, added up by ingredient and aisle
+    pub fn shopping_list_items(recipes: &[(Recipe, f64)]) -> Vec<NewShoppingListItem> {
+        let mut totals: Vec<IngredientTotal> = vec![];
+        let mut verbatim: Vec<NewShoppingListItem> = vec![];
+
+        for (recipe, multiplier) in recipes {
+            let ingredients = RecipeIngredientGroup::flatten(recipe.ingredients.clone()).unwrap_or_default();
+            for ingredient in ingredients {
+                let qty_unit = ingredient.qty_unit.trim();
+                let normalized_name = normalize_ingredient_name(&ingredient.content);
+                if normalized_name.is_empty() && qty_unit.is_empty() {
+                    continue;
+                }
+
+                let quantity = if qty_unit.is_empty() {
+                    None
+                } else {
+                    match Quantity::parse(qty_unit) {
+                        Some(quantity) => Some(quantity.scaled(*multiplier)),
+                        // Kept as written, with its recipe
+                        None => {
+                            verbatim.push(NewShoppingListItem {
+                                aisle: Aisle::of(&normalized_name),
+                                name: format!("{} {}", qty_unit, ingredient.content.trim()).trim().to_string(),
+                                quantity: None,
+                                recipe_names: vec![recipe.name.clone()],
+                                is_verbatim: true,
+                            });
+                            continue;
+                        }
+                    }
+                };
+
+                let dimension = quantity.as_ref().map(|quantity| quantity.unit.dimension.clone());
+                let total = match totals
+                    .iter_mut()
+                    .find(|total| total.normalized_name == normalized_name && total.dimension == dimension)
+                {
+                    Some(total) => total,
+                    None => {
+                        totals.push(IngredientTotal {
+                            normalized_name: normalized_name.clone(),
+                            dimension,
+                            name: ingredient_display_name(&ingredient.content),
+                            quantities: vec![],
+                            recipe_names: vec![],
+                        });
+                        totals.last_mut().expect("The total that was just added")
+                    }
+                };
+                total.quantities.extend(quantity);
+                if !total.recipe_names.contains(&recipe.name) {
+                    total.recipe_names.push(recipe.name.clone());
+                }
+            }
+        }
+
+        let mut items: Vec<NewShoppingListItem> = totals
+            .into_iter()
+            .map(|total| NewShoppingListItem {
+                aisle: Aisle::of(&total.normalized_name),
+                name: total.name,
+                quantity: Quantity::sum(&total.quantities).map(|quantity| quantity.to_string()),
+                recipe_names: total.recipe_names,
+                is_verbatim: false,
+            })
+            .chain(verbatim)
+            .collect();
+        items.sort_by_key(|item| {
+            let aisle_index = Aisle::ALL.iter().position(|aisle| *aisle == item.aisle);
+            (aisle_index, item.name.to_lowercase())
+        });
+        items
+    }
+
+    /// Servings a recipe is written for, from its notes, or the default servings of the planner
+    pub fn recipe_servings(recipe: &Recipe) -> f64 {
+        use crate::app::components::meal_planner::DEFAULT_SERVINGS;
+        use crate::app::components::recipe_export::servings_from_notes;
+
+        servings_from_notes(recipe)
+            .and_then(|servings| Quantity::parse(&servings))
+            .map(|quantity| quantity.amount)
+            .filter(|servings| *servings > 0.0)
+            .unwrap_or(DEFAULT_SERVINGS as f64)
+    }
+
+    /// The current list, there is only one at a time
+    pub async fn current_list(conn: &mut SqliteConnection) -> Result<Option<DbRowShoppingList>, ServerFnError> {
+        Ok(sqlx::query_as::<_, DbRowShoppingList>("SELECT id, title, created_at, version FROM shopping_lists ORDER BY id DESC LIMIT 1")
+            .fetch_optional(conn)
+            .await?)
+    }
+}
+
+/// The current shopping list, if one was made
+#[server]
+pub async fn get_shopping_list() -> Result<Option<ShoppingList>, ServerFnError> {
+    use self::ssr::*;
+    use crate::app::components::recipe_server_functions::ssr::db;
+
+    let mut conn = db().await?;
+    let Some(list) = current_list(&mut conn).await? else {
+        return Ok(None);
+    };
+    let items = sqlx::query_as::<_, DbRowShoppingListItem>(
+        "SELECT id, aisle, item_name, quantity, recipe_names, is_verbatim, checked
+        FROM shopping_list_items WHERE list_id = $1 ORDER BY position, id",
+    )
+    .bind(list.id)
+    .fetch_all(&mut conn)
+    .await?
+    .into_iter()
+    .map(DbRowShoppingListItem::to_item)
+    .collect::<Result<Vec<ShoppingListItem>, ServerFnError>>()?;
+
+    Ok(Some(ShoppingList {
+        id: list.id,
+        title: list.title,
+        created_at: list.created_at,
+        version: list.version,
+        items,
+    }))
+}
+
+/// (list ID, version) of the current list, cheap enough to be polled
+#[server]
+pub async fn get_shopping_list_version() -> Result<Option<(u32, u32)>, ServerFnError> {
+    use self::ssr::*;
+    use crate::app::components::recipe_server_functions::ssr::db;
+
+    let mut conn = db().await?;
+    Ok(current_list(&mut conn).await?.map(|list| (list.id, list.version)))
+}
+
+#[server]
+pub async fn shopping_list_function(action: ShoppingListActionDescriptor) -> Result<(), ServerFnError> {
+    use self::ssr::*;
+    use crate::app::components::auth::auth_server_functions::check_login;
+    use crate::app::components::meal_planner::{parse_plan_date, ssr::meal_plan_entries};
+    use crate::app::components::recipe_server_functions::ssr::{all_recipes, db};
+    use sqlx::Connection;
+
+    if !check_login().await? {
+        return Err(ServerFnError::ServerError("Not logged in.".to_string()));
+    }
+
+    let mut conn = db().await?;
+
+    match action {
+        ShoppingListActionDescriptor::Generate(source) => {
+            let all_recipes = all_recipes(&mut conn).await?;
+            let recipe = |id: u16| all_recipes.iter().find(|recipe| recipe.id == Some(id)).cloned();
+
+            let (title, recipes) = match source {
+                ShoppingListSource::Recipes(selection) => {
+                    let recipes: Vec<(_, f64)> = selection
+                        .into_iter()
+                        .filter_map(|(id, multiplier)| Some((recipe(id)?, multiplier.max(0.0))))
+                        .collect();
+                    let title = match recipes.as_slice() {
+                        [(recipe, _)] => recipe.name.clone(),
+                        recipes => format!("{} recipes", recipes.len()),
+                    };
+                    (title, recipes)
+                }
+                ShoppingListSource::Week(week_start) => {
+                    let Some(from) = parse_plan_date(&week_start) else {
+                        return Err(ServerFnError::ServerError(format!("Invalid date: {}", week_start)));
+                    };
+                    let entries = meal_plan_entries(&mut conn, from, from + chrono::Days::new(7)).await?;
+                    // Scaled from the servings the recipe is written for to the planned ones
+                    let recipes = entries
+                        .into_iter()
+                        .filter_map(|entry| {
+                            let recipe = recipe(entry.recipe_id)?;
+                            let multiplier = entry.servings as f64 / recipe_servings(&recipe);
+                            Some((recipe, multiplier))
+                        })
+                        .collect();
+                    (format!("Week of {}", from.format("%-d %B %Y")), recipes)
+                }
+            };
+
+            let items = shopping_list_items(&recipes);
+
+            let mut tx = conn.begin().await?;
+            // The old list goes only once the new one is in, so list IDs keep increasing
+            // and the polled (list ID, version) changes on every phone
+            let list_id = sqlx::query("INSERT INTO shopping_lists (title, created_at) VALUES ($1, $2);")
+                .bind(&title)
+                .bind(chrono::Local::now().format("%Y-%m-%d %H:%M").to_string())
+                .execute(&mut *tx)
+                .await?
+                .last_insert_rowid();
+            sqlx::query("DELETE FROM shopping_list_items WHERE list_id <> $1;")
+                .bind(list_id)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query("DELETE FROM shopping_lists WHERE id <> $1;")
+                .bind(list_id)
+                .execute(&mut *tx)
+                .await?;
+            for (position, item) in items.iter().enumerate() {
+                sqlx::query(
+                    "INSERT INTO shopping_list_items (list_id, aisle, item_name, quantity, recipe_names, is_verbatim, position)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7);",
+                )
+                .bind(list_id)
+                .bind(item.aisle.as_str())
+                .bind(&item.name)
+                .bind(&item.quantity)
+                .bind(serde_json::to_string(&item.recipe_names)?)
+                .bind(item.is_verbatim)
+                .bind(position as u32)
+                .execute(&mut *tx)
+                .await?;
+            }
+            tx.commit().await?;
+            log!("Shopping list {:?} was made with {:?} lines", title, items.len());
+        }
+        ShoppingListActionDescriptor::SetChecked(id, checked) => {
+            let mut tx = conn.begin().await?;
+            sqlx::query("UPDATE shopping_list_items SET checked = $1 WHERE id = $2;")
+                .bind(checked)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query("UPDATE shopping_lists SET version = version + 1 WHERE id = (SELECT list_id FROM shopping_list_items WHERE id = $1);")
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Pick recipes and how many times each, to make a new list
+#[component]
+pub fn ShoppingListForm() -> impl IntoView {
+    let shopping_list_action = use_context::<ShoppingListServerAction>()
+        .expect("To find ShoppingListServerAction in context.")
+        .0;
+    let all_recipes_light = use_context::<RecipesLightResource>()
+        .expect("To find RecipesLightResource in context.")
+        .0;
+
+    // (recipe ID, recipe name, servings multiplier)
+    let selection = RwSignal::new(Vec::<(u16, String, f64)>::new());
+
+    let on_make = move |_| {
+        let recipes = selection
+            .get_untracked()
+            .into_iter()
+            .map(|(id, _, multiplier)| (id, multiplier))
+            .collect();
+        shopping_list_action.dispatch(ShoppingListActionDescriptor::Generate(ShoppingListSource::Recipes(recipes)));
+        selection.set(vec![]);
+    };
+
+    view! {
+        <details class="shopping-list-form">
+            <summary>"New list from recipes"</summary>
+            <ul class="shopping-list-selection">
+                <For
+                    each=move || selection.get()
+                    key=|(id, _, _)| *id
+                    let:selected
+                >
+                    {
+                        let (id, name, multiplier) = selected;
+                        view! {
+                            <li class="shopping-list-selected">
+                                <span class="shopping-list-selected-name">{ name }</span>
+                                <label>
+                                    "×"
+                                    <input
+                                        type="number"
+                                        min="0.25"
+                                        step="0.25"
+                                        value=multiplier.to_string()
+                                        on:input=move |ev| {
+                                            if let Ok(value) = event_target_value(&ev).replace(',', ".").parse::<f64>() {
+                                                selection.update(|selection| {
+                                                    if let Some(selected) = selection.iter_mut().find(|(selected_id, _, _)| *selected_id == id) {
+                                                        selected.2 = value;
+                                                    }
+                                                });
+                                            }
+                                        }
+                                    />
+                                </label>
+                                <button
+                                    class="shopping-list-selected-remove"
+                                    on:click=move |_| selection.update(|selection| selection.retain(|(selected_id, _, _)| *selected_id != id))
+                                >"×"</button>
+                            </li>
+                        }
+                    }
+                </For>
+            </ul>
+            <select
+                class="shopping-list-add-recipe"
+                // Back to the placeholder once a recipe is picked
+                prop:value=move || { selection.track(); String::new() }
+                on:change=move |ev| {
+                    let Ok(id) = event_target_value(&ev).parse::<u16>() else {
+                        return;
+                    };
+                    let name = all_recipes_light
+                        .get_untracked()
+                        .and_then(Result::ok)
+                        .and_then(|recipes| recipes.into_iter().find(|recipe| recipe.id == id))
+                        .map(|recipe| recipe.name)
+                        .unwrap_or_default();
+                    selection.update(|selection| {
+                        if !selection.iter().any(|(selected_id, _, _)| *selected_id == id) {
+                            selection.push((id, name, 1.0));
+                        }
+                    });
+                }
+            >
+                <option value="">"+ Add a recipe"</option>
+                {move || all_recipes_light.get()
+                    .and_then(Result::ok)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|recipe| view! { <option value=recipe.id.to_string()>{ recipe.name }</option> })
+                    .collect_view()}
+            </select>
+            <button
+                class="shopping-list-make"
+                disabled=move || selection.read().is_empty()
+                on:click=on_make
+            >
+                "Make the list"
+            </button>
+        </details>
+    }
+}
+
+/// The current list by aisle, checked lines are shared between devices
+#[component]
+pub fn ShoppingListView() -> impl IntoView {
+    // Is logged in
+    let check_login_resource = use_context::<LoginCheckResource>()
+        .expect("Expected to find LoginCheckAction in context")
+        .0;
+    let is_logged_in = move || check_login_resource.get() == Some(true);
+
+    let shopping_list_action = use_context::<ShoppingListServerAction>()
+        .expect("To find ShoppingListServerAction in context.")
+        .0;
+    let generate_pending = shopping_list_action.pending();
+
+    // Poll the version, the list is only fetched again when it changed
+    let refresh = RwSignal::new(0_u32);
+    let _ = leptos_use::use_interval_fn(move || refresh.update(|n| *n += 1), REFRESH_INTERVAL_MS);
+    let version_resource = Resource::new(
+        move || refresh.get(),
+        |_| get_shopping_list_version(),
+    );
+    let list_version = Memo::new(move |_| version_resource.get().and_then(Result::ok).flatten());
+
+    let list_resource = Resource::new(
+        move || (list_version.get(), shopping_list_action.version().get()),
+        |_| async move {
+            get_shopping_list().await.map_err(|e| {
+                error!("ERROR: Could not get the shopping list: {:?}", e.to_string());
+                e.to_string()
+            })
+        },
+    );
+
+    view! {
+        <Show when=generate_pending>
+            <p class="shopping-list-info">"Making the list..."</p>
+        </Show>
+        <Transition
+            fallback=move || view! { <p class="shopping-list-info">"Loading the list..."</p> }
+        >
+            {move || match list_resource.get() {
+                Some(Ok(Some(list))) => {
+                    let aisles = Aisle::ALL.into_iter().filter_map(|aisle| {
+                        let items: Vec<ShoppingListItem> = list.items
+                            .iter()
+                            .filter(|item| item.aisle == aisle)
+                            .cloned()
+                            .collect();
+                        (!items.is_empty()).then(|| view! {
+                            <section class="shopping-list-aisle">
+                                <h3 class="shopping-list-aisle-name">{ aisle.label() }</h3>
+                                <ul class="shopping-list-items">
+                                    {items.into_iter().map(|item| {
+                                        let id = item.id;
+                                        let is_verbatim = item.is_verbatim;
+                                        let recipes = item.recipe_names.join(", ");
+                                        view! {
+                                            <li
+                                                class="shopping-list-item"
+                                                class:checked=item.checked
+                                                class:verbatim=is_verbatim
+                                            >
+                                                <label title=recipes.clone()>
+                                                    <input
+                                                        type="checkbox"
+                                                        prop:checked=item.checked
+                                                        disabled=move || !is_logged_in()
+                                                        on:change=move |ev| shopping_list_action.dispatch(
+                                                            ShoppingListActionDescriptor::SetChecked(id, event_target_checked(&ev))
+                                                        )
+                                                    />
+                                                    <span class="shopping-list-item-quantity">{ item.quantity }</span>
+                                                    <span class="shopping-list-item-name">{ item.name }</span>
+                                                </label>
+                                                // Unread lines keep their recipe, to check the quantity there
+                                                <Show when=move || is_verbatim>
+                                                    <span class="shopping-list-item-recipes">{ recipes.clone() }</span>
+                                                </Show>
+                                            </li>
+                                        }
+                                    }).collect_view()}
+                                </ul>
+                            </section>
+                        })
+                    }).collect_view();
+
+                    view! {
+                        <div class="shopping-list">
+                            <h2 class="shopping-list-title">{ list.title }</h2>
+                            <p class="shopping-list-date">{ format!("Made on {}", list.created_at) }</p>
+                            { aisles }
+                        </div>
+                    }.into_any()
+                },
+                Some(Ok(None)) => view! {
+                    <p class="shopping-list-info">"No shopping list yet."</p>
+                }.into_any(),
+                Some(Err(e)) => view! { <p class="shopping-list-info">{e}</p> }.into_any(),
+                None => ().into_any(),
+            }}
+        </Transition>
+    }
+}
@@ -1,8 +1,544 @@
+use crate::app::components::recipe::Recipe;
 use crate::app::elements::icons_svg::HashtagSVG;
-use crate::app::IsTagsMenuOpen;
+use crate::app::{IsTagsMenuOpen, LoginCheckResource, SelectedTagsRwSignal, TagServerAction, TagUsagesResource, ThemeColor};
 use leptos::ev::MouseEvent;
+use leptos::logging::*;
 use leptos::{leptos_dom, prelude::*};
 use crate::app::elements::popups::BODY_STOP_SCROLL_CLASS;
+use serde::{Deserialize, Serialize};
+
+/// A tag, the number of recipes using it, its color and the tag it is under.
+/// Categories like "Cuisine" are tags too, even if no recipe uses them.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TagUsage {
+    pub name: String,
+    pub count: u32,
+    pub color: Option<ThemeColor>,
+    pub parent: Option<String>,
+}
+
+/// "Cuisine › Italian"
+pub const TAG_PATH_SEPARATOR: &str = " › ";
+
+/// The color of a tag, if it has one
+pub fn tag_color(usages: &[TagUsage], name: &str) -> Option<ThemeColor> {
+    usages.iter().find(|usage| usage.name == name).and_then(|usage| usage.color)
+}
+
+/// The tag and the tags it is under, the root category first
+pub fn tag_ancestry(usages: &[TagUsage], name: &str) -> Vec<String> {
+    let mut ancestry = vec![name.to_string()];
+    while let Some(parent) = ancestry
+        .last()
+        .and_then(|tag| usages.iter().find(|usage| usage.name == *tag))
+        .and_then(|usage| usage.parent.clone())
+    {
+        // Cycles are refused by the server, but stop anyway
+        if ancestry.contains(&parent) {
+            break;
+        }
+        ancestry.push(parent);
+    }
+    ancestry.reverse();
+    ancestry
+}
+
+/// "Cuisine › Italian"
+pub fn tag_path(usages: &[TagUsage], name: &str) -> String {
+    tag_ancestry(usages, name).join(TAG_PATH_SEPARATOR)
+}
+
+/// The tag and every tag under it, a recipe with any of them has the tag
+pub fn tag_with_descendants(usages: &[TagUsage], name: &str) -> Vec<String> {
+    let mut tags = vec![name.to_string()];
+    tags.extend(
+        usages
+            .iter()
+            .filter(|usage| usage.name != name && tag_ancestry(usages, &usage.name).iter().any(|tag| tag == name))
+            .map(|usage| usage.name.clone()),
+    );
+    tags
+}
+
+/// The tags of the menu: each root category with the tags under it, then the tags outside of any category
+pub fn tag_categories(usages: &[TagUsage], tags: &[String]) -> (Vec<(String, Vec<String>)>, Vec<String>) {
+    let mut categories: Vec<(String, Vec<String>)> = vec![];
+    let mut other_tags: Vec<String> = vec![];
+
+    for tag in tags {
+        let ancestry = tag_ancestry(usages, tag);
+        if ancestry.len() == 1 {
+            other_tags.push(tag.clone());
+            continue;
+        }
+        // Every tag between the root and this one is shown too
+        let root = ancestry[0].clone();
+        let position = match categories.iter().position(|(category, _)| *category == root) {
+            Some(position) => position,
+            None => {
+                categories.push((root, vec![]));
+                categories.len() - 1
+            }
+        };
+        for ancestor in ancestry.into_iter().skip(1) {
+            if !categories[position].1.contains(&ancestor) {
+                categories[position].1.push(ancestor);
+            }
+        }
+    }
+
+    // Roots are only shown with their category
+    other_tags.retain(|tag| categories.iter().all(|(root, _)| root != tag));
+    categories.sort_by_key(|(root, _)| root.to_lowercase());
+    for (_, category_tags) in categories.iter_mut() {
+        category_tags.sort_by_key(|tag| tag_path(usages, tag).to_lowercase());
+    }
+    (categories, other_tags)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum TagActionDescriptor {
+    // Merged into `to` if it already exists
+    Rename {
+        from: String,
+        to: String,
+    },
+    // With tag name, removed from every recipe
+    Delete(String),
+    SetColor {
+        name: String,
+        color: Option<ThemeColor>,
+    },
+    // A new category is created by putting a tag under it
+    SetParent {
+        name: String,
+        parent: Option<String>,
+    },
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use super::*;
+    use crate::app::components::recipe::{JsonRecipeTags, RecipeTag};
+    use sqlx::SqliteConnection;
+
+    #[derive(sqlx::FromRow)]
+    pub struct DbRowRecipeTags {
+        pub id: u16,
+        pub recipe_tags: String,
+    }
+
+    #[derive(sqlx::FromRow)]
+    pub struct DbRowTagColor {
+        pub tag_name: String,
+        pub color: String,
+    }
+
+    #[derive(sqlx::FromRow)]
+    pub struct DbRowTagParent {
+        pub tag_name: String,
+        pub parent_name: String,
+    }
+
+    pub async fn tag_parents(conn: &mut SqliteConnection) -> Result<Vec<DbRowTagParent>, ServerFnError> {
+        Ok(sqlx::query_as::<_, DbRowTagParent>("SELECT tag_name, parent_name FROM tag_parents")
+            .fetch_all(conn)
+            .await?)
+    }
+
+    /// Whether `ancestor` is above `tag`, the walk stops on a tag seen twice
+    pub fn is_under(parents: &[DbRowTagParent], tag: &str, ancestor: &str) -> bool {
+        let mut visited: Vec<&str> = vec![];
+        let mut current = tag;
+        while let Some(row) = parents.iter().find(|row| row.tag_name == current) {
+            if row.parent_name == ancestor {
+                return true;
+            }
+            if visited.contains(&row.parent_name.as_str()) {
+                return false;
+            }
+            visited.push(&row.parent_name);
+            current = &row.parent_name;
+        }
+        false
+    }
+
+    /// The tags under themselves, through their parents
+    pub fn tags_in_cycles(parents: &[DbRowTagParent]) -> Vec<String> {
+        parents
+            .iter()
+            .filter(|row| is_under(parents, &row.tag_name, &row.tag_name))
+            .map(|row| row.tag_name.clone())
+            .collect()
+    }
+
+    /// The tag names of every recipe, by recipe ID
+    pub async fn all_recipe_tags(conn: &mut SqliteConnection) -> Result<Vec<(u16, Vec<String>)>, ServerFnError> {
+        let rows = sqlx::query_as::<_, DbRowRecipeTags>("SELECT id, recipe_tags FROM recipes")
+            .fetch_all(conn)
+            .await?;
+        rows.into_iter()
+            .map(|row| -> Result<(u16, Vec<String>), ServerFnError> {
+                let tags = serde_json::from_str::<JsonRecipeTags>(&row.recipe_tags)?
+                    .to_recipe_tags()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|tag| tag.name)
+                    .collect();
+                Ok((row.id, tags))
+            })
+            .collect()
+    }
+
+    pub async fn set_recipe_tags(conn: &mut SqliteConnection, id: u16, tags: Vec<String>) -> Result<(), ServerFnError> {
+        // No tags are stored as none, so the recipe sheet hides its tags
+        let tags = (!tags.is_empty()).then(|| tags.into_iter().map(|name| RecipeTag { name }).collect());
+        sqlx::query("UPDATE recipes SET recipe_tags = $1 WHERE id = $2;")
+            .bind(serde_json::to_string(&JsonRecipeTags::from_recipe_tags(tags))?)
+            .bind(id)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Tags with `from` replaced by `to`, or removed without `to`, without duplicates
+    pub fn replace_tag(tags: &[String], from: &str, to: Option<&str>) -> Vec<String> {
+        let mut new_tags: Vec<String> = vec![];
+        for tag in tags {
+            let tag = if tag == from { to } else { Some(tag.as_str()) };
+            if let Some(tag) = tag.filter(|tag| !new_tags.iter().any(|new_tag| new_tag == tag)) {
+                new_tags.push(tag.to_string());
+            }
+        }
+        new_tags
+    }
+
+    /// Most tags suggested for a recipe
+    const MAX_TAG_SUGGESTIONS: usize = 8;
+    // A tag must come this many times with a tag or an ingredient of the recipe
+    const MIN_CO_OCCURRENCES: u32 = 2;
+    // Below this score, a tag is just noise
+    const MIN_TAG_SCORE: f64 = 0.25;
+
+    // Singular words, see `normalize_ingredient_name`
+    const MEAT_FISH_WORDS: &[&str] = &[
+        "anchovy", "bacon", "beef", "chicken", "chorizo", "clam", "cod", "crab", "duck", "fish", "gelatin", "ham",
+        "lamb", "lard", "lobster", "mackerel", "meat", "mince", "mussel", "oyster", "pancetta", "pork", "prawn",
+        "prosciutto", "rabbit", "salmon", "sardine", "sausage", "scallop", "shrimp", "squid", "steak", "trout",
+        "tuna", "turkey", "veal", "venison",
+        "agneau", "boeuf", "bœuf", "canard", "crevette", "dinde", "jambon", "lardon", "poisson", "porc", "poulet",
+        "saumon", "thon", "veau", "viande",
+    ];
+    const ANIMAL_PRODUCT_WORDS: &[&str] = &[
+        "butter", "cheese", "cream", "egg", "feta", "honey", "mascarpone", "milk", "mozzarella", "parmesan",
+        "ricotta", "yogurt", "yoghurt",
+        "beurre", "crème", "fromage", "lait", "miel", "oeuf", "œuf",
+    ];
+    // "coconut milk" and "peanut butter" are not animal products
+    const PLANT_WORDS: &[&str] = &["almond", "coconut", "oat", "peanut", "soy", "amande", "coco", "soja"];
+
+    fn tag_names(recipe: &Recipe) -> Vec<String> {
+        recipe.tags.iter().flatten().map(|tag| tag.name.clone()).collect()
+    }
+
+    fn ingredient_names(recipe: &Recipe) -> Vec<String> {
+        use crate::app::components::ingredient_units::normalize_ingredient_name;
+
+        let mut names: Vec<String> = recipe
+            .ingredients
+            .iter()
+            .flatten()
+            .flat_map(|group| group.ingredients.iter())
+            .map(|ingredient| normalize_ingredient_name(&ingredient.content))
+            .filter(|name| !name.is_empty())
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Tags given by the ingredients alone: without meat or fish a recipe is vegetarian,
+    /// without any animal product it is vegan too
+    pub fn ingredient_rule_tags(ingredient_names: &[String]) -> Vec<&'static str> {
+        let has_word = |words: &[&str]| {
+            ingredient_names.iter().any(|name| {
+                let name_words: Vec<&str> = name.split_whitespace().collect();
+                !name_words.iter().any(|word| PLANT_WORDS.contains(word))
+                    && name_words.iter().any(|word| words.contains(word))
+            })
+        };
+        if ingredient_names.is_empty() || has_word(MEAT_FISH_WORDS) {
+            vec![]
+        } else if has_word(ANIMAL_PRODUCT_WORDS) {
+            vec!["vegetarian"]
+        } else {
+            vec!["vegan", "vegetarian"]
+        }
+    }
+
+    /// Tags for a draft recipe: the ingredient rules first, then the tags that
+    /// usually come with its tags and ingredients in the other recipes
+    pub fn suggested_tags(draft: &Recipe, recipes: &[Recipe]) -> Vec<String> {
+        let draft_tags = tag_names(draft);
+        let draft_ingredients = ingredient_names(draft);
+        let is_new_tag = |tag: &str| !draft_tags.iter().any(|draft_tag| draft_tag.to_lowercase() == tag.to_lowercase());
+
+        let other_recipes: Vec<(Vec<String>, Vec<String>)> = recipes
+            .iter()
+            .filter(|recipe| draft.id.is_none() || recipe.id != draft.id)
+            .map(|recipe| (tag_names(recipe), ingredient_names(recipe)))
+            .collect();
+
+        // Rule tags are written like the existing tags, if any
+        let mut suggestions: Vec<String> = vec![];
+        for rule_tag in ingredient_rule_tags(&draft_ingredients) {
+            let tag = other_recipes
+                .iter()
+                .flat_map(|(tags, _)| tags.iter())
+                .find(|tag| tag.to_lowercase() == rule_tag)
+                .cloned()
+                .unwrap_or_else(|| rule_tag.to_string());
+            if is_new_tag(&tag) {
+                suggestions.push(tag);
+            }
+        }
+
+        // Sum of the chances to have the tag, knowing the recipe has the tag or the ingredient.
+        // Ingredients are many, together they weigh as much as one tag.
+        let features = draft_tags
+            .iter()
+            .map(|tag| (tag, true, 1.0))
+            .chain(draft_ingredients.iter().map(|name| (name, false, 1.0 / draft_ingredients.len() as f64)));
+        let mut scores: Vec<(String, f64)> = vec![];
+        for (feature, is_tag, weight) in features {
+            let recipes_with_feature: Vec<&Vec<String>> = other_recipes
+                .iter()
+                .filter(|(tags, ingredients)| if is_tag { tags.contains(feature) } else { ingredients.contains(feature) })
+                .map(|(tags, _)| tags)
+                .collect();
+
+            let mut co_occurrences: Vec<(&String, u32)> = vec![];
+            for tag in recipes_with_feature.iter().flat_map(|tags| tags.iter()) {
+                match co_occurrences.iter_mut().find(|(other, _)| *other == tag) {
+                    Some((_, count)) => *count += 1,
+                    None => co_occurrences.push((tag, 1)),
+                }
+            }
+
+            for (tag, count) in co_occurrences {
+                if count < MIN_CO_OCCURRENCES || !is_new_tag(tag) {
+                    continue;
+                }
+                let score = weight * count as f64 / recipes_with_feature.len() as f64;
+                match scores.iter_mut().find(|(other, _)| other == tag) {
+                    Some((_, total)) => *total += score,
+                    None => scores.push((tag.clone(), score)),
+                }
+            }
+        }
+
+        scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+        for (tag, score) in scores {
+            if score >= MIN_TAG_SCORE && !suggestions.contains(&tag) {
+                suggestions.push(tag);
+            }
+        }
+        suggestions.truncate(MAX_TAG_SUGGESTIONS);
+        suggestions
+    }
+}
+
+/// Tags proposed for a recipe being edited, the most likely first
+#[server]
+pub async fn suggest_recipe_tags(recipe: Recipe) -> Result<Vec<String>, ServerFnError> {
+    use self::ssr::*;
+    use crate::app::components::recipe_server_functions::ssr::{all_recipes, db};
+
+    let mut conn = db().await?;
+    let recipes = all_recipes(&mut conn).await?;
+    Ok(suggested_tags(&recipe, &recipes))
+}
+
+/// Every tag with its usage count, sorted by name
+#[server]
+pub async fn get_tag_usages() -> Result<Vec<TagUsage>, ServerFnError> {
+    use self::ssr::*;
+    use crate::app::components::recipe_server_functions::ssr::db;
+
+    let mut conn = db().await?;
+
+    let mut usages: Vec<TagUsage> = vec![];
+    for (_, tags) in all_recipe_tags(&mut conn).await? {
+        for tag in tags {
+            match usages.iter_mut().find(|usage| usage.name == tag) {
+                Some(usage) => usage.count += 1,
+                None => usages.push(TagUsage { name: tag, count: 1, color: None, parent: None }),
+            }
+        }
+    }
+
+    for row in tag_parents(&mut conn).await? {
+        // Categories may have no recipes
+        for name in [&row.parent_name, &row.tag_name] {
+            if !usages.iter().any(|usage| usage.name == *name) {
+                usages.push(TagUsage { name: name.clone(), count: 0, color: None, parent: None });
+            }
+        }
+        if let Some(usage) = usages.iter_mut().find(|usage| usage.name == row.tag_name) {
+            usage.parent = Some(row.parent_name);
+        }
+    }
+
+    let colors = sqlx::query_as::<_, DbRowTagColor>("SELECT tag_name, color FROM tag_colors")
+        .fetch_all(&mut conn)
+        .await?;
+    for color in colors {
+        if let Some(usage) = usages.iter_mut().find(|usage| usage.name == color.tag_name) {
+            usage.color = color.color.parse().ok();
+        }
+    }
+
+    usages.sort_by_key(|usage| usage.name.to_lowercase());
+    Ok(usages)
+}
+
+#[server]
+pub async fn tag_function(action: TagActionDescriptor) -> Result<(), ServerFnError> {
+    use self::ssr::*;
+    use crate::app::components::auth::auth_server_functions::check_login;
+    use crate::app::components::recipe_server_functions::ssr::db;
+    use sqlx::Connection;
+
+    if !check_login().await? {
+        return Err(ServerFnError::ServerError("Not logged in.".to_string()));
+    }
+
+    let mut conn = db().await?;
+
+    match action {
+        TagActionDescriptor::Rename { from, to } => {
+            let to = to.trim().to_string();
+            if to.is_empty() {
+                return Err(ServerFnError::ServerError("The tag needs a name.".to_string()));
+            }
+            if to == from {
+                return Ok(());
+            }
+
+            // Every recipe or none
+            let mut tx = conn.begin().await?;
+            let mut renamed = 0;
+            for (id, tags) in all_recipe_tags(&mut tx).await? {
+                if tags.contains(&from) {
+                    set_recipe_tags(&mut tx, id, replace_tag(&tags, &from, Some(&to))).await?;
+                    renamed += 1;
+                }
+            }
+            // A merged tag keeps its own color
+            sqlx::query("UPDATE OR IGNORE tag_colors SET tag_name = $1 WHERE tag_name = $2;")
+                .bind(&to)
+                .bind(&from)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query("DELETE FROM tag_colors WHERE tag_name = $1;")
+                .bind(&from)
+                .execute(&mut *tx)
+                .await?;
+            // The tags under it follow, a merged tag keeps its own parent
+            sqlx::query("UPDATE tag_parents SET parent_name = $1 WHERE parent_name = $2;")
+                .bind(&to)
+                .bind(&from)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query("UPDATE OR IGNORE tag_parents SET tag_name = $1 WHERE tag_name = $2;")
+                .bind(&to)
+                .bind(&from)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query("DELETE FROM tag_parents WHERE tag_name = $1 OR tag_name = parent_name;")
+                .bind(&from)
+                .execute(&mut *tx)
+                .await?;
+            // Nothing is committed if a tag ends up under itself
+            let cycle = tags_in_cycles(&tag_parents(&mut tx).await?);
+            if !cycle.is_empty() {
+                return Err(ServerFnError::ServerError(format!(
+                    "Renaming {:?} to {:?} would put {} under itself.", from, to, cycle.join(", ")
+                )));
+            }
+            tx.commit().await?;
+            log!("Tag {:?} was renamed to {:?} in {} recipes", from, to, renamed);
+        }
+        TagActionDescriptor::Delete(name) => {
+            let mut tx = conn.begin().await?;
+            for (id, tags) in all_recipe_tags(&mut tx).await? {
+                if tags.contains(&name) {
+                    set_recipe_tags(&mut tx, id, replace_tag(&tags, &name, None)).await?;
+                }
+            }
+            sqlx::query("DELETE FROM tag_colors WHERE tag_name = $1;")
+                .bind(&name)
+                .execute(&mut *tx)
+                .await?;
+            // The tags under it leave the category
+            sqlx::query("DELETE FROM tag_parents WHERE tag_name = $1 OR parent_name = $1;")
+                .bind(&name)
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+            log!("Tag {:?} was deleted", name);
+        }
+        TagActionDescriptor::SetColor { name, color } => {
+            match color {
+                Some(color) => {
+                    sqlx::query(
+                        "INSERT INTO tag_colors (tag_name, color) VALUES ($1, $2)
+                        ON CONFLICT (tag_name) DO UPDATE SET color = excluded.color;",
+                    )
+                    .bind(&name)
+                    .bind(color.as_str())
+                    .execute(&mut conn)
+                    .await?;
+                }
+                None => {
+                    sqlx::query("DELETE FROM tag_colors WHERE tag_name = $1;")
+                        .bind(&name)
+                        .execute(&mut conn)
+                        .await?;
+                }
+            }
+        }
+        TagActionDescriptor::SetParent { name, parent } => {
+            match parent.map(|parent| parent.trim().to_string()).filter(|parent| !parent.is_empty()) {
+                Some(parent) => {
+                    // A tag can't be under itself, even through other tags
+                    if parent == name || is_under(&tag_parents(&mut conn).await?, &parent, &name) {
+                        return Err(ServerFnError::ServerError(format!(
+                            "{:?} is already under {:?}.", parent, name
+                        )));
+                    }
+                    sqlx::query(
+                        "INSERT INTO tag_parents (tag_name, parent_name) VALUES ($1, $2)
+                        ON CONFLICT (tag_name) DO UPDATE SET parent_name = excluded.parent_name;",
+                    )
+                    .bind(&name)
+                    .bind(&parent)
+                    .execute(&mut conn)
+                    .await?;
+                    log!("Tag {:?} is now under {:?}", name, parent);
+                }
+                None => {
+                    sqlx::query("DELETE FROM tag_parents WHERE tag_name = $1;")
+                        .bind(&name)
+                        .execute(&mut conn)
+                        .await?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
 
 #[component]
 pub fn TagList(
@@ -12,23 +548,101 @@ pub fn TagList(
     selected_tags_signal: RwSignal<Vec<String>>,
 ) -> impl IntoView {
 
+    // Colors and categories set on the tags page
+    let tag_usages = use_context::<TagUsagesResource>()
+        .expect("To find TagUsagesResource in context.")
+        .0;
+
+    // Categories with the tags under them, then the other tags
+    let menu_tags = move || {
+        let usages = tag_usages.get().and_then(|usages| usages.ok()).unwrap_or_default();
+        let (categories, other_tags) = tag_categories(&usages, &all_tags.get());
+        (usages, categories, other_tags)
+    };
+
     let tags_state = move || {
-        all_tags
-            .get()
-            .iter()
-            .map(|t| RwSignal::new((selected_tags_signal.read().contains(t), t.clone())))
+        let (_, categories, other_tags) = menu_tags();
+        categories
+            .into_iter()
+            .flat_map(|(root, category_tags)| std::iter::once(root).chain(category_tags))
+            .chain(other_tags)
+            .map(|t| RwSignal::new((selected_tags_signal.read().contains(&t), t)))
             .collect::<Vec<RwSignal<(bool, String)>>>()
     };
 
+    // Categories are collapsed until opened
+    let open_categories = RwSignal::new(Vec::<String>::new());
+
+    let tag_elem = move |name: String, label: String| {
+        let tag_state_signal = RwSignal::new((selected_tags_signal.read().contains(&name), name));
+        view_from_tag_state(tag_state_signal, label, selected_tags_signal, tag_usages)
+    };
+
     let all_tag_elems = move || {
-        // then generate the buttons
-        tags_state()
+        let (usages, categories, other_tags) = menu_tags();
+
+        let category_elems = categories
             .into_iter()
-            .map(|tag_state_signal| {
-                view_from_tag_state(tag_state_signal, selected_tags_signal)
+            .map(|(root, category_tags)| {
+                let is_open = {
+                    let root = root.clone();
+                    move || open_categories.read().contains(&root)
+                };
+                let on_toggle_click = {
+                    let root = root.clone();
+                    move |ev: MouseEvent| {
+                        ev.stop_propagation();
+                        open_categories.update(|open| {
+                            match open.iter().position(|category| *category == root) {
+                                Some(position) => { open.remove(position); },
+                                None => open.push(root.clone()),
+                            }
+                        });
+                    }
+                };
+                // Tags are labelled from their category, "Italian" or "Italian › Sicilian"
+                let category_tag_elems = category_tags
+                    .into_iter()
+                    .map(|tag| {
+                        let label = tag_ancestry(&usages, &tag)[1..].join(TAG_PATH_SEPARATOR);
+                        tag_elem(tag, label)
+                    })
+                    .collect_view();
+
+                view! {
+                    <li class="tag-category">
+                        <ul class="tag-category-header">
+                            <button
+                                class="tag-category-toggle"
+                                class:open=is_open.clone()
+                                on:click=on_toggle_click
+                            >
+                                "▸"
+                            </button>
+                            { tag_elem(root.clone(), root) }
+                        </ul>
+                        <ul
+                            class="tag-category-tags"
+                            class:collapsed=move || !is_open()
+                        >
+                            { category_tag_elems }
+                        </ul>
+                    </li>
+                }
             })
+            .collect_view();
+
+        // then generate the buttons
+        let other_tag_elems = other_tags
+            .into_iter()
+            .map(|tag| tag_elem(tag.clone(), tag))
             .rev()
-            .collect_view()
+            .collect_view();
+
+        view! {
+            { category_elems }
+            { other_tag_elems }
+        }
     };
 
     let is_tags_menu_open = use_context::<IsTagsMenuOpen>()
@@ -141,12 +755,31 @@ pub fn TagList(
 
 fn view_from_tag_state(
     tag_state_signal: RwSignal<(bool, String)>,
+    label: String,
     selected_tags_signal: RwSignal<Vec<String>>,
+    tag_usages: Resource<Result<Vec<TagUsage>, ServerFnError>>,
 ) -> AnyView {
+    let tag_style = move || {
+        let (is_selected, name) = tag_state_signal.get();
+        tag_usages
+            .get()
+            .and_then(|usages| usages.ok())
+            .and_then(|usages| tag_color(&usages, &name))
+            .map(|color| {
+                if is_selected {
+                    color.as_bg_main_color() + &color.as_alt_color()
+                } else {
+                    color.as_outline_color()
+                }
+            })
+            .unwrap_or_default()
+    };
+
     view! {
         <li class="tag-list-entry">
             <button
                 class="tag-button"
+                style=tag_style
                 class:tag-selected = move || tag_state_signal.get().0
                 on:click = move |ev: MouseEvent| {
                     ev.stop_propagation();
@@ -167,9 +800,184 @@ fn view_from_tag_state(
                     })
                 }
             >
-                { move || tag_state_signal.get().1 }
+                { label }
             </button>
         </li>
     }
     .into_any()
 }
+
+/// Every tag with its usage, to rename, merge, delete or color them
+#[component]
+pub fn TagAdminList() -> impl IntoView {
+    // Is logged in
+    let check_login_resource = use_context::<LoginCheckResource>()
+        .expect("Expected to find LoginCheckAction in context")
+        .0;
+    let is_logged_in = move || check_login_resource.get() == Some(true);
+
+    let tag_action = use_context::<TagServerAction>()
+        .expect("To find TagServerAction in context.")
+        .0;
+    let tag_usages = use_context::<TagUsagesResource>()
+        .expect("To find TagUsagesResource in context.")
+        .0;
+    let selected_tags_signal = use_context::<SelectedTagsRwSignal>()
+        .expect("To find SelectedTagsRwSignal in context.")
+        .0;
+
+    // Keep the tag selection of the home page valid
+    let rename_tag = move |from: String, to: String| {
+        let to = to.trim().to_string();
+        if to.is_empty() || to == from {
+            return;
+        }
+        selected_tags_signal.update(|tags| {
+            let new_tags = tags
+                .iter()
+                .map(|tag| if *tag == from { to.clone() } else { tag.clone() })
+                .fold(vec![], |mut new_tags: Vec<String>, tag| {
+                    if !new_tags.contains(&tag) {
+                        new_tags.push(tag);
+                    }
+                    new_tags
+                });
+            *tags = new_tags;
+        });
+        tag_action.dispatch(TagActionDescriptor::Rename { from, to });
+    };
+    let delete_tag = move |name: String| {
+        let is_confirmed = window()
+            .confirm_with_message(&format!("Remove the tag \"{}\" from every recipe?", name))
+            .unwrap_or(false);
+        if is_confirmed {
+            selected_tags_signal.update(|tags| tags.retain(|tag| *tag != name));
+            tag_action.dispatch(TagActionDescriptor::Delete(name));
+        }
+    };
+
+    view! {
+        <Transition fallback=move || view! { <p>"Loading tags..."</p> }>
+            {move || match tag_usages.get() {
+                Some(Ok(usages)) if usages.is_empty() => view! {
+                    <p class="tag-admin-info">"No recipe has tags yet."</p>
+                }.into_any(),
+                Some(Ok(usages)) => {
+                    let tag_names: Vec<String> = usages.iter().map(|usage| usage.name.clone()).collect();
+                    let parent_options = tag_names
+                        .iter()
+                        .map(|tag| view! { <option value=tag.clone()></option> })
+                        .collect_view();
+                    let rows = usages.iter().cloned().map(|usage| {
+                        let path = tag_path(&usages, &usage.name);
+                        let TagUsage { name, count, color, parent } = usage;
+                        let new_name = RwSignal::new(name.clone());
+                        // Renaming to an existing tag merges both
+                        let is_merge = {
+                            let tag_names = tag_names.clone();
+                            let name = name.clone();
+                            move || {
+                                let new_name = new_name.get();
+                                new_name.trim() != name && tag_names.iter().any(|tag| tag == new_name.trim())
+                            }
+                        };
+                        let name_for_rename = name.clone();
+                        let name_for_delete = name.clone();
+                        let name_for_color = name.clone();
+                        let name_for_parent = name.clone();
+
+                        view! {
+                            <li class="tag-admin-row">
+                                <span
+                                    class="tag-admin-name"
+                                    style=color.map(|color| color.as_outline_color()).unwrap_or_default()
+                                >
+                                    { path }
+                                </span>
+                                <span class="tag-admin-count">
+                                    { if count == 1 { "1 recipe".to_string() } else { format!("{} recipes", count) } }
+                                </span>
+                                <Show when=is_logged_in>
+                                    <select
+                                        class="tag-admin-color"
+                                        on:change={
+                                            let name = name_for_color.clone();
+                                            move |ev| {
+                                                let color = event_target_value(&ev).parse::<ThemeColor>().ok();
+                                                tag_action.dispatch(TagActionDescriptor::SetColor { name: name.clone(), color });
+                                            }
+                                        }
+                                    >
+                                        <option value="" prop:selected=color.is_none()>"No color"</option>
+                                        {ThemeColor::ALL.into_iter().map(|theme_color| view! {
+                                            <option
+                                                value=theme_color.as_str()
+                                                style=theme_color.as_visible_color()
+                                                prop:selected=color == Some(theme_color)
+                                            >
+                                                { theme_color.label() }
+                                            </option>
+                                        }).collect_view()}
+                                    </select>
+                                    <input
+                                        class="tag-admin-new-name"
+                                        type="text"
+                                        prop:value=new_name
+                                        on:input=move |ev| new_name.set(event_target_value(&ev))
+                                    />
+                                    <button
+                                        class="tag-admin-button"
+                                        on:click={
+                                            let name = name_for_rename.clone();
+                                            move |_| rename_tag(name.clone(), new_name.get_untracked())
+                                        }
+                                    >
+                                        { let is_merge = is_merge.clone(); move || if is_merge() { "Merge" } else { "Rename" } }
+                                    </button>
+                                    // Typing a new name creates the category
+                                    <input
+                                        class="tag-admin-parent"
+                                        type="text"
+                                        placeholder="Category"
+                                        list="tag-admin-parents"
+                                        prop:value=parent.unwrap_or_default()
+                                        on:change={
+                                            let name = name_for_parent.clone();
+                                            move |ev| {
+                                                let parent = Some(event_target_value(&ev)).filter(|parent| !parent.trim().is_empty());
+                                                tag_action.dispatch(TagActionDescriptor::SetParent { name: name.clone(), parent });
+                                            }
+                                        }
+                                    />
+                                    <button
+                                        class="tag-admin-button delete"
+                                        on:click={
+                                            let name = name_for_delete.clone();
+                                            move |_| delete_tag(name.clone())
+                                        }
+                                    >
+                                        "Delete"
+                                    </button>
+                                </Show>
+                            </li>
+                        }
+                    }).collect_view();
+
+                    view! {
+                        <datalist id="tag-admin-parents">
+                            { parent_options }
+                        </datalist>
+                        <ul class="tag-admin-list">
+                            { rows }
+                        </ul>
+                    }.into_any()
+                },
+                Some(Err(e)) => {
+                    error!("ERROR: Could not get the tags: {:?}", e.to_string());
+                    view! { <pre class="error">"Server Error: " { e.to_string() }</pre> }.into_any()
+                },
+                None => ().into_any(),
+            }}
+        </Transition>
+    }
+}
@@ -132,6 +132,126 @@ pub fn PrintButtonSVG(color: String) -> impl IntoView {
     }
 }
 
+#[component]
+pub fn DownloadButtonSVG(color: String) -> impl IntoView {
+    view! {
+
+        <svg
+            class="recipe-menu-icon download"
+            fill=color
+            viewBox="0 0 24 24"
+            xml:space="preserve"
+            xmlns="http://www.w3.org/2000/svg"
+            xmlns:svg="http://www.w3.org/2000/svg"
+        >
+            <path
+                fill-rule="evenodd"
+                clip-rule="evenodd"
+                d="m 12,2.5 c 0.8284,0 1.5,0.6716 1.5,1.5 v 8.3787 l 2.4393,-2.4394 c 0.5858,-0.5857 1.5356,-0.5857 2.1214,0 0.5857,0.5858 0.5857,1.5356 0,2.1214 l -5,5 c -0.5858,0.5857 -1.5356,0.5857 -2.1214,0 l -5,-5 c -0.5857,-0.5858 -0.5857,-1.5356 0,-2.1214 0.5858,-0.5857 1.5356,-0.5857 2.1214,0 L 10.5,12.3787 V 4 c 0,-0.8284 0.6716,-1.5 1.5,-1.5 z M 4,16.5 c 0.8284,0 1.5,0.6716 1.5,1.5 v 1.5 h 13 V 18 c 0,-0.8284 0.6716,-1.5 1.5,-1.5 0.8284,0 1.5,0.6716 1.5,1.5 v 3 c 0,0.8284 -0.6716,1.5 -1.5,1.5 H 4 C 3.1716,22.5 2.5,21.8284 2.5,21 v -3 c 0,-0.8284 0.6716,-1.5 1.5,-1.5 z"
+            />
+        </svg>
+
+    }
+}
+
+#[component]
+pub fn CalendarButtonSVG(color: String) -> impl IntoView {
+    view! {
+
+        <svg
+            class="recipe-menu-icon calendar"
+            fill=color
+            viewBox="0 0 24 24"
+            xml:space="preserve"
+            xmlns="http://www.w3.org/2000/svg"
+            xmlns:svg="http://www.w3.org/2000/svg"
+        >
+            <path
+                fill-rule="evenodd"
+                clip-rule="evenodd"
+                d="M 7,2 C 7.5523,2 8,2.4477 8,3 V 4 H 16 V 3 C 16,2.4477 16.4477,2 17,2 17.5523,2 18,2.4477 18,3 V 4 H 19 C 20.6569,4 22,5.3431
+                    22,7 V 19 C 22,20.6569 20.6569,22 19,22 H 5 C 3.3431,22 2,20.6569 2,19 V 7 C 2,5.3431 3.3431,4 5,4 H 6 V 3 C 6,2.4477 6.4477,2
+                    7,2 z M 4,10 v 9 c 0,0.5523 0.4477,1 1,1 h 14 c 0.5523,0 1,-0.4477 1,-1 V 10 z M 7,12 h 3 v 3 H 7 z"
+            />
+        </svg>
+
+    }
+}
+
+#[component]
+pub fn CollectionButtonSVG(color: String) -> impl IntoView {
+    view! {
+
+        <svg
+            class="recipe-menu-icon collection"
+            fill=color
+            viewBox="0 0 24 24"
+            xml:space="preserve"
+            xmlns="http://www.w3.org/2000/svg"
+            xmlns:svg="http://www.w3.org/2000/svg"
+        >
+            <path
+                fill-rule="evenodd"
+                clip-rule="evenodd"
+                d="M 7,2 H 17 C 18.6569,2 20,3.3431 20,5 V 21 C 20,21.3746 19.7907,21.7178 19.4576,21.8892 19.1245,22.0606 18.7236,22.0315
+                    18.4188,21.8137 L 12,17.2289 5.5812,21.8137 C 5.2764,22.0315 4.8755,22.0606 4.5424,21.8892 4.2093,21.7178 4,21.3746 4,21 V 5 C
+                    4,3.3431 5.3431,2 7,2 z"
+            />
+        </svg>
+
+    }
+}
+
+#[component]
+pub fn CookButtonSVG(color: String) -> impl IntoView {
+    view! {
+
+        <svg
+            class="recipe-menu-icon cook"
+            fill=color
+            viewBox="0 0 24 24"
+            xml:space="preserve"
+            xmlns="http://www.w3.org/2000/svg"
+            xmlns:svg="http://www.w3.org/2000/svg"
+        >
+            <path
+                fill-rule="evenodd"
+                clip-rule="evenodd"
+                d="M 9,2 C 9.5523,2 10,2.4477 10,3 V 5 C 10,5.5523 9.5523,6 9,6 8.4477,6 8,5.5523 8,5 V 3 C 8,2.4477 8.4477,2 9,2 z M 15,2 C
+                    15.5523,2 16,2.4477 16,3 v 2 c 0,0.5523 -0.4477,1 -1,1 -0.5523,0 -1,-0.4477 -1,-1 V 3 c 0,-0.5523 0.4477,-1 1,-1 z M 3,8 H 21
+                    c 0.5523,0 1,0.4477 1,1 0,0.5523 -0.4477,1 -1,1 h -1 v 8 c 0,2.2091 -1.7909,4 -4,4 H 8 C 5.7909,22 4,20.2091 4,18 V 10 H 3 C
+                    2.4477,10 2,9.5523 2,9 2,8.4477 2.4477,8 3,8 z"
+            />
+        </svg>
+
+    }
+}
+
+#[component]
+pub fn CookedButtonSVG(color: String) -> impl IntoView {
+    view! {
+
+        <svg
+            class="recipe-menu-icon cooked"
+            fill=color
+            viewBox="0 0 24 24"
+            xml:space="preserve"
+            xmlns="http://www.w3.org/2000/svg"
+            xmlns:svg="http://www.w3.org/2000/svg"
+        >
+            <path
+                fill-rule="evenodd"
+                clip-rule="evenodd"
+                d="M 12,2 C 17.5228,2 22,6.4772 22,12 22,17.5228 17.5228,22 12,22 6.4772,22 2,17.5228 2,12 2,6.4772 6.4772,2 12,2 z M
+                    16.7071,8.2929 C 16.3166,7.9024 15.6834,7.9024 15.2929,8.2929 L 10.5,13.0858 8.7071,11.2929 C 8.3166,10.9024 7.6834,10.9024
+                    7.2929,11.2929 6.9024,11.6834 6.9024,12.3166 7.2929,12.7071 L 9.7929,15.2071 C 10.1834,15.5976 10.8166,15.5976 11.2071,15.2071
+                    L 16.7071,9.7071 C 17.0976,9.3166 17.0976,8.6834 16.7071,8.2929 z"
+            />
+        </svg>
+
+    }
+}
+
 #[component]
 pub fn EditButtonSVG(color: String) -> impl IntoView {
     view! {
@@ -232,6 +352,96 @@ pub fn LogoutButtonSVG() -> impl IntoView {
     }
 }
 
+#[component]
+pub fn PlannerButtonSVG() -> impl IntoView {
+    view! {
+
+        <svg
+            class="planner-icon-svg settings-icon"
+            viewBox="0 0 24 24"
+            xml:space="preserve"
+            xmlns="http://www.w3.org/2000/svg"
+        >
+            <path
+                fill-rule="evenodd"
+                clip-rule="evenodd"
+                d="M 7,2 C 7.5523,2 8,2.4477 8,3 V 4 H 16 V 3 C 16,2.4477 16.4477,2 17,2 17.5523,2 18,2.4477 18,3 V 4 H 19 C 20.6569,4 22,5.3431
+                    22,7 V 19 C 22,20.6569 20.6569,22 19,22 H 5 C 3.3431,22 2,20.6569 2,19 V 7 C 2,5.3431 3.3431,4 5,4 H 6 V 3 C 6,2.4477 6.4477,2
+                    7,2 z M 4,10 v 9 c 0,0.5523 0.4477,1 1,1 h 14 c 0.5523,0 1,-0.4477 1,-1 V 10 z M 7,12 h 3 v 3 H 7 z"
+            />
+        </svg>
+
+    }
+}
+
+#[component]
+pub fn PantryButtonSVG() -> impl IntoView {
+    view! {
+
+        <svg
+            class="pantry-icon-svg settings-icon"
+            viewBox="0 0 24 24"
+            xml:space="preserve"
+            xmlns="http://www.w3.org/2000/svg"
+        >
+            <path
+                fill-rule="evenodd"
+                clip-rule="evenodd"
+                d="M 7,1 h 10 c 0.5523,0 1,0.4477 1,1 v 2 c 0,0.5523 -0.4477,1 -1,1 H 7 C 6.4477,5 6,4.5523 6,4 V 2 C 6,1.4477 6.4477,1 7,1
+                    z M 6.5,6 h 11 C 19.433,6 21,7.567 21,9.5 V 19 c 0,2.2091 -1.7909,4 -4,4 H 7 C 4.7909,23 3,21.2091 3,19 V 9.5 C 3,7.567
+                    4.567,6 6.5,6 z M 7,11 v 6 h 10 v -6 z"
+            />
+        </svg>
+
+    }
+}
+
+#[component]
+pub fn TagsButtonSVG() -> impl IntoView {
+    view! {
+
+        <svg
+            class="tags-icon-svg settings-icon"
+            viewBox="0 0 24 24"
+            xml:space="preserve"
+            xmlns="http://www.w3.org/2000/svg"
+        >
+            <path
+                fill-rule="evenodd"
+                clip-rule="evenodd"
+                d="M 3,3 H 11 C 11.2652,3 11.5196,3.1054 11.7071,3.2929 L 20.7071,12.2929 C 21.0976,12.6834 21.0976,13.3166 20.7071,13.7071
+                    L 13.7071,20.7071 C 13.3166,21.0976 12.6834,21.0976 12.2929,20.7071 L 3.2929,11.7071 C 3.1054,11.5196 3,11.2652 3,11 V 4 C
+                    3,3.4477 3.4477,3 4,3 z M 7.5,6 C 6.6716,6 6,6.6716 6,7.5 6,8.3284 6.6716,9 7.5,9 8.3284,9 9,8.3284 9,7.5 9,6.6716 8.3284,6
+                    7.5,6 z"
+            />
+        </svg>
+
+    }
+}
+
+#[component]
+pub fn ShoppingButtonSVG() -> impl IntoView {
+    view! {
+
+        <svg
+            class="shopping-icon-svg settings-icon"
+            viewBox="0 0 24 24"
+            xml:space="preserve"
+            xmlns="http://www.w3.org/2000/svg"
+        >
+            <path
+                fill-rule="evenodd"
+                clip-rule="evenodd"
+                d="M 1,2.5 C 1,1.6716 1.6716,1 2.5,1 h 1.2 c 1.05,0 1.96,0.72 2.2,1.74 L 6.1,4 H 21 c 0.97,0 1.68,0.91 1.45,1.85
+                    l -1.8,7.2 C 20.43,13.91 19.65,14.5 18.76,14.5 H 8.4 l 0.3,1.5 H 19 c 0.8284,0 1.5,0.6716 1.5,1.5 0,0.8284 -0.6716,1.5
+                    -1.5,1.5 H 7.5 C 6.78,19 6.16,18.49 6.03,17.79 L 3.5,4 h -1 C 1.6716,4 1,3.3284 1,2.5 z M 9,20 a 1.75,1.75 0 1 1 0,3.5
+                    1.75,1.75 0 0 1 0,-3.5 z m 9,0 a 1.75,1.75 0 1 1 0,3.5 1.75,1.75 0 0 1 0,-3.5 z"
+            />
+        </svg>
+
+    }
+}
+
 #[component]
 pub fn BackupButtonSVG() -> impl IntoView {
     view! {
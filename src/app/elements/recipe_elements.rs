@@ -1,8 +1,13 @@
 use crate::app::*;
-use components::recipe_sheets::RecipeSignals;
+use components::collections::CollectionMembership;
+use components::cook_log::CookedItForm;
+use components::meal_planner::AddToMealPlan;
+use components::recipe_export::RecipeExportFormat;
+use components::recipe_sheets::{IngredientGroupSignals, RecipeSignals};
+use components::tags::suggest_recipe_tags;
 use elements::icons_svg::{
-    BackButtonSVG, BackupButtonSVG, CrossButtonSVG, EditButtonSVG, LogoutButtonSVG, PlusIconSVG,
-    PrintButtonSVG, RemoveSVG, SortSVG, SortUpDownVG, DeleteButtonSVG,
+    BackButtonSVG, BackupButtonSVG, CalendarButtonSVG, CollectionButtonSVG, CookButtonSVG, CookedButtonSVG, CrossButtonSVG, DownloadButtonSVG, EditButtonSVG, LogoutButtonSVG,
+    PantryButtonSVG, PlannerButtonSVG, TagsButtonSVG, PlusIconSVG, PrintButtonSVG, ShoppingButtonSVG, RemoveSVG, SortSVG, SortUpDownVG, DeleteButtonSVG,
 };
 use leptos::ev::MouseEvent;
 use gloo_timers::callback::Timeout;
@@ -35,6 +40,10 @@ pub fn RecipeMenu(
         .0;
 
     let menu_open = RwSignal::new(false);
+    let download_open = RwSignal::new(false);
+    let plan_open = RwSignal::new(false);
+    let cooked_open = RwSignal::new(false);
+    let collections_open = RwSignal::new(false);
 
     // Recipe Menu ref
     let recipe_menu_div_ref = NodeRef::<leptos::html::Div>::new();
@@ -152,6 +161,96 @@ pub fn RecipeMenu(
                                     <p class="recipe-menu-text" >"Print"</p>
                                 </button>
 
+                                // Button Cook
+                                <button
+                                    style=move || { color.as_alt_color() }
+                                    class="recipe-menu-option"
+                                    on:click=move |ev| {
+                                        ev.stop_propagation();
+                                        let cook_path = "/recipe/".to_owned() + &recipe_id.to_string() + "/cook";
+                                        let navigate = leptos_router::hooks::use_navigate();
+                                        navigate(&cook_path, Default::default());
+                                    }
+                                >
+                                    <CookButtonSVG color=color.alt_color() />
+                                    <p class="recipe-menu-text" >"Cook"</p>
+                                </button>
+
+                                // Button Download
+                                <button
+                                    style=move || { color.as_alt_color() }
+                                    class="recipe-menu-option"
+                                    class:selected=download_open
+                                    on:click=move |ev| {
+                                        ev.stop_propagation();
+                                        // Choose the format first
+                                        plan_open.set(false);
+                                        cooked_open.set(false);
+                                        collections_open.set(false);
+                                        download_open.update(|b| *b = !*b);
+                                    }
+                                >
+                                    <DownloadButtonSVG color=color.alt_color() />
+                                    <p class="recipe-menu-text" >"Download"</p>
+                                </button>
+
+                                // Button Plan
+                                <button
+                                    style=move || { color.as_alt_color() }
+                                    class="recipe-menu-option"
+                                    class:unavailable=move || { check_login_resource.get() != Some(true) }
+                                    class:selected=plan_open
+                                    on:click=move |ev: MouseEvent| {
+                                        ev.stop_propagation();
+                                        // Choose the day first
+                                        download_open.set(false);
+                                        cooked_open.set(false);
+                                        collections_open.set(false);
+                                        plan_open.update(|b| *b = !*b);
+                                    }
+                                >
+                                    <CalendarButtonSVG color=color.alt_color() />
+                                    <p class="recipe-menu-text" >"Plan"</p>
+                                </button>
+
+                                // Button Cooked it
+                                <button
+                                    style=move || { color.as_alt_color() }
+                                    class="recipe-menu-option"
+                                    class:unavailable=move || { check_login_resource.get() != Some(true) }
+                                    class:selected=cooked_open
+                                    on:click=move |ev: MouseEvent| {
+                                        ev.stop_propagation();
+                                        // Rate it first
+                                        download_open.set(false);
+                                        plan_open.set(false);
+                                        collections_open.set(false);
+                                        cooked_open.update(|b| *b = !*b);
+                                    }
+                                >
+                                    <CookedButtonSVG color=color.alt_color() />
+                                    <p class="recipe-menu-text" >"Cooked it"</p>
+                                </button>
+
+                                // Button Collections
+                                <button
+                                    style=move || { color.as_alt_color() }
+                                    class="recipe-menu-option"
+                                    class:unavailable=move || { check_login_resource.get() != Some(true) }
+                                    class:selected=collections_open
+                                    on:click=move |ev: MouseEvent| {
+                                        ev.stop_propagation();
+                                        // Choose the collections first
+                                        download_open.set(false);
+                                        plan_open.set(false);
+                                        cooked_open.set(false);
+                                        collections_open.update(|b| *b = !*b);
+                                    }
+                                >
+                                    <CollectionButtonSVG color=color.alt_color() />
+                                    <p class="recipe-menu-text" >"Save"</p>
+                                </button>
+
                                 // Button Delete
                                 <button
                                     style=move || { color.as_alt_color() }
@@ -174,6 +273,63 @@ pub fn RecipeMenu(
                                 </button>
 
                             </div>
+
+                            // Download formats
+                            <div
+                                class="recipe-menu-download-formats"
+                                class:menu-closed=move || !(menu_open.get() && download_open.get())
+                            >
+                                {RecipeExportFormat::ALL.into_iter().map(|format| view! {
+                                    <button
+                                        style=move || { color.as_alt_color() }
+                                        class="recipe-menu-download-format"
+                                        on:click=move |ev| {
+                                            ev.stop_propagation();
+                                            menu_open.set(false);
+                                            download_open.set(false);
+                                            // Served as an attachment, so the page stays
+                                            let download_path = format.url(recipe_id);
+                                            let window = web_sys::window().expect("window should be available");
+                                            window
+                                                .location()
+                                                .set_href(&download_path)
+                                                .unwrap_or_else(|_| error!("Could not start the download."));
+                                        }
+                                    >
+                                        { format.label() }
+                                    </button>
+                                }).collect_view()}
+                            </div>
+
+                            // Add to the meal planner
+                            <div
+                                class="recipe-menu-plan"
+                                class:menu-closed=move || !(menu_open.get() && plan_open.get())
+                            >
+                                <Show when=move || check_login_resource.get() == Some(true)>
+                                    <AddToMealPlan recipe_id=recipe_id />
+                                </Show>
+                            </div>
+
+                            // Log that it was cooked
+                            <div
+                                class="recipe-menu-cooked"
+                                class:menu-closed=move || !(menu_open.get() && cooked_open.get())
+                            >
+                                <Show when=move || check_login_resource.get() == Some(true)>
+                                    <CookedItForm recipe_id=recipe_id />
+                                </Show>
+                            </div>
+
+                            // Favorite and collections
+                            <div
+                                class="recipe-menu-collections"
+                                class:menu-closed=move || !(menu_open.get() && collections_open.get())
+                            >
+                                <Show when=move || check_login_resource.get() == Some(true)>
+                                    <CollectionMembership recipe_id=recipe_id />
+                                </Show>
+                            </div>
                 
                         </div>
                     }
@@ -194,21 +350,22 @@ pub fn RecipeMenu(
         let is_new_recipe = is_new_recipe.expect("Expected is_new_recipe to be provided.");
 
         let recipe_signals = recipe_signals.expect("Expected recipe_signals to be provided.");
-        let (name_signal, _, _, _, _) = recipe_signals.get_untracked();
+        let (name_signal, _, _, _, _, _) = recipe_signals.get_untracked();
 
         let on_save_click = move |ev: MouseEvent| {
             ev.stop_propagation();
             // Get recipe
             let signals = recipe_signals.get_untracked();
             // Gather recipe
-            use components::recipe_sheets::fetch_entries_from_signals;
+            use components::recipe_sheets::{fetch_entries_from_signals, fetch_ingredient_groups_from_signals};
             let mut updated_recipe: Recipe = Recipe {
                 id: recipe_id,
                 name: signals.0.clone().get_untracked(),
                 tags: fetch_entries_from_signals(signals.1.get_untracked()),
-                ingredients: fetch_entries_from_signals(signals.2.get_untracked()),
-                instructions: signals.3.get_untracked(),
+                ingredients: fetch_ingredient_groups_from_signals(signals.2.get_untracked()),
+                instructions: fetch_entries_from_signals(signals.3.get_untracked()),
                 notes: fetch_entries_from_signals(signals.4.get_untracked()),
+                source: signals.5.get_untracked().cleaned(),
             };
 
             // Check recipe name
@@ -345,7 +502,7 @@ pub fn IngredientMultiplier(
                 class:not-valid=move || !is_input_valid.get()
                 style=move || color.get().as_visible_color()
                 node_ref=mult_ref
-                value="1"
+                value=mult.get_untracked().to_string()
                 placeholder=""
                 on:input=move |ev| {
                     let value = event_target_value(&ev);
@@ -376,12 +533,15 @@ pub fn EditableEntryList<T>(
     entry_type: RecipeEntryType,
     rw_entries: RwSignal<Vec<(u16, ArcRwSignal<T>)>>,
     theme_color: RwSignal<ThemeColor>,
+    /// Hide the list title, when the list is part of a bigger section
+    #[prop(optional)] hide_title: bool,
 ) -> impl IntoView
 where
     T: RecipeEntry<S = ArcRwSignal<T>>,
 {
     let (entry_type_title, style_class) = entry_type.title_and_class();
     let style_class_clone = style_class.clone();
+    let title_class = style_class.clone();
 
     // dirty signal
     let is_page_dirty = use_context::<IsPageDirtySignal>()
@@ -407,13 +567,17 @@ where
 
         <div class={style_class.clone() + " container editable list"}>
 
-            <h3
-                id="field-title"
-                class=style_class.clone()
-                style=move || theme_color.get().as_visible_color()
+            <Show
+                when=move || !hide_title
             >
-                { entry_type_title }
-            </h3>
+                <h3
+                    id="field-title"
+                    class=title_class.clone()
+                    style=move || theme_color.get().as_visible_color()
+                >
+                    { entry_type_title.clone() }
+                </h3>
+            </Show>
 
             <ul class=style_class.clone() >
 
@@ -514,28 +678,203 @@ where
 }
 
 #[component]
-pub fn EditableInstructions(
-    entry_type: RecipeEntryType,
-    entry_signal: RwSignal<RecipeInstruction>,
+pub fn EditableSource(
+    rw_source: RwSignal<RecipeSource>,
     theme_color: RwSignal<ThemeColor>,
 ) -> impl IntoView {
-    let (entry_type_title, style_class) = entry_type.title_and_class();
+
+    // (class, placeholder, getter, setter) for each field
+    type SourceField = (&'static str, &'static str, fn(&RecipeSource) -> String, fn(&mut RecipeSource, String));
+    let fields: [SourceField; 4] = [
+        ("source url",    "Website URL", |s| s.url.clone(),    |s, v| s.url = v),
+        ("source author", "Author",      |s| s.author.clone(), |s, v| s.author = v),
+        ("source book",   "Book title",  |s| s.book.clone(),   |s, v| s.book = v),
+        ("source page",   "Page",        |s| s.page.clone(),   |s, v| s.page = v),
+    ];
 
     view! {
-        <div class={style_class.clone() + " container editable"}>
+        <div class="source container editable">
+
+            <h3
+                id="field-title"
+                class="source"
+                style=move || theme_color.get().as_visible_color()
+            >
+                "Source"
+            </h3>
+
+            <div class="editable-source">
+                { fields
+                    .into_iter()
+                    .map(|(class, placeholder, get_field, set_field)| view! {
+                        <input
+                            id="text-input"
+                            class=class
+                            type="text"
+                            placeholder=placeholder
+                            // get_untracked() because this is only initial value
+                            value=get_field(&rw_source.get_untracked())
+                            on:input=move |ev| {
+                                let value = event_target_value(&ev);
+                                rw_source.update(|source| set_field(source, value));
+                            }
+                        />
+                    })
+                    .collect_view()
+                }
+            </div>
+
+        </div>
+    }
+}
+
+#[component]
+pub fn EditableIngredientGroups(
+    rw_groups: RwSignal<Vec<(u16, IngredientGroupSignals)>>,
+    theme_color: RwSignal<ThemeColor>,
+) -> impl IntoView {
+    let (entry_type_title, style_class) = RecipeEntryType::Ingredients.title_and_class();
+
+    // dirty signal
+    let is_page_dirty = use_context::<IsPageDirtySignal>()
+        .expect("Expected to find IsPageDirtySignal in context")
+        .0;
+
+    // Counter to assign new IDs
+    let id_counter: RwSignal<u16> = RwSignal::new(
+        rw_groups.read().len().try_into().unwrap()
+    );
+
+    // Add Group closure
+    let add_group = move |_| {
+        let new_group = IngredientGroupSignals {
+            name: RwSignal::new("".to_string()),
+            ingredients: RwSignal::new(vec![(0, ArcRwSignal::new(RecipeIngredient::default()))]),
+        };
+        rw_groups.update(move |groups| {
+            groups.push((id_counter.get(), new_group));
+        });
+        id_counter.update(|x| *x += 1);
+        is_page_dirty.set(true);
+    };
+
+    // Move a group up (-1) or down (+1)
+    let move_group = move |group_id: u16, offset: isize| {
+        rw_groups.update(|groups| {
+            if let Some(index) = groups.iter().position(|(id, _)| *id == group_id) {
+                let new_index = index as isize + offset;
+                if new_index >= 0 && (new_index as usize) < groups.len() {
+                    groups.swap(index, new_index as usize);
+                }
+            }
+        });
+    };
+
+    view! {
+
+        <div class={style_class.clone() + " container editable groups"}>
 
             <h3
                 id="field-title"
                 class=style_class.clone()
                 style=move || theme_color.get().as_visible_color()
             >
-                {entry_type_title}
+                { entry_type_title }
             </h3>
 
-            <li class={style_class.clone()} id="entry-li">
-                { RecipeInstruction::into_editable_view(entry_signal, None) }
-            </li>
+            <For
+                each=move || rw_groups.get()
+                key=|group| group.0
+                children=move |(id, group)| {
+
+                    let is_first = move || {
+                        rw_groups.read().first().is_some_and(|(first_id, _)| *first_id == id)
+                    };
+                    let is_last = move || {
+                        rw_groups.read().last().is_some_and(|(last_id, _)| *last_id == id)
+                    };
+                    let is_alone = move || rw_groups.read().len() < 2;
+
+                    view! {
+                        <div class="ingredient-group">
+
+                            <div class="ingredient-group-header">
+                                <input
+                                    class=          "ingredients group-name"
+                                    type=           "text"
+                                    id=             "text-input"
+                                    placeholder=    "Group name (optional)"
+                                    value=          group.name.get_untracked()
+                                    on:input=move |ev| {
+                                        group.name.set(event_target_value(&ev));
+                                    }
+                                />
+
+                                <Show
+                                    when=move || !is_first()
+                                >
+                                    <button
+                                        class="ingredient-group-button"
+                                        on:click=move |ev| {
+                                            ev.stop_propagation();
+                                            move_group(id, -1);
+                                        }
+                                    >
+                                        <SortUpDownVG is_up=true />
+                                    </button>
+                                </Show>
+
+                                <Show
+                                    when=move || !is_last()
+                                >
+                                    <button
+                                        class="ingredient-group-button"
+                                        on:click=move |ev| {
+                                            ev.stop_propagation();
+                                            move_group(id, 1);
+                                        }
+                                    >
+                                        <SortUpDownVG is_up=false />
+                                    </button>
+                                </Show>
+
+                                <Show
+                                    when=move || !is_alone()
+                                >
+                                    <button
+                                        class="ingredient-group-button delete"
+                                        on:click=move |ev| {
+                                            ev.stop_propagation();
+                                            rw_groups.update(|groups| {
+                                                groups.retain(|(group_id, _)| *group_id != id);
+                                            });
+                                        }
+                                    >
+                                        <CrossButtonSVG
+                                            add_class="delete-entry-icon-svg".to_string()
+                                        />
+                                    </button>
+                                </Show>
+                            </div>
 
+                            <EditableEntryList
+                                rw_entries=         group.ingredients
+                                entry_type=         RecipeEntryType::Ingredients
+                                theme_color=        theme_color
+                                hide_title=         true
+                            />
+                        </div>
+                    }
+                }
+            />
+
+            <button
+                class="add-group-button"
+                on:click=add_group
+            >
+                <PlusIconSVG add_class="add-button".to_string() />
+                <span>"Group"</span>
+            </button>
         </div>
     }
     .into_any()
@@ -544,6 +883,9 @@ pub fn EditableInstructions(
 #[component]
 pub fn EditableTags(
     rw_entries: RwSignal<Vec<(u16, ArcRwSignal<RecipeTag>)>>,
+    // Ingredients of the recipe, to suggest tags
+    rw_groups: RwSignal<Vec<(u16, IngredientGroupSignals)>>,
+    recipe_id: Option<u16>,
     theme_color: RwSignal<ThemeColor>,
 ) -> impl IntoView {
     let (entry_type_title, style_class) = RecipeEntryType::Tag.title_and_class();
@@ -583,6 +925,29 @@ pub fn EditableTags(
 
     let current_tag_field = RwSignal::new("".to_string());
 
+    // The recipe as it is being edited, the server suggests tags for it
+    let draft_recipe = Signal::derive(move || Recipe {
+        id: recipe_id,
+        tags: Some(rw_entries.get().iter().map(|(_, tag_signal)| tag_signal.get()).collect()),
+        ingredients: Some(
+            rw_groups
+                .get()
+                .iter()
+                .map(|(_, group)| RecipeIngredientGroup {
+                    name: group.name.get(),
+                    ingredients: group.ingredients.get().iter().map(|(_, entry)| entry.get()).collect(),
+                })
+                .collect(),
+        ),
+        ..Default::default()
+    });
+    // Wait for the typing to stop
+    let debounced_draft_recipe = leptos_use::signal_debounced(draft_recipe, 1000.0);
+    let suggested_tags_resource = Resource::new(
+        move || debounced_draft_recipe.get(),
+        suggest_recipe_tags,
+    );
+
     view! {
 
         <div class={style_class.clone() + " container editable list"}>
@@ -696,8 +1061,20 @@ pub fn EditableTags(
                             .collect::<Vec<String>>();
                         
                         // Filter tags to show in suggestion
-                        // get all tags
-                        let mut tags_to_suggest = all_tags.get();
+                        // tags suggested for this recipe first
+                        let suggested_tags = suggested_tags_resource
+                            .get()
+                            .and_then(|res| match res {
+                                Ok(tags) => Some(tags),
+                                Err(e) => {
+                                    error!("ERROR: Could not suggest tags: {}", e.to_string());
+                                    None
+                                }
+                            })
+                            .unwrap_or_default();
+                        // then all tags
+                        let mut tags_to_suggest = suggested_tags.clone();
+                        tags_to_suggest.extend(all_tags.get().into_iter().filter(|tag| !suggested_tags.contains(tag)));
                         // remove current tags from suggestions
                         tags_to_suggest.retain(|tag| { !current_tags.contains(tag) });
                         // get current search bar input
@@ -712,9 +1089,11 @@ pub fn EditableTags(
                                 .into_iter()
                                 .map(|tag| {
                                     let tag_name = tag.clone();
+                                    let is_recommended = suggested_tags.contains(&tag);
                                     view! {
                                         <button
                                             class="tag-suggested"
+                                            class:recommended=is_recommended
                                             on:click=move |ev| {
                                                 ev.stop_propagation();
                                                 add_entry(tag_name.clone());
@@ -956,6 +1335,94 @@ pub fn SettingsMenu() -> impl IntoView {
                         </button>
                     </Show>
 
+                    // Planner
+                    <Show
+                        when=move || page_name.get() != "Planner"
+                    >
+                        <button
+                            class="settings-button planner"
+                            on:click=move |ev| {
+                                ev.stop_propagation();
+                                ev.prevent_default();
+                                is_settings_menu_open.set(false);
+
+                                let navigate = leptos_router::hooks::use_navigate();
+                                navigate("/planner", Default::default());
+                            }
+                        >
+                            <PlannerButtonSVG/>
+                            <p class="settings-button-text planner" >
+                                "Planner"
+                            </p>
+                        </button>
+                    </Show>
+
+                    // Shopping List
+                    <Show
+                        when=move || page_name.get() != "Shopping List"
+                    >
+                        <button
+                            class="settings-button shopping"
+                            on:click=move |ev| {
+                                ev.stop_propagation();
+                                ev.prevent_default();
+                                is_settings_menu_open.set(false);
+
+                                let navigate = leptos_router::hooks::use_navigate();
+                                navigate("/shopping-list", Default::default());
+                            }
+                        >
+                            <ShoppingButtonSVG/>
+                            <p class="settings-button-text shopping" >
+                                "Shopping"
+                            </p>
+                        </button>
+                    </Show>
+
+                    // Pantry
+                    <Show
+                        when=move || page_name.get() != "Pantry"
+                    >
+                        <button
+                            class="settings-button pantry"
+                            on:click=move |ev| {
+                                ev.stop_propagation();
+                                ev.prevent_default();
+                                is_settings_menu_open.set(false);
+
+                                let navigate = leptos_router::hooks::use_navigate();
+                                navigate("/pantry", Default::default());
+                            }
+                        >
+                            <PantryButtonSVG/>
+                            <p class="settings-button-text pantry" >
+                                "Pantry"
+                            </p>
+                        </button>
+                    </Show>
+
+                    // Tags
+                    <Show
+                        when=move || page_name.get() != "Tags"
+                    >
+                        <button
+                            class="settings-button tags"
+                            on:click=move |ev| {
+                                ev.stop_propagation();
+                                ev.prevent_default();
+                                is_settings_menu_open.set(false);
+
+                                let navigate = leptos_router::hooks::use_navigate();
+                                navigate("/tags", Default::default());
+                            }
+                        >
+                            <TagsButtonSVG/>
+                            <p class="settings-button-text tags" >
+                                "Tags"
+                            </p>
+                        </button>
+                    </Show>
+
                     // Logout
                     <button
                         class="settings-button logout"
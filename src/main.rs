@@ -11,6 +11,11 @@ async fn main() -> std::io::Result<()> {
     use home_cook_book::app::*;
     use home_cook_book::app::components::auth::auth_utils::SharedLoginStates;
     use home_cook_book::app::components::recipe_server_functions::ssr::*;
+    use home_cook_book::app::components::recipe_images::ssr::serve_recipe_image;
+    use home_cook_book::app::components::recipe_export::ssr::{serve_book_export, serve_recipe_export};
+    use home_cook_book::app::components::recipe_pdf::ssr::serve_book_pdf;
+    use home_cook_book::app::components::backup::ssr::serve_backup_download;
+    use home_cook_book::app::components::auto_backup::ssr::{run_auto_backups, serve_auto_backup, AutoBackupConfig};
     
 
     let mut conn = db().await.expect("couldn't connect to DB");
@@ -29,6 +34,9 @@ async fn main() -> std::io::Result<()> {
     // Initializing login states
     let states = SharedLoginStates::init_states();
 
+    // Automatic backups, in the background
+    tokio::spawn(run_auto_backups(AutoBackupConfig::load()));
+
     HttpServer::new(move || {
         let leptos_options = &conf.leptos_options;
         let site_root = leptos_options.site_root.clone().to_string();
@@ -42,6 +50,18 @@ async fn main() -> std::io::Result<()> {
             .service(Files::new("/assets", &site_root))
             // serve the favicon from /favicon.ico
             .service(favicon)
+            // serve the recipe images, to logged in users only
+            .service(serve_recipe_image)
+            // serve single recipe downloads
+            .service(serve_recipe_export)
+            // serve whole book downloads, zipped
+            .service(serve_book_export)
+            // serve the PDF books
+            .service(serve_book_pdf)
+            // serve the JSON backup as a file
+            .service(serve_backup_download)
+            // serve the automatic backups, to logged in users only
+            .service(serve_auto_backup)
             .leptos_routes(routes, {
                 let leptos_options = leptos_options.clone();
                 move || {